@@ -0,0 +1,394 @@
+use num_traits::Float;
+
+use crate::rolling::EwmMoments;
+
+/// A structure that computes exponentially weighted statistics over paired values.
+///
+/// `EwmPairedStatistics<T>` is the exponentially weighted counterpart of
+/// [`PairedStatistics`](crate::PairedStatistics). Instead of a fixed window of equally
+/// weighted pairs, every pair is weighted by `(1 - alpha)^t` where `t` is its age, so
+/// covariance, correlation and beta adapt smoothly to changing relationships. The weighting
+/// and bias correction follow pandas `ewm().cov()` and `ewm().corr()`.
+///
+/// State is O(1) and every update is O(1), independent of how long the series runs.
+#[derive(Debug, Clone)]
+pub struct EwmPairedStatistics<T> {
+    moments_x: EwmMoments<T>,
+    moments_y: EwmMoments<T>,
+    /// Weighted sum of co-deviations
+    comoment: T,
+}
+
+impl<T> EwmPairedStatistics<T>
+where
+    T: Default + Clone + Float,
+{
+    /// Creates a new `EwmPairedStatistics` instance with the specified smoothing factor.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The smoothing factor, `0 < alpha <= 1`
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The `EwmPairedStatistics` instance
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alpha` is outside of `(0, 1]`
+    pub fn new(alpha: T) -> Self {
+        Self {
+            moments_x: EwmMoments::new(alpha),
+            moments_y: EwmMoments::new(alpha),
+            comoment: T::zero(),
+        }
+    }
+
+    /// Creates a new `EwmPairedStatistics` instance from a span, `alpha = 2 / (span + 1)`
+    ///
+    /// # Arguments
+    ///
+    /// * `span` - The span of the decay, `span >= 1`
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The `EwmPairedStatistics` instance
+    ///
+    /// # Panics
+    ///
+    /// Panics if `span` is less than 1
+    pub fn with_span(span: T) -> Self {
+        Self::new(EwmMoments::alpha_from_span(span))
+    }
+
+    /// Creates a new `EwmPairedStatistics` instance from a half-life, `alpha = 1 - exp(-ln(2) / halflife)`
+    ///
+    /// # Arguments
+    ///
+    /// * `halflife` - The number of observations after which a weight halves, `halflife > 0`
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The `EwmPairedStatistics` instance
+    ///
+    /// # Panics
+    ///
+    /// Panics if `halflife` is not positive
+    pub fn with_halflife(halflife: T) -> Self {
+        Self::new(EwmMoments::alpha_from_halflife(halflife))
+    }
+
+    /// Creates a new `EwmPairedStatistics` instance from a center of mass, `alpha = 1 / (1 + com)`
+    ///
+    /// # Arguments
+    ///
+    /// * `com` - The center of mass of the weights, `com >= 0`
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The `EwmPairedStatistics` instance
+    ///
+    /// # Panics
+    ///
+    /// Panics if `com` is negative
+    pub fn with_com(com: T) -> Self {
+        Self::new(EwmMoments::alpha_from_com(com))
+    }
+
+    /// Returns the smoothing factor
+    ///
+    /// # Returns
+    ///
+    /// * `T` - The smoothing factor
+    pub const fn alpha(&self) -> T {
+        self.moments_x.alpha()
+    }
+
+    /// Returns whether the weights are adjusted
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the weights are normalized over the full history
+    pub const fn adjust(&self) -> bool {
+        self.moments_x.adjust()
+    }
+
+    /// Sets whether the weights are adjusted, should be set before the first pair is added
+    ///
+    /// # Arguments
+    ///
+    /// * `adjust` - True to normalize the weights over the full history (pandas `adjust=True`),
+    ///   false for the recursive form (pandas `adjust=False`)
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub const fn set_adjust(&mut self, adjust: bool) -> &mut Self {
+        self.moments_x.set_adjust(adjust);
+        self.moments_y.set_adjust(adjust);
+        self
+    }
+
+    /// Returns the Delta Degrees of Freedom
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - The Delta Degrees of Freedom
+    pub const fn ddof(&self) -> bool {
+        self.moments_x.ddof()
+    }
+
+    /// Sets the Delta Degrees of Freedom
+    ///
+    /// When set, the covariance is scaled by `(Σw)² / ((Σw)² - Σw²)` (pandas `bias=False`).
+    ///
+    /// # Arguments
+    ///
+    /// * `ddof` - The Delta Degrees of Freedom
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub const fn set_ddof(&mut self, ddof: bool) -> &mut Self {
+        self.moments_x.set_ddof(ddof);
+        self.moments_y.set_ddof(ddof);
+        self
+    }
+
+    /// Returns the minimum number of observations required before values are reported
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The minimum number of observations
+    pub const fn min_periods(&self) -> usize {
+        self.moments_x.min_periods()
+    }
+
+    /// Sets the minimum number of observations required before values are reported
+    ///
+    /// # Arguments
+    ///
+    /// * `min_periods` - The minimum number of observations
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub const fn set_min_periods(&mut self, min_periods: usize) -> &mut Self {
+        self.moments_x.set_min_periods(min_periods);
+        self.moments_y.set_min_periods(min_periods);
+        self
+    }
+
+    /// Returns the number of pairs seen so far
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of pairs
+    pub const fn count(&self) -> usize {
+        self.moments_x.count()
+    }
+
+    /// Resets the statistics
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub fn reset(&mut self) -> &mut Self {
+        self.moments_x.reset();
+        self.moments_y.reset();
+        self.comoment = T::zero();
+        self
+    }
+
+    /// Updates the paired statistical calculations with a new value pair in the time series
+    ///
+    /// Decays the weight of every previous pair by `1 - alpha` and incorporates the new
+    /// pair into the weighted means, variances and co-moment.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A tuple containing the paired values (x, y) to incorporate into calculations
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The updated statistics object for method chaining
+    pub fn next(&mut self, (x, y): (T, T)) -> &mut Self {
+        let (w_a, w) = self.moments_x.next_weights();
+        let decay = T::one() - self.alpha();
+        let w_t = w_a + w;
+
+        let dx = x - self.moments_x.current_mean();
+        let dy = y - self.moments_y.current_mean();
+
+        self.comoment = if self.count() == 0 {
+            T::zero()
+        } else {
+            self.comoment * decay + w_a * w * dx * dy / w_t
+        };
+
+        self.moments_x.next(x);
+        self.moments_y.next(y);
+
+        self
+    }
+
+    /// Returns the exponentially weighted covariance of the paired values
+    ///
+    /// Exponentially weighted covariance tracks how two series move together while
+    /// emphasizing the most recent co-movements:
+    ///
+    /// - Adapts hedge ratios to changing market relationships without window drop-off
+    /// - Provides the covariance matrix entries of RiskMetrics-style risk models
+    /// - Feeds exponentially weighted correlation and beta estimates
+    /// - Supports dynamic portfolio optimization and risk decomposition
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The weighted covariance, or `None` if fewer than `min_periods` pairs
+    ///   were seen or the bias correction is undefined
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::EwmPairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = EwmPairedStatistics::with_span(3.0);
+    /// let mut results = vec![];
+    /// let inputs = [(2.0, 1.0), (4.0, 3.0), (6.0, 2.0), (8.0, 5.0), (10.0, 7.0)];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).cov().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 5] = [0.0, 0.8889, 0.1633, 2.3111, 4.0042];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    ///
+    /// stats.reset().set_ddof(true);
+    /// results = vec![];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).cov().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 4] = [2.0, 0.2857, 3.7143, 6.2065];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn cov(&self) -> Option<T> {
+        if !self.moments_x.is_ready() {
+            return None;
+        }
+        let cov = self.comoment / self.moments_x.weight_sum();
+        self.moments_x.bias_correction().map(|c| cov * c)
+    }
+
+    /// Returns the exponentially weighted correlation coefficient of the paired values
+    ///
+    /// Exponentially weighted correlation normalizes the weighted covariance by the
+    /// weighted standard deviations, producing a measure between -1 and 1 that responds
+    /// quickly to relationship breakdowns:
+    ///
+    /// - Detects decoupling of historically related instruments early
+    /// - Provides time-varying inputs for pairs selection and monitoring
+    /// - Avoids the lag and drop-off artifacts of fixed-window correlation
+    /// - Supports regime-aware diversification analysis
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The weighted correlation, or `None` if either variance is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::EwmPairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = EwmPairedStatistics::with_halflife(3.0);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///     (0.496714, 0.115991),
+    ///     (-0.138264, -0.329650),
+    ///     (0.647689, 0.574363),
+    ///     (1.523030, 0.109481),
+    ///     (-0.234153, -1.026366),
+    ///     (-0.234137, -0.445040),
+    ///     (1.579213, 0.599033),
+    ///     (0.767435, 0.694328),
+    ///     (-0.469474, -0.782644),
+    ///     (0.542560, -0.326360)
+    /// ];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).corr().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 9] = [1.0, 0.9499, 0.3608, 0.7087, 0.7087, 0.8404, 0.7617, 0.8474, 0.813];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn corr(&self) -> Option<T> {
+        self.cov()
+            .zip(self.moments_x.stddev().zip(self.moments_y.stddev()))
+            .and_then(|(cov, (stddev_x, stddev_y))| {
+                if stddev_x.is_zero() || stddev_y.is_zero() {
+                    None
+                } else {
+                    Some(cov / (stddev_x * stddev_y))
+                }
+            })
+    }
+
+    /// Returns the exponentially weighted beta coefficient of the paired values
+    ///
+    /// Beta is the weighted covariance divided by the weighted variance of `y`, i.e. the
+    /// sensitivity of `x` to moves in `y`:
+    ///
+    /// - Provides adaptive hedge ratios that track changing sensitivities
+    /// - Quantifies time-varying systematic exposure to a benchmark
+    /// - Supports dynamic beta-neutral portfolio construction
+    /// - Detects shifts in factor loadings faster than fixed-window estimates
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The weighted beta, or `None` if the variance of `y` is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::EwmPairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = EwmPairedStatistics::with_halflife(3.0);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///      (0.015, 0.010),
+    ///      (0.025, 0.015),
+    ///      (-0.010, -0.005),
+    ///      (0.030, 0.020),
+    ///      (0.005, 0.010),
+    ///      (-0.015, -0.010),
+    ///      (0.020, 0.015),
+    /// ];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).beta().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 6] = [2.0, 1.7316, 1.6287, 1.6412, 1.4595, 1.4623];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn beta(&self) -> Option<T> {
+        self.cov()
+            .zip(self.moments_y.variance())
+            .and_then(
+                |(cov, var)| {
+                    if var.is_zero() { None } else { Some(cov / var) }
+                },
+            )
+    }
+}
@@ -0,0 +1,448 @@
+use num_traits::Float;
+
+use crate::rolling::EwmMoments;
+
+/// A structure that computes exponentially weighted statistics over a time series.
+///
+/// Where [`SingleStatistics`](crate::SingleStatistics) weights every value of a fixed window
+/// equally, `EwmStatistics` gives geometrically decaying weights to older observations, so
+/// recent data dominates while the full history still contributes. This is the weighting
+/// behind RiskMetrics-style volatility estimates and most exponentially smoothed risk models.
+///
+/// The smoothing factor can be given directly or derived from a span, half-life or center of
+/// mass, and the weighting follows pandas `ewm`:
+///
+/// - `adjust = true` (default) normalizes the weights `(1 - alpha)^t` over the full history,
+///   matching `ewm(adjust=True)`
+/// - `adjust = false` uses the recursive form `y_t = (1 - alpha) * y_(t-1) + alpha * x_t`,
+///   matching `ewm(adjust=False)`
+///
+/// State is O(1) and every update is O(1), independent of how long the series runs.
+#[derive(Debug, Clone)]
+pub struct EwmStatistics<T> {
+    /// Exponentially weighted moments
+    moments: EwmMoments<T>,
+}
+
+impl<T> EwmStatistics<T>
+where
+    T: Default + Clone + Float,
+{
+    /// Creates a new `EwmStatistics` instance with the specified smoothing factor.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The smoothing factor, `0 < alpha <= 1`
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics object
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alpha` is outside of `(0, 1]`
+    pub fn new(alpha: T) -> Self {
+        Self {
+            moments: EwmMoments::new(alpha),
+        }
+    }
+
+    /// Creates a new `EwmStatistics` instance from a span, `alpha = 2 / (span + 1)`
+    ///
+    /// # Arguments
+    ///
+    /// * `span` - The span of the decay, `span >= 1`
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics object
+    ///
+    /// # Panics
+    ///
+    /// Panics if `span` is less than 1
+    pub fn with_span(span: T) -> Self {
+        Self::new(EwmMoments::alpha_from_span(span))
+    }
+
+    /// Creates a new `EwmStatistics` instance from a half-life, `alpha = 1 - exp(-ln(2) / halflife)`
+    ///
+    /// # Arguments
+    ///
+    /// * `halflife` - The number of observations after which a weight halves, `halflife > 0`
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics object
+    ///
+    /// # Panics
+    ///
+    /// Panics if `halflife` is not positive
+    pub fn with_halflife(halflife: T) -> Self {
+        Self::new(EwmMoments::alpha_from_halflife(halflife))
+    }
+
+    /// Creates a new `EwmStatistics` instance from a center of mass, `alpha = 1 / (1 + com)`
+    ///
+    /// # Arguments
+    ///
+    /// * `com` - The center of mass of the weights, `com >= 0`
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics object
+    ///
+    /// # Panics
+    ///
+    /// Panics if `com` is negative
+    pub fn with_com(com: T) -> Self {
+        Self::new(EwmMoments::alpha_from_com(com))
+    }
+
+    /// Returns the smoothing factor
+    ///
+    /// # Returns
+    ///
+    /// * `T` - The smoothing factor
+    pub const fn alpha(&self) -> T {
+        self.moments.alpha()
+    }
+
+    /// Returns whether the weights are adjusted
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the weights are normalized over the full history
+    pub const fn adjust(&self) -> bool {
+        self.moments.adjust()
+    }
+
+    /// Sets whether the weights are adjusted, should be set before the first value is added
+    ///
+    /// # Arguments
+    ///
+    /// * `adjust` - True to normalize the weights over the full history (pandas `adjust=True`),
+    ///   false for the recursive form (pandas `adjust=False`)
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub const fn set_adjust(&mut self, adjust: bool) -> &mut Self {
+        self.moments.set_adjust(adjust);
+        self
+    }
+
+    /// Returns the Delta Degrees of Freedom
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - The Delta Degrees of Freedom
+    pub const fn ddof(&self) -> bool {
+        self.moments.ddof()
+    }
+
+    /// Sets the Delta Degrees of Freedom
+    ///
+    /// When set, the bias-corrected estimators are returned (pandas `bias=False`). The
+    /// variance is scaled by `(Σw)² / ((Σw)² - Σw²)`, and skewness and kurtosis use the
+    /// sample corrections with the effective number of observations `(Σw)² / Σw²`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ddof` - The Delta Degrees of Freedom
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub const fn set_ddof(&mut self, ddof: bool) -> &mut Self {
+        self.moments.set_ddof(ddof);
+        self
+    }
+
+    /// Returns the minimum number of observations required before values are reported
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The minimum number of observations
+    pub const fn min_periods(&self) -> usize {
+        self.moments.min_periods()
+    }
+
+    /// Sets the minimum number of observations required before values are reported
+    ///
+    /// # Arguments
+    ///
+    /// * `min_periods` - The minimum number of observations
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub const fn set_min_periods(&mut self, min_periods: usize) -> &mut Self {
+        self.moments.set_min_periods(min_periods);
+        self
+    }
+
+    /// Returns the number of observations seen so far
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of observations
+    pub const fn count(&self) -> usize {
+        self.moments.count()
+    }
+
+    /// Resets the statistics
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub fn reset(&mut self) -> &mut Self {
+        self.moments.reset();
+        self
+    }
+
+    /// Updates the statistical calculations with a new value in the time series
+    ///
+    /// Decays the weight of every previous observation by `1 - alpha` and incorporates
+    /// the new data point. This is the core method that should be called whenever new
+    /// data is available for processing.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The new value to be added to the time series
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub fn next(&mut self, value: T) -> &mut Self {
+        self.moments.next(value);
+        self
+    }
+
+    /// Returns the exponentially weighted mean
+    ///
+    /// The exponentially weighted mean tracks the level of a series with a lag controlled
+    /// by the smoothing factor rather than by a hard window length:
+    ///
+    /// - Provides a smooth, responsive reference level for trend-following models
+    /// - Avoids the drop-off artifacts of fixed windows when large values leave the window
+    /// - Serves as the center for exponentially weighted volatility estimates
+    /// - Forms the basis of EMA-derived indicators such as MACD
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The weighted mean, or `None` if fewer than `min_periods` values were seen
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::EwmStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = EwmStatistics::with_span(5.0);
+    /// let inputs = [25.4, 26.2, 26.0, 26.1, 25.8, 25.9, 26.3, 26.2, 26.5];
+    /// let mut results = vec![];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).mean().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 9] = [25.4, 25.88, 25.9368, 26.0046, 25.9261, 25.9165, 26.0523, 26.1035, 26.2392];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    ///
+    /// stats.reset().set_adjust(false);
+    /// results = vec![];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).mean().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 9] = [25.4, 25.6667, 25.7778, 25.8852, 25.8568, 25.8712, 26.0141, 26.0761, 26.2174];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn mean(&self) -> Option<T> {
+        self.moments.mean()
+    }
+
+    /// Returns the exponentially weighted variance
+    ///
+    /// Exponentially weighted variance is the standard volatility estimator of
+    /// RiskMetrics-style risk models:
+    ///
+    /// - Reacts quickly to volatility clusters while retaining long memory
+    /// - Provides the variance input for parametric Value-at-Risk models
+    /// - Enables volatility targeting and risk-parity position sizing
+    /// - Avoids ghosting effects when extreme observations leave a fixed window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The weighted variance, or `None` if fewer than `min_periods` values
+    ///   were seen or the bias correction is undefined
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::EwmStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = EwmStatistics::with_span(5.0);
+    /// let inputs = [25.4, 26.2, 26.0, 26.1, 25.8, 25.9, 26.3, 26.2, 26.5];
+    /// let mut results = vec![];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).variance().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 9] = [0.0, 0.1536, 0.0844, 0.0558, 0.0443, 0.0283, 0.0519, 0.0388, 0.0609];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    ///
+    /// stats.reset().set_ddof(true);
+    /// results = vec![];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).variance().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 8] = [0.32, 0.1337, 0.0796, 0.0599, 0.0371, 0.0669, 0.0495, 0.0772];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn variance(&self) -> Option<T> {
+        self.moments.variance()
+    }
+
+    /// Returns the exponentially weighted standard deviation
+    ///
+    /// As the square root of the weighted variance, this is the volatility figure
+    /// reported by exponentially weighted risk models:
+    ///
+    /// - Sets volatility-adjusted stop distances and position sizes
+    /// - Scales signals into risk units across instruments
+    /// - Feeds volatility targeting overlays at the portfolio level
+    /// - Detects volatility regime shifts faster than equal-weight estimates
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The weighted standard deviation, or `None` if fewer than `min_periods`
+    ///   values were seen or the bias correction is undefined
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::EwmStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = EwmStatistics::with_span(5.0);
+    /// let inputs = [25.4, 26.2, 26.0, 26.1, 25.8, 25.9, 26.3, 26.2, 26.5];
+    /// let mut results = vec![];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).stddev().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 9] = [0.0, 0.3919, 0.2906, 0.2363, 0.2105, 0.1681, 0.2278, 0.1971, 0.2468];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    ///
+    /// stats.reset().set_ddof(true);
+    /// results = vec![];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).stddev().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 8] = [0.5657, 0.3656, 0.2821, 0.2448, 0.1927, 0.2587, 0.2226, 0.2778];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn stddev(&self) -> Option<T> {
+        self.moments.stddev()
+    }
+
+    /// Returns the z-score of the most recent value relative to the weighted distribution
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The z-score of the most recent value, or `None` if the standard
+    ///   deviation is not positive
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::EwmStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = EwmStatistics::with_span(5.0);
+    /// let inputs = [25.4, 26.2, 26.0, 26.1, 25.8, 25.9, 26.3, 26.2, 26.5];
+    /// let mut results = vec![];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).zscore().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 8] = [0.8165, 0.2174, 0.4037, -0.599, -0.0984, 1.0874, 0.4895, 1.0565];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn zscore(&self) -> Option<T> {
+        self.moments.zscore()
+    }
+
+    /// Returns the exponentially weighted skewness
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The weighted skewness, or `None` if the variance is not positive
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::EwmStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = EwmStatistics::with_span(5.0);
+    /// let inputs = [25.4, 26.2, 26.0, 26.1, 25.8, 25.9, 26.3, 26.2, 26.5];
+    /// let mut results = vec![];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).skew().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 8] = [-0.4082, -1.0882, -1.9307, -0.864, -0.9068, -0.5463, -1.2026, -0.7995];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn skew(&self) -> Option<T> {
+        self.moments.skew()
+    }
+
+    /// Returns the exponentially weighted excess kurtosis
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The weighted excess kurtosis, or `None` if the variance is not positive
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::EwmStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = EwmStatistics::with_span(5.0);
+    /// let inputs = [25.4, 26.2, 26.0, 26.1, 25.8, 25.9, 26.3, 26.2, 26.5];
+    /// let mut results = vec![];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).kurt().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 8] = [-1.8333, -0.3336, 2.375, 0.4575, 2.1634, -0.1221, 1.3061, 0.2822];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn kurt(&self) -> Option<T> {
+        self.moments.kurt()
+    }
+}
@@ -0,0 +1,127 @@
+use num_traits::Float;
+
+use crate::rolling::RollingWma;
+
+/// # Hull Moving Average (HMA)
+///
+/// A low-lag moving average that extrapolates the linearly weighted average of half the period
+/// against the full period and smooths the result over the square root of the period:
+///
+/// `HMA = WMA(2 * WMA(x, period / 2) - WMA(x, period), floor(sqrt(period)))`
+///
+/// Each of the three linearly weighted averages is updated in O(1), so the HMA is O(1) per
+/// update regardless of the period. The first value is available after
+/// `period + floor(sqrt(period)) - 1` inputs.
+///
+/// # Examples
+///
+/// ```
+/// use ta_statistics::HullMovingAverage;
+/// use assert_approx_eq::assert_approx_eq;
+///
+/// let mut hma = HullMovingAverage::new(4);
+/// let mut results = vec![];
+/// let inputs = [10.0, 10.5, 11.2, 10.9, 11.5, 11.9, 12.3, 12.1, 11.8, 12.5];
+/// inputs.iter().for_each(|i| {
+///     hma.next(*i).get().map(|v| results.push(v));
+/// });
+///
+/// let expected: [f64; 6] = [11.3533, 11.8289, 12.31, 12.3333, 11.9578, 12.1622];
+/// for (i, e) in expected.iter().enumerate() {
+///     assert_approx_eq!(e, results[i], 0.0001);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct HullMovingAverage<T> {
+    /// Linearly weighted average over half the period
+    half: RollingWma<T>,
+    /// Linearly weighted average over the full period
+    full: RollingWma<T>,
+    /// Linearly weighted average of the extrapolated series over the square root of the period
+    smooth: RollingWma<T>,
+}
+
+impl<T: Default + Clone + Float> HullMovingAverage<T> {
+    /// Creates a new `HullMovingAverage` instance with the specified period
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The size of the rolling window
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The moving average object
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is less than 2
+    pub fn new(period: usize) -> Self {
+        assert!(period >= 2, "period must be >= 2");
+        Self {
+            half: RollingWma::new(period / 2),
+            full: RollingWma::new(period),
+            smooth: RollingWma::new(period.isqrt()),
+        }
+    }
+
+    /// Returns the period of the moving average
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The period of the moving average
+    pub const fn period(&self) -> usize {
+        self.full.period()
+    }
+
+    /// Resets the moving average
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The moving average object
+    pub fn reset(&mut self) -> &mut Self {
+        self.half.reset();
+        self.full.reset();
+        self.smooth.reset();
+        self
+    }
+
+    /// Recomputes the moving average, could be called to avoid
+    /// prolonged compounding of floating rounding errors
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The moving average object
+    pub fn recompute(&mut self) -> &mut Self {
+        self.half.recompute();
+        self.full.recompute();
+        self.smooth.recompute();
+        self
+    }
+
+    /// Updates the moving average with a new value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The new value to be added to the rolling window
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The moving average object
+    pub fn next(&mut self, value: T) -> &mut Self {
+        let half = self.half.next(value).wma();
+        let full = self.full.next(value).wma();
+        if let Some((half, full)) = half.zip(full) {
+            self.smooth.next(half + half - full);
+        }
+        self
+    }
+
+    /// Returns the Hull moving average
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Hull moving average, or `None` if not enough values have been seen
+    pub fn get(&self) -> Option<T> {
+        self.smooth.wma()
+    }
+}
@@ -0,0 +1,435 @@
+use alloc::vec::Vec;
+use num_traits::Float;
+
+/// Default accuracy parameter of [`KllSketch`]
+const DEFAULT_KLL_K: usize = 200;
+
+/// Smallest capacity of a compactor level
+const MIN_LEVEL_CAPACITY: usize = 8;
+
+/// Decay of the compactor capacities towards the lower levels
+const CAPACITY_DECAY: f64 = 2.0 / 3.0;
+
+/// # KLL Quantile Sketch
+///
+/// A mergeable streaming quantile sketch after Karnin, Lang and Liberty, for expanding
+/// windows too large to hold every observation. Observations enter a hierarchy of compactors:
+/// once a level is full it is sorted and every other item, starting at a random offset, is
+/// promoted to the next level with twice the weight, while the rest are discarded.
+///
+/// Memory is O(k · log(n / k)) items rather than O(n). Updates are amortized O(log k) and
+/// quantile queries sort the retained items, so they cost O(k · log(n / k) · log k) and
+/// allocate scratch space.
+///
+/// # Error bounds
+///
+/// The error is bounded in rank, not in value. With accuracy parameter `k`, the rank of the
+/// returned quantile is within about `1.7 / k · n` of `q · n` with high probability, e.g.
+/// ±0.85% of `n` for the default `k = 200` and ±0.2% for `k = 800`. The bound holds for
+/// sketches merged across shards, with `n` the combined number of observations. Results are
+/// exact while fewer than `k` observations have been seen.
+///
+/// The compaction offsets are drawn from an internal generator with a fixed seed, so a
+/// sketch is deterministic for a given input sequence.
+///
+/// # Examples
+///
+/// ```
+/// use ta_statistics::KllSketch;
+///
+/// let mut left = KllSketch::new(200);
+/// let mut right = KllSketch::new(200);
+/// for i in 0..50_000 {
+///     left.next(i as f64);
+///     right.next((50_000 + i) as f64);
+/// }
+///
+/// left.merge(&right);
+/// assert_eq!(left.count(), 100_000);
+///
+/// let median = left.quantile(0.5).unwrap();
+/// assert!((median - 50_000.0).abs() < 0.02 * 100_000.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KllSketch<T> {
+    /// Accuracy parameter, the capacity of the top level
+    k: usize,
+    /// Compactor levels, items at level `h` carry a weight of `2^h`
+    levels: Vec<Vec<T>>,
+    /// Number of observations summarized by the sketch
+    count: u64,
+    /// Smallest observation
+    min: Option<T>,
+    /// Largest observation
+    max: Option<T>,
+    /// State of the xorshift generator drawing compaction offsets
+    rng: u64,
+}
+
+impl<T: Float> Default for KllSketch<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_KLL_K)
+    }
+}
+
+impl<T: Float> KllSketch<T> {
+    /// Creates a new `KllSketch` instance with the specified accuracy parameter
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The accuracy parameter, larger values trade memory for a smaller rank error
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The sketch
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is less than 8
+    pub fn new(k: usize) -> Self {
+        assert!(k >= MIN_LEVEL_CAPACITY, "k must be >= 8");
+        Self {
+            k,
+            levels: vec![Vec::with_capacity(k)],
+            count: 0,
+            min: None,
+            max: None,
+            rng: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    /// Returns the accuracy parameter
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The accuracy parameter
+    pub const fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Returns the number of observations summarized by the sketch
+    ///
+    /// # Returns
+    ///
+    /// * `u64` - The number of observations
+    pub const fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns true if the sketch has not seen any observation
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the sketch is empty
+    pub const fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Returns the number of items retained by the sketch
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of retained items
+    pub fn retained(&self) -> usize {
+        self.levels.iter().map(Vec::len).sum()
+    }
+
+    /// Resets the sketch
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The sketch
+    pub fn reset(&mut self) -> &mut Self {
+        self.levels.truncate(1);
+        self.levels.iter_mut().for_each(Vec::clear);
+        self.count = 0;
+        self.min = None;
+        self.max = None;
+        self
+    }
+
+    /// Updates the sketch with a new value
+    ///
+    /// NaN values are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The new value to be summarized
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The sketch
+    pub fn next(&mut self, value: T) -> &mut Self {
+        if value.is_nan() {
+            return self;
+        }
+        self.count += 1;
+        self.min = Some(self.min.map_or(value, |m| m.min(value)));
+        self.max = Some(self.max.map_or(value, |m| m.max(value)));
+        self.levels[0].push(value);
+        self.compress();
+        self
+    }
+
+    /// Merges another sketch into this one
+    ///
+    /// The merged sketch summarizes the observations of both, so sketches built on separate
+    /// shards can be combined. The accuracy parameter of this sketch is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The sketch to merge
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The sketch
+    pub fn merge(&mut self, other: &Self) -> &mut Self {
+        if other.is_empty() {
+            return self;
+        }
+        while self.levels.len() < other.levels.len() {
+            self.levels.push(Vec::new());
+        }
+        for (level, items) in self.levels.iter_mut().zip(other.levels.iter()) {
+            level.extend_from_slice(items);
+        }
+        self.count += other.count;
+        self.min = self.min.zip(other.min).map(|(a, b)| a.min(b)).or(other.min);
+        self.max = self.max.zip(other.max).map(|(a, b)| a.max(b)).or(other.max);
+        self.compress();
+        self
+    }
+
+    /// Returns the smallest value seen
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The minimum, or `None` if the sketch is empty
+    pub const fn min(&self) -> Option<T> {
+        self.min
+    }
+
+    /// Returns the largest value seen
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The maximum, or `None` if the sketch is empty
+    pub const fn max(&self) -> Option<T> {
+        self.max
+    }
+
+    /// Returns the approximate quantile of the values seen
+    ///
+    /// Returns the smallest retained value whose cumulative weight reaches `q · n`, so the
+    /// quantiles 0 and 1 are the exact minimum and maximum.
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - The quantile to calculate
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The quantile, or `None` if the sketch is empty or `q` is outside `[0, 1]`
+    pub fn quantile(&self, q: f64) -> Option<T> {
+        weighted_quantile(
+            self.weighted_items().collect(),
+            self.count,
+            q,
+            self.min,
+            self.max,
+        )
+    }
+
+    /// Returns the approximate fraction of the values seen that are less than or equal to `value`
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to rank
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The normalized rank, or `None` if the sketch is empty
+    pub fn rank(&self, value: T) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let below: u64 = self
+            .weighted_items()
+            .filter(|&(v, _)| v <= value)
+            .map(|(_, w)| w)
+            .sum();
+        T::from(below as f64 / self.count as f64)
+    }
+
+    /// Returns the retained items with their weights
+    pub(crate) fn weighted_items(&self) -> impl Iterator<Item = (T, u64)> + '_ {
+        self.levels
+            .iter()
+            .enumerate()
+            .flat_map(|(h, items)| items.iter().map(move |&v| (v, 1u64 << h)))
+    }
+
+    /// Returns the capacity of a level given the current number of levels
+    fn level_capacity(&self, level: usize) -> usize {
+        let depth = (self.levels.len() - 1 - level) as i32;
+        let capacity = Float::ceil(self.k as f64 * Float::powi(CAPACITY_DECAY, depth));
+        Ord::max(capacity as usize, MIN_LEVEL_CAPACITY)
+    }
+
+    /// Compacts the lowest full levels until the retained items fit the total capacity
+    fn compress(&mut self) {
+        loop {
+            let total_capacity: usize =
+                (0..self.levels.len()).map(|h| self.level_capacity(h)).sum();
+            if self.retained() < total_capacity {
+                return;
+            }
+
+            let Some(level) =
+                (0..self.levels.len()).find(|&h| self.levels[h].len() >= self.level_capacity(h))
+            else {
+                return;
+            };
+            self.compact(level);
+        }
+    }
+
+    /// Promotes every other item of a sorted level to the next level
+    ///
+    /// An odd item out stays behind, so the total weight is preserved exactly.
+    fn compact(&mut self, level: usize) {
+        if level + 1 == self.levels.len() {
+            self.levels.push(Vec::new());
+        }
+
+        let offset = (self.next_random() & 1) as usize;
+        let (lower, upper) = self.levels.split_at_mut(level + 1);
+        let items = &mut lower[level];
+        items.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+
+        let leftover = (items.len() % 2 == 1).then(|| items.remove(0));
+        upper[0].extend(items.iter().skip(offset).step_by(2).copied());
+        items.clear();
+        items.extend(leftover);
+    }
+
+    /// Advances the xorshift generator
+    fn next_random(&mut self) -> u64 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        self.rng
+    }
+}
+
+/// Returns the smallest value whose cumulative weight reaches `q · total`
+///
+/// # Arguments
+///
+/// * `items` - The weighted items, in any order
+/// * `total` - The total weight of the items
+/// * `q` - The quantile to calculate
+/// * `min` - The exact minimum, returned for `q = 0`
+/// * `max` - The exact maximum, returned for `q = 1`
+///
+/// # Returns
+///
+/// * `Option<T>` - The quantile, or `None` if there are no items or `q` is outside `[0, 1]`
+pub(crate) fn weighted_quantile<T: Float>(
+    mut items: Vec<(T, u64)>,
+    total: u64,
+    q: f64,
+    min: Option<T>,
+    max: Option<T>,
+) -> Option<T> {
+    if total == 0 || !(0.0..=1.0).contains(&q) {
+        return None;
+    }
+    if q == 0.0 {
+        return min;
+    }
+    if q == 1.0 {
+        return max;
+    }
+
+    items.sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(core::cmp::Ordering::Equal));
+    let target = q * total as f64;
+    let mut cumulative = 0u64;
+    for (value, weight) in items {
+        cumulative += weight;
+        if cumulative as f64 >= target {
+            return Some(value);
+        }
+    }
+    max
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic permutation of `0..n` so that the stream is not sorted
+    fn shuffled(n: u64) -> impl Iterator<Item = f64> {
+        (0..n).map(move |i| ((i * 7_919) % n) as f64)
+    }
+
+    #[test]
+    fn exact_below_capacity() {
+        let mut sketch = KllSketch::new(200);
+        shuffled(101).for_each(|v| {
+            sketch.next(v);
+        });
+        assert_eq!(sketch.retained(), 101);
+        assert_eq!(sketch.quantile(0.5), Some(50.0));
+        assert_eq!(sketch.quantile(0.0), Some(0.0));
+        assert_eq!(sketch.quantile(1.0), Some(100.0));
+        assert_eq!(sketch.quantile(1.5), None);
+    }
+
+    #[test]
+    fn rank_error_within_bound() {
+        let n = 200_000;
+        for k in [100, 200, 400] {
+            let mut sketch = KllSketch::new(k);
+            shuffled(n).for_each(|v| {
+                sketch.next(v);
+            });
+            assert_eq!(sketch.count(), n);
+            assert!(sketch.retained() < 4 * k);
+
+            let bound = 1.7 / k as f64 * n as f64;
+            for q in [0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99] {
+                let v = sketch.quantile(q).unwrap_or(f64::NAN);
+                assert!((v - q * n as f64).abs() <= bound, "k {} q {} v {}", k, q, v);
+            }
+            let r = sketch.rank(n as f64 / 4.0).unwrap_or(f64::NAN);
+            assert!((r - 0.25).abs() <= 1.7 / k as f64);
+        }
+    }
+
+    #[test]
+    fn merge_works() {
+        let n = 200_000;
+        let mut shards: Vec<KllSketch<f64>> = (0..4).map(|_| KllSketch::default()).collect();
+        shuffled(n).enumerate().for_each(|(i, v)| {
+            shards[i % 4].next(v);
+        });
+
+        let mut merged = KllSketch::default();
+        for shard in &shards {
+            merged.merge(shard);
+        }
+        assert_eq!(merged.count(), n);
+        assert_eq!(merged.min(), Some(0.0));
+        assert_eq!(merged.max(), Some((n - 1) as f64));
+
+        let bound = 1.7 / DEFAULT_KLL_K as f64 * n as f64;
+        for q in [0.1, 0.5, 0.9] {
+            let v = merged.quantile(q).unwrap_or(f64::NAN);
+            assert!((v - q * n as f64).abs() <= bound, "q {} v {}", q, v);
+        }
+
+        merged.reset();
+        assert_eq!(merged.quantile(0.5), None);
+    }
+}
@@ -20,14 +20,71 @@ extern crate alloc;
 mod rolling;
 mod utils;
 
+mod quantile_method;
+pub use quantile_method::QuantileMethod;
+
+mod rank_method;
+pub use rank_method::RankMethod;
+
+mod var_method;
+pub use var_method::VarMethod;
+
 mod single_statistics;
-pub use single_statistics::SingleStatistics;
+pub use single_statistics::{SingleStatistics, SingleStatisticsBuilder};
+
+mod ewm_statistics;
+pub use ewm_statistics::EwmStatistics;
 
 mod paired_statistics;
-pub use paired_statistics::PairedStatistics;
+pub use paired_statistics::{PairedStatistics, PairedStatisticsBuilder};
+
+mod ewm_paired_statistics;
+pub use ewm_paired_statistics::EwmPairedStatistics;
+
+mod weighted_statistics;
+pub use weighted_statistics::WeightedStatistics;
+
+mod return_statistics;
+pub use return_statistics::ReturnStatistics;
+
+mod spread_statistics;
+pub use spread_statistics::SpreadStatistics;
+
+mod time_single_statistics;
+pub use time_single_statistics::TimeSingleStatistics;
+
+mod time_paired_statistics;
+pub use time_paired_statistics::TimePairedStatistics;
 
 mod minimum;
 pub use minimum::Minimum;
 
 mod maximum;
 pub use maximum::Maximum;
+
+mod weighted_moving_average;
+pub use weighted_moving_average::WeightedMovingAverage;
+
+mod triangular_moving_average;
+pub use triangular_moving_average::TriangularMovingAverage;
+
+mod hull_moving_average;
+pub use hull_moving_average::HullMovingAverage;
+
+mod polynomial_regression;
+pub use polynomial_regression::PolynomialRegression;
+
+mod p2_quantile;
+pub use p2_quantile::P2Quantile;
+
+mod kll_sketch;
+pub use kll_sketch::KllSketch;
+
+mod rolling_quantile_sketch;
+pub use rolling_quantile_sketch::RollingQuantileSketch;
+
+mod time_minimum;
+pub use time_minimum::TimeMinimum;
+
+mod time_maximum;
+pub use time_maximum::TimeMaximum;
@@ -1,6 +1,6 @@
 use num_traits::Float;
 
-use crate::utils::{Min, MonotonicQueue};
+use crate::utils::{MIN_GROWABLE_CAPACITY, Min, MonotonicQueue};
 
 /// # Minimum Value Calculation for Rolling Windows
 ///
@@ -53,8 +53,57 @@ impl<T: Default + Clone + Float> Minimum<T> {
         self.0.front()
     }
 
+    /// Returns the number of bars since the minimum value of the rolling window
+    ///
+    /// Ties resolve to the most recent occurrence, so a value equal to the current minimum
+    /// resets the count to zero.
+    ///
+    /// # Returns
+    ///
+    /// None if the window is empty, otherwise the bars since the minimum, 0 for the latest value
+    pub fn bars_since(&self) -> Option<usize> {
+        self.0.front_age()
+    }
+
+    /// Returns the index of the minimum value within the rolling window
+    ///
+    /// Index 0 is the oldest value in the window, and ties resolve to the most recent occurrence.
+    ///
+    /// # Returns
+    ///
+    /// None if the window is empty, otherwise the index of the minimum
+    pub fn argmin(&self) -> Option<usize> {
+        let len = Ord::min(self.0.element_count(), self.0.capacity());
+        Some(len - 1 - self.bars_since()?)
+    }
+
     /// Resets the rolling window
     pub fn reset(&mut self) {
         self.0.reset();
     }
+
+    /// Creates a new instance over an expanding window that never evicts values
+    pub(crate) fn cumulative() -> Self {
+        Self(MonotonicQueue::new(1))
+    }
+
+    /// Pushes a new value into the expanding window, see [`MonotonicQueue::push_cumulative`]
+    pub(crate) fn push_cumulative(&mut self, value: T) {
+        self.0.push_cumulative(value)
+    }
+
+    /// Creates a new instance whose window is driven by explicit positions
+    pub(crate) fn growable() -> Self {
+        Self(MonotonicQueue::new(MIN_GROWABLE_CAPACITY))
+    }
+
+    /// Pushes a new value at the given position, see [`MonotonicQueue::push_at`]
+    pub(crate) fn push_at(&mut self, value: T, pos: usize) {
+        self.0.push_at(value, pos)
+    }
+
+    /// Drops values positioned before `start`, see [`MonotonicQueue::expire`]
+    pub(crate) fn expire(&mut self, start: usize) {
+        self.0.expire(start)
+    }
 }
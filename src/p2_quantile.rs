@@ -0,0 +1,274 @@
+use num_traits::Float;
+
+use crate::QuantileMethod;
+
+/// # P² Quantile Estimator
+///
+/// Estimates a single quantile of an unbounded stream in O(1) memory and O(1) time per update
+/// with the P² algorithm of Jain and Chlamtac. Five markers track the minimum, the maximum, the
+/// target quantile and the quantiles halfway to either extreme. Each update shifts the marker
+/// positions and adjusts their heights with a piecewise-parabolic interpolation.
+///
+/// The estimate is exact for the first five observations. After that there is no worst-case
+/// error bound. For smooth distributions the estimate converges to the true quantile, and the
+/// error is typically well below 1% of the interquartile range after a few thousand
+/// observations. Heavy clustering, strong trends or tail quantiles beyond 0.01 or 0.99 converge
+/// more slowly. The markers cannot be combined, so use [`KllSketch`](crate::KllSketch) when
+/// estimates must be merged across shards or need a guaranteed rank error.
+///
+/// # Examples
+///
+/// ```
+/// use ta_statistics::P2Quantile;
+///
+/// let mut p95 = P2Quantile::new(0.95);
+/// for i in 0..10_000 {
+///     p95.next(((i * 7_919) % 10_000) as f64);
+/// }
+///
+/// let estimate = p95.get().unwrap();
+/// assert!((estimate - 9_500.0).abs() < 50.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct P2Quantile<T> {
+    /// Target quantile
+    q: f64,
+    /// Marker heights
+    heights: [T; 5],
+    /// Actual marker positions
+    positions: [f64; 5],
+    /// Desired marker positions
+    desired: [f64; 5],
+    /// Increments of the desired marker positions
+    increments: [f64; 5],
+    /// Number of observations
+    count: u64,
+}
+
+impl<T: Float> P2Quantile<T> {
+    /// Creates a new `P2Quantile` instance estimating the specified quantile
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - The quantile to estimate
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The estimator
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is not between 0 and 1
+    pub fn new(q: f64) -> Self {
+        assert!((0.0..=1.0).contains(&q), "q must be between 0 and 1");
+        Self {
+            q,
+            heights: [T::zero(); 5],
+            positions: [1.0, 2.0, 3.0, 4.0, 5.0],
+            desired: [1.0, 1.0 + 2.0 * q, 1.0 + 4.0 * q, 3.0 + 2.0 * q, 5.0],
+            increments: [0.0, q / 2.0, q, (1.0 + q) / 2.0, 1.0],
+            count: 0,
+        }
+    }
+
+    /// Returns the target quantile
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The target quantile
+    pub const fn q(&self) -> f64 {
+        self.q
+    }
+
+    /// Returns the number of observations
+    ///
+    /// # Returns
+    ///
+    /// * `u64` - The number of observations
+    pub const fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Resets the estimator
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The estimator
+    pub fn reset(&mut self) -> &mut Self {
+        *self = Self::new(self.q);
+        self
+    }
+
+    /// Updates the estimator with a new value
+    ///
+    /// NaN values are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The new value
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The estimator
+    pub fn next(&mut self, value: T) -> &mut Self {
+        if value.is_nan() {
+            return self;
+        }
+
+        if self.count < 5 {
+            let n = self.count as usize;
+            self.heights[n] = value;
+            self.heights[..=n]
+                .sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+            self.count += 1;
+            return self;
+        }
+        self.count += 1;
+
+        let h = &mut self.heights;
+        let cell = if value < h[0] {
+            h[0] = value;
+            0
+        } else if value >= h[4] {
+            h[4] = value;
+            3
+        } else {
+            (1..4).find(|&i| value < h[i]).map_or(3, |i| i - 1)
+        };
+
+        self.positions[cell + 1..]
+            .iter_mut()
+            .for_each(|p| *p += 1.0);
+        for (d, inc) in self.desired.iter_mut().zip(self.increments) {
+            *d += inc;
+        }
+
+        for i in 1..4 {
+            let d = self.desired[i] - self.positions[i];
+            let n = &self.positions;
+            if (d >= 1.0 && n[i + 1] - n[i] > 1.0) || (d <= -1.0 && n[i - 1] - n[i] < -1.0) {
+                let step = d.signum();
+                let height = self
+                    .parabolic(i, step)
+                    .filter(|&p| self.heights[i - 1] < p && p < self.heights[i + 1])
+                    .or_else(|| self.linear(i, step));
+                if let Some(height) = height {
+                    self.heights[i] = height;
+                }
+                self.positions[i] += step;
+            }
+        }
+        self
+    }
+
+    /// Returns the estimated quantile
+    ///
+    /// Until the sixth value the markers hold every value seen, and the quantile of the
+    /// values is returned exactly, interpolated linearly like [`QuantileMethod::Linear`].
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The estimated quantile, or `None` if no value has been seen
+    pub fn get(&self) -> Option<T> {
+        match self.count {
+            0 => None,
+            n @ 1..=5 => {
+                let (lo, hi, frac) = QuantileMethod::Linear.ranks(self.q, n as usize);
+                let (lower, upper) = (self.heights[lo], self.heights[hi]);
+                Some(lower + (upper - lower) * T::from(frac)?)
+            }
+            _ => Some(self.heights[2]),
+        }
+    }
+
+    /// Returns the estimated quantile `q`, like the `quantile` method of the other
+    /// quantile estimators
+    ///
+    /// The markers only track the target quantile, so any other quantile is unavailable.
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - The quantile to return, which must be the target quantile
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The estimated quantile, or `None` if no value has been seen or `q`
+    ///   is not the target quantile
+    pub fn quantile(&self, q: f64) -> Option<T> {
+        if q != self.q {
+            return None;
+        }
+        self.get()
+    }
+
+    /// Returns the piecewise-parabolic prediction of a marker height
+    fn parabolic(&self, i: usize, d: f64) -> Option<T> {
+        let (q, n) = (&self.heights, &self.positions);
+        let d_t = T::from(d)?;
+        let span = T::from(n[i + 1] - n[i - 1])?;
+        let right = T::from(n[i] - n[i - 1] + d)? * (q[i + 1] - q[i]) / T::from(n[i + 1] - n[i])?;
+        let left = T::from(n[i + 1] - n[i] - d)? * (q[i] - q[i - 1]) / T::from(n[i] - n[i - 1])?;
+        Some(q[i] + d_t / span * (right + left))
+    }
+
+    /// Returns the linear prediction of a marker height
+    fn linear(&self, i: usize, d: f64) -> Option<T> {
+        let j = if d > 0.0 { i + 1 } else { i - 1 };
+        let (q, n) = (&self.heights, &self.positions);
+        Some(q[i] + T::from(d)? * (q[j] - q[i]) / T::from(n[j] - n[i])?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn p2_matches_reference() {
+        // Worked example of Jain and Chlamtac (1985), median of 20 observations
+        let inputs = [
+            0.02, 0.15, 0.74, 3.39, 0.83, 22.37, 10.15, 15.43, 38.62, 15.92, 34.6, 10.28, 1.47,
+            0.4, 0.05, 11.39, 0.27, 0.42, 0.09, 11.37,
+        ];
+        let mut p2 = P2Quantile::new(0.5);
+        inputs.iter().for_each(|v| {
+            p2.next(*v);
+        });
+        assert_approx_eq!(p2.get().unwrap(), 4.44, 0.01);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn p2_converges() {
+        let n = 100_000;
+        for q in [0.05, 0.25, 0.5, 0.75, 0.95] {
+            let mut p2 = P2Quantile::new(q);
+            (0..n).for_each(|i| {
+                p2.next(((i * 7_919) % n) as f64);
+            });
+            assert!((p2.get().unwrap() - q * n as f64).abs() < 0.005 * n as f64);
+        }
+
+        let mut p2 = P2Quantile::new(0.5);
+        assert_eq!(p2.get(), None);
+        [3.0, 1.0, 2.0, 4.0].iter().for_each(|v| {
+            p2.next(*v);
+        });
+        assert_eq!(p2.get(), Some(2.5));
+        p2.reset();
+        assert_eq!(p2.count(), 0);
+
+        let mut p95 = P2Quantile::new(0.95);
+        [5.0, 2.0, 4.0, 1.0, 3.0].iter().for_each(|v| {
+            p95.next(*v);
+        });
+        assert_approx_eq!(p95.get().unwrap(), 4.8, 1e-12);
+        assert_eq!(p95.quantile(0.95), p95.get());
+        assert_eq!(p95.quantile(0.5), None);
+        p95.reset();
+        assert_eq!(p95.count(), 0);
+    }
+}
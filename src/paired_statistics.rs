@@ -1,6 +1,12 @@
 use num_traits::Float;
+use ordered_float::PrimitiveFloat;
 
-use crate::rolling::RollingMoments;
+use alloc::vec::Vec;
+
+use crate::{
+    rolling::{RollingMoments, RollingRanks},
+    utils::{adf_tstat, engle_granger_pvalue},
+};
 
 type Kbn<T> = compensated_summation::KahanBabuskaNeumaier<T>;
 
@@ -11,17 +17,56 @@ type Kbn<T> = compensated_summation::KahanBabuskaNeumaier<T>;
 ///
 /// The structure automatically updates statistics as new values are added and old values
 /// are removed from the window, making it efficient for rolling statistics analysis.
+///
+/// [`PairedStatistics::new`] enables the statistics that update in O(1). Rank correlations
+/// keep both series in order-statistic trees in O(log n) per update and are enabled with
+/// [`PairedStatistics::builder`].
 #[derive(Debug, Clone)]
 pub struct PairedStatistics<T> {
     moments_x: RollingMoments<T>,
     moments_y: RollingMoments<T>,
     sum_xy: Kbn<T>,
+    capture: CaptureSums<T>,
+    ranks: Option<RollingRanks<T>>,
     ddof: bool,
 }
 
+/// Sums of the values over the periods in which the benchmark `y` rose or fell
+#[derive(Debug, Clone)]
+struct CaptureSums<T> {
+    up_x: Kbn<T>,
+    up_y: Kbn<T>,
+    down_x: Kbn<T>,
+    down_y: Kbn<T>,
+}
+
+impl<T: Float> Default for CaptureSums<T> {
+    fn default() -> Self {
+        Self {
+            up_x: Kbn::default(),
+            up_y: Kbn::default(),
+            down_x: Kbn::default(),
+            down_y: Kbn::default(),
+        }
+    }
+}
+
+impl<T: Float> CaptureSums<T> {
+    /// Adds a pair to the sums of its benchmark direction, scaled by `sign`
+    fn update(&mut self, x: T, y: T, sign: T) {
+        if y > T::zero() {
+            self.up_x += sign * x;
+            self.up_y += sign * y;
+        } else if y < T::zero() {
+            self.down_x += sign * x;
+            self.down_y += sign * y;
+        }
+    }
+}
+
 impl<T> PairedStatistics<T>
 where
-    T: Default + Clone + Float,
+    T: Default + Clone + Float + PrimitiveFloat,
 {
     /// Creates a new `PairedStatistics` instance with the specified period.
     ///
@@ -33,16 +78,129 @@ where
     ///
     /// * `Self` - The `PairedStatistics` instance
     pub fn new(period: usize) -> Self {
+        Self::builder(period).build()
+    }
+
+    /// Creates a builder for a `PairedStatistics` instance with the specified period.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The period of the statistics
+    ///
+    /// # Returns
+    ///
+    /// * `PairedStatisticsBuilder<T>` - The builder
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::PairedStatistics;
+    /// let mut stats = PairedStatistics::builder(3).with_ranks().build();
+    /// let inputs = [(1.0, 2.0), (2.0, 1.0), (3.0, 4.0), (4.0, 3.0)];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i);
+    /// });
+    ///
+    /// assert_eq!(stats.spearman(), Some(0.5));
+    /// assert_eq!(PairedStatistics::<f64>::new(3).spearman(), None);
+    /// ```
+    pub fn builder(period: usize) -> PairedStatisticsBuilder<T> {
+        PairedStatisticsBuilder::new(period)
+    }
+
+    /// Creates a new `PairedStatistics` instance over an expanding window.
+    ///
+    /// The expanding window never evicts pairs, so every statistic covers all pairs
+    /// since inception using O(1) memory. Statistics are reported from the first pair onwards.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The `PairedStatistics` instance
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = PairedStatistics::expanding();
+    /// let mut results = vec![];
+    /// let inputs = [(2.0, 1.0), (4.0, 3.0), (6.0, 2.0), (8.0, 5.0), (10.0, 7.0)];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).cov().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 5] = [0.0, 1.0, 0.6667, 2.75, 5.6];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// assert_eq!(stats.period(), 5);
+    /// ```
+    pub fn expanding() -> Self {
         Self {
-            moments_x: RollingMoments::new(period),
-            moments_y: RollingMoments::new(period),
+            moments_x: RollingMoments::expanding(),
+            moments_y: RollingMoments::expanding(),
+            sum_xy: Kbn::default(),
+            capture: CaptureSums::default(),
+            ranks: None,
+            ddof: false,
+        }
+    }
+
+    /// Creates a new `PairedStatistics` instance over a trailing time span.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - The length of the window in timestamp units
+    /// * `max_capacity` - The optional upper bound on the number of pairs held
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The `PairedStatistics` instance
+    pub(crate) fn with_time_window(duration: u64, max_capacity: Option<usize>) -> Self {
+        Self {
+            moments_x: RollingMoments::with_time_window(duration, max_capacity),
+            moments_y: RollingMoments::with_time_window(duration, max_capacity),
             sum_xy: Kbn::default(),
+            capture: CaptureSums::default(),
+            ranks: None,
             ddof: false,
         }
     }
 
+    /// Enables the Spearman and Kendall rank correlations of a time window
+    ///
+    /// The ranks are rebuilt from the pairs currently in the window.
+    pub(crate) fn enable_ranks(&mut self) {
+        let mut ranks = RollingRanks::growable();
+        for (&x, &y) in self.moments_x.iter().zip(self.moments_y.iter()) {
+            ranks.push(x, y);
+        }
+        self.ranks = Some(ranks);
+    }
+
+    /// Returns the length of the time window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u64>` - The length of the window in timestamp units, or `None` for count windows
+    pub(crate) const fn duration(&self) -> Option<u64> {
+        self.moments_x.duration()
+    }
+
+    /// Returns the number of pairs in the window
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of pairs in the window
+    pub(crate) const fn count(&self) -> usize {
+        self.moments_x.count()
+    }
+
     /// Returns the period of the statistics
     ///
+    /// For expanding windows this is the number of pairs seen since inception.
+    ///
     /// # Returns
     ///
     /// * `usize` - The period of the statistics
@@ -59,6 +217,10 @@ where
         self.moments_x.reset();
         self.moments_y.reset();
         self.sum_xy = Default::default();
+        self.capture = Default::default();
+        if let Some(ranks) = &mut self.ranks {
+            ranks.reset();
+        }
         self
     }
 
@@ -71,8 +233,11 @@ where
     pub fn recompute(&mut self) -> &mut Self {
         self.moments_x.recompute();
         self.moments_y.recompute();
+        self.sum_xy = Default::default();
+        self.capture = Default::default();
         for (&x, &y) in self.moments_x.iter().zip(self.moments_y.iter()) {
             self.sum_xy += x * y;
+            self.capture.update(x, y, T::one());
         }
         self
     }
@@ -95,17 +260,68 @@ where
         self.moments_x.next(x);
         self.moments_y.next(y);
 
-        if self.moments_x.is_ready() {
-            if let Some((px, py)) = self.moments_x.popped().zip(self.moments_y.popped()) {
-                self.sum_xy -= px * py;
+        if self.moments_x.is_ready()
+            && let Some((px, py)) = self.moments_x.popped().zip(self.moments_y.popped())
+        {
+            self.sum_xy -= px * py;
+            self.capture.update(px, py, -T::one());
+            if let Some(ranks) = &mut self.ranks {
+                ranks.pop();
             }
         }
 
         self.sum_xy += x * y;
+        self.capture.update(x, y, T::one());
+        if let Some(ranks) = &mut self.ranks {
+            ranks.push(x, y);
+        }
+
+        self
+    }
+
+    /// Updates the paired statistical calculations with a new timestamped value pair
+    ///
+    /// Evicts every pair that aged out of the time window before adding the new one.
+    ///
+    /// # Arguments
+    ///
+    /// * `ts` - The timestamp of the pair, expected to be non-decreasing across calls. A
+    ///   timestamp older than the latest one is treated as the latest timestamp
+    /// * `value` - A tuple containing the paired values (x, y) to incorporate into calculations
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The updated statistics object for method chaining
+    pub(crate) fn next_at(&mut self, ts: u64, (x, y): (T, T)) -> &mut Self {
+        while let Some((px, py)) = self.moments_x.expire(ts).zip(self.moments_y.expire(ts)) {
+            self.remove_expired(px, py);
+        }
+
+        self.moments_x.next_at(ts, x);
+        self.moments_y.next_at(ts, y);
+
+        if let Some((px, py)) = self.moments_x.popped().zip(self.moments_y.popped()) {
+            self.remove_expired(px, py);
+        }
+
+        self.sum_xy += x * y;
+        self.capture.update(x, y, T::one());
+        if let Some(ranks) = &mut self.ranks {
+            ranks.push(x, y);
+        }
 
         self
     }
 
+    /// Removes a pair that left a time window from the cross sums and ranks
+    fn remove_expired(&mut self, x: T, y: T) {
+        self.sum_xy -= x * y;
+        self.capture.update(x, y, -T::one());
+        if let Some(ranks) = &mut self.ranks {
+            ranks.pop();
+        }
+    }
+
     /// Returns the Delta Degrees of Freedom
     ///
     /// # Returns
@@ -221,6 +437,9 @@ where
 
         let n = T::from(self.period())?;
         if self.ddof() {
+            if n <= T::one() {
+                return None;
+            }
             Some(cov * (n / (n - T::one())))
         } else {
             Some(cov)
@@ -293,6 +512,10 @@ where
     /// - Provides relative sensitivity analysis for pair relationships
     /// - Serves as a key input for factor modeling and attribution analysis
     ///
+    /// Beta is the least squares slope of `x` on `y`. The covariance and the variance share
+    /// the same normalization, so the slope does not depend on the Delta Degrees of Freedom
+    /// and matches the one behind [`alpha`](Self::alpha) and [`residual`](Self::residual).
+    ///
     /// # Returns
     ///
     /// * `Option<T>` - The beta coefficient in the window, or `None` if the window is not full
@@ -323,12 +546,1017 @@ where
     /// for (i, e) in expected.iter().enumerate() {
     ///     assert_approx_eq!(e, results[i], 0.001);
     /// }
+    ///
+    /// let population = stats.beta();
+    /// stats.set_ddof(true);
+    /// assert_eq!(stats.beta(), population);
     /// ```
     pub fn beta(&self) -> Option<T> {
-        self.cov().zip(self.variance()).and_then(
-            |(cov, (_, var))| {
-                if var.is_zero() { None } else { Some(cov / var) }
-            },
-        )
+        let (cov, _, var_y) = self.population_moments()?;
+        if var_y.is_zero() {
+            return None;
+        }
+        Some(cov / var_y)
+    }
+
+    /// Returns the population covariance and the population variances of the paired values
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(T, T, T)>` - A tuple containing (cov, var_x, var_y), or `None` if the window is not full
+    fn population_moments(&self) -> Option<(T, T, T)> {
+        let (mean_x, mean_y) = self.mean()?;
+        let (mean_xy, _) = self.mean_prod()?;
+        let (var_x, var_y) = self.variance()?;
+        Some((mean_xy - mean_x * mean_y, var_x, var_y))
+    }
+
+    /// Returns the residual sum of squares of the regression of `x` on `y`
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The residual sum of squares, or `None` if the window is not full or `y` is constant
+    fn residual_ss(&self) -> Option<T> {
+        let (cov, var_x, var_y) = self.population_moments()?;
+        if var_y.is_zero() {
+            return None;
+        }
+        let n = T::from(self.period())?;
+        Some(Float::max(n * (var_x - cov * cov / var_y), T::zero()))
+    }
+
+    /// Returns the coefficient of determination (R²) of the regression behind beta
+    ///
+    /// R² is the share of the variance of `x` explained by its linear relationship with `y`,
+    /// equal to the squared correlation:
+    ///
+    /// - Measures how much of an instrument's movement the benchmark accounts for
+    /// - Qualifies beta estimates before using them as hedge ratios
+    /// - Separates systematic from idiosyncratic risk
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The R² in the window, or `None` if the window is not full or either series is constant
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = PairedStatistics::new(4);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///      (0.015, 0.010),
+    ///      (0.025, 0.015),
+    ///      (-0.010, -0.005),
+    ///      (0.030, 0.020),
+    ///      (0.005, 0.010),
+    ///      (-0.015, -0.010),
+    ///      (0.020, 0.015),
+    /// ];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).r2().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 4] = [0.9944, 0.9216, 0.9475, 0.9429];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn r2(&self) -> Option<T> {
+        let (cov, var_x, var_y) = self.population_moments()?;
+        if var_x.is_zero() || var_y.is_zero() {
+            return None;
+        }
+        Some(Float::min(cov * cov / (var_x * var_y), T::one()))
+    }
+
+    /// Returns the standard error of the estimate of the regression behind beta
+    ///
+    /// The standard error of the estimate is the typical distance of `x` from the fitted
+    /// line, `sqrt(SSE / (n - 2))`. It always uses `n - 2` degrees of freedom for the two
+    /// fitted parameters, regardless of `ddof`:
+    ///
+    /// - Measures the idiosyncratic noise left after removing the benchmark exposure
+    /// - Sizes residual risk of beta-hedged positions
+    /// - Provides the basis for the standard error of beta
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The standard error of the estimate, or `None` if the window is not full, holds fewer than three pairs or `y` is constant
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = PairedStatistics::new(4);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///      (0.015, 0.010),
+    ///      (0.025, 0.015),
+    ///      (-0.010, -0.005),
+    ///      (0.030, 0.020),
+    ///      (0.005, 0.010),
+    ///      (-0.015, -0.010),
+    ///      (0.020, 0.015),
+    /// ];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).regression_stderr().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 4] = [0.001637, 0.006339, 0.005669, 0.00573];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.000001);
+    /// }
+    /// ```
+    pub fn regression_stderr(&self) -> Option<T> {
+        let n = T::from(self.period())?;
+        let _2 = T::from(2)?;
+        if n <= _2 {
+            return None;
+        }
+        self.residual_ss().map(|sse| (sse / (n - _2)).sqrt())
+    }
+
+    /// Returns the standard error of the beta coefficient
+    ///
+    /// The standard error of beta is the standard error of the estimate scaled by the
+    /// spread of `y`, `SE / sqrt(Σ (y - ȳ)²)`:
+    ///
+    /// - Quantifies the uncertainty of hedge ratios
+    /// - Provides confidence intervals around beta
+    /// - Normalizes beta into a t-statistic for significance tests
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The standard error of beta, or `None` if the window is not full, holds fewer than three pairs or `y` is constant
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = PairedStatistics::new(4);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///      (0.015, 0.010),
+    ///      (0.025, 0.015),
+    ///      (-0.010, -0.005),
+    ///      (0.030, 0.020),
+    ///      (0.005, 0.010),
+    ///      (-0.015, -0.010),
+    ///      (0.020, 0.015),
+    /// ];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).beta_stderr().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 4] = [0.0875, 0.3388, 0.2377, 0.2516];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn beta_stderr(&self) -> Option<T> {
+        let (_, var_y) = self.variance()?;
+        let syy = T::from(self.period())? * var_y;
+        self.regression_stderr().map(|se| se / syy.sqrt())
+    }
+
+    /// Returns the t-statistic of the beta coefficient
+    ///
+    /// The t-statistic divides beta by its standard error. Under the null hypothesis of
+    /// no relationship it follows a Student's t distribution with `n - 2` degrees of freedom:
+    ///
+    /// - Tells a significant benchmark exposure apart from noise
+    /// - Filters unreliable hedge ratios before rebalancing
+    /// - Detects the breakdown of factor relationships
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The t-statistic of beta, or `None` if the window is not full, holds fewer than three pairs or the fit is exact
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = PairedStatistics::new(4);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///      (0.015, 0.010),
+    ///      (0.025, 0.015),
+    ///      (-0.010, -0.005),
+    ///      (0.030, 0.020),
+    ///      (0.005, 0.010),
+    ///      (-0.015, -0.010),
+    ///      (0.020, 0.015),
+    /// ];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).beta_tstat().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 4] = [18.7794, 4.8488, 6.0093, 5.747];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.001);
+    /// }
+    /// ```
+    pub fn beta_tstat(&self) -> Option<T> {
+        let (cov, _, var_y) = self.population_moments()?;
+        let se = self.beta_stderr()?;
+        if se.is_zero() {
+            return None;
+        }
+        Some(cov / var_y / se)
+    }
+
+    /// Returns the alpha (intercept) of the regression of `x` on the benchmark `y`
+    ///
+    /// Alpha is the part of the mean of `x` not explained by its exposure to `y`,
+    /// `x̄ - β·ȳ`, completing the fitted line `x = α + β·y` behind [`beta`](Self::beta):
+    ///
+    /// - Measures the return earned independently of the benchmark
+    /// - Separates skill from market exposure in performance attribution
+    /// - Provides the intercept for residuals and hedged spreads
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The alpha in the window, or `None` if the window is not full or `y` is constant
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = PairedStatistics::new(4);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///      (0.015, 0.010),
+    ///      (0.025, 0.015),
+    ///      (-0.010, -0.005),
+    ///      (0.030, 0.020),
+    ///      (0.005, 0.010),
+    ///      (-0.015, -0.010),
+    ///      (0.020, 0.015),
+    /// ];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).alpha().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 4] = [-0.001429, -0.003929, -0.002857, -0.002651];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.000001);
+    /// }
+    /// ```
+    pub fn alpha(&self) -> Option<T> {
+        let (mean_x, mean_y) = self.mean()?;
+        Some(mean_x - self.beta()? * mean_y)
+    }
+
+    /// Returns the standard error of the alpha coefficient
+    ///
+    /// The standard error of alpha scales the standard error of the estimate by the distance
+    /// of the benchmark mean from zero, `SE · sqrt(1 / n + ȳ² / Σ (y - ȳ)²)`:
+    ///
+    /// - Quantifies the uncertainty of the estimated alpha
+    /// - Provides confidence intervals around the intercept
+    /// - Normalizes alpha into a t-statistic for significance tests
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The standard error of alpha, or `None` if the window is not full, holds fewer than three pairs or `y` is constant
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = PairedStatistics::new(4);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///      (0.015, 0.010),
+    ///      (0.025, 0.015),
+    ///      (-0.010, -0.005),
+    ///      (0.030, 0.020),
+    ///      (0.005, 0.010),
+    ///      (-0.015, -0.010),
+    ///      (0.020, 0.015),
+    /// ];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).alpha_stderr().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 4] = [0.001198, 0.004639, 0.002972, 0.003613];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.000001);
+    /// }
+    /// ```
+    pub fn alpha_stderr(&self) -> Option<T> {
+        let (_, mean_y) = self.mean()?;
+        let (_, var_y) = self.variance()?;
+        let n = T::from(self.period())?;
+        let se = self.regression_stderr()?;
+        Some(se * (T::one() / n + mean_y * mean_y / (n * var_y)).sqrt())
+    }
+
+    /// Returns the t-statistic of the alpha coefficient
+    ///
+    /// The t-statistic divides alpha by its standard error. Under the null hypothesis of a
+    /// zero intercept it follows a Student's t distribution with `n - 2` degrees of freedom:
+    ///
+    /// - Tells persistent outperformance apart from luck
+    /// - Flags strategies whose alpha is indistinguishable from zero
+    /// - Tracks the decay of an edge over rolling windows
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The t-statistic of alpha, or `None` if the window is not full, holds fewer than three pairs or the fit is exact
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = PairedStatistics::new(4);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///      (0.015, 0.010),
+    ///      (0.025, 0.015),
+    ///      (-0.010, -0.005),
+    ///      (0.030, 0.020),
+    ///      (0.005, 0.010),
+    ///      (-0.015, -0.010),
+    ///      (0.020, 0.015),
+    /// ];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).alpha_tstat().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 4] = [-1.19257, -0.84678, -0.96148, -0.733639];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.00001);
+    /// }
+    /// ```
+    pub fn alpha_tstat(&self) -> Option<T> {
+        let se = self.alpha_stderr()?;
+        if se.is_zero() {
+            return None;
+        }
+        Some(self.alpha()? / se)
+    }
+
+    /// Returns the residual of the latest pair from the regression line
+    ///
+    /// The residual `x - (α + β·y)` of the latest pair is the part of its move not explained
+    /// by the benchmark, measured against the line fitted over the whole window:
+    ///
+    /// - Isolates the idiosyncratic move of the latest bar
+    /// - Tracks the spread of beta-hedged positions
+    /// - Feeds residual-based mean-reversion signals
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The latest residual, or `None` if the window is not full or `y` is constant
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = PairedStatistics::new(4);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///      (0.015, 0.010),
+    ///      (0.025, 0.015),
+    ///      (-0.010, -0.005),
+    ///      (0.030, 0.020),
+    ///      (0.005, 0.010),
+    ///      (-0.015, -0.010),
+    ///      (0.020, 0.015),
+    /// ];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).residual().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 4] = [-0.001429, -0.0075, 0.002143, 0.000964];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.000001);
+    /// }
+    /// ```
+    pub fn residual(&self) -> Option<T> {
+        let x = self.moments_x.value()?;
+        let y = self.moments_y.value()?;
+        Some(x - self.alpha()? - self.beta()? * y)
+    }
+
+    /// Returns the z-score of the latest residual
+    ///
+    /// The residual of the latest pair divided by the standard error of the estimate, i.e. how
+    /// many typical residuals the latest bar lies away from the regression line:
+    ///
+    /// - Signals stretched relative moves for statistical arbitrage entries
+    /// - Normalizes residuals across pairs with different noise levels
+    /// - Flags outliers that break the benchmark relationship
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The z-score of the latest residual, or `None` if the window is not full, holds fewer than three pairs or the fit is exact
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = PairedStatistics::new(4);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///      (0.015, 0.010),
+    ///      (0.025, 0.015),
+    ///      (-0.010, -0.005),
+    ///      (0.030, 0.020),
+    ///      (0.005, 0.010),
+    ///      (-0.015, -0.010),
+    ///      (0.020, 0.015),
+    /// ];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).residual_zscore().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 4] = [-0.872872, -1.183216, 0.377964, 0.168216];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.000001);
+    /// }
+    /// ```
+    pub fn residual_zscore(&self) -> Option<T> {
+        let se = self.regression_stderr()?;
+        if se.is_zero() {
+            return None;
+        }
+        Some(self.residual()? / se)
+    }
+
+    /// Returns Jensen's alpha of `x` over the benchmark `y` for a risk-free rate
+    ///
+    /// Jensen's alpha is the CAPM intercept `(x̄ - rf) - β·(ȳ - rf)`, the mean return in excess
+    /// of what the benchmark exposure earns above the risk-free rate, per period:
+    ///
+    /// - Measures risk-adjusted outperformance against the CAPM
+    /// - Ranks managers net of the return their beta explains
+    /// - Adjusts the regression alpha for the financing of the exposure
+    ///
+    /// # Arguments
+    ///
+    /// * `rf` - The risk-free rate per period
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - Jensen's alpha in the window, or `None` if the window is not full or `y` is constant
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = PairedStatistics::new(4);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///      (0.015, 0.010),
+    ///      (0.025, 0.015),
+    ///      (-0.010, -0.005),
+    ///      (0.030, 0.020),
+    ///      (0.005, 0.010),
+    ///      (-0.015, -0.010),
+    ///      (0.020, 0.015),
+    /// ];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).jensens_alpha(0.001).map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 4] = [-0.000786, -0.003286, -0.002429, -0.002205];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.000001);
+    /// }
+    /// ```
+    pub fn jensens_alpha(&self, rf: T) -> Option<T> {
+        let (mean_x, mean_y) = self.mean()?;
+        Some(mean_x - rf - self.beta()? * (mean_y - rf))
+    }
+
+    /// Returns the tracking error of `x` against the benchmark `y`
+    ///
+    /// The tracking error is the standard deviation of the active returns `x - y`, following
+    /// `ddof` like the other dispersion measures:
+    ///
+    /// - Measures how closely a portfolio follows its benchmark
+    /// - Budgets active risk for enhanced index strategies
+    /// - Normalizes active returns into the information ratio
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The tracking error in the window, or `None` if the window is not full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = PairedStatistics::new(4);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///      (0.015, 0.010),
+    ///      (0.025, 0.015),
+    ///      (-0.010, -0.005),
+    ///      (0.030, 0.020),
+    ///      (0.005, 0.010),
+    ///      (-0.015, -0.010),
+    ///      (0.020, 0.015),
+    /// ];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).tracking_error().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 4] = [0.006124, 0.0075, 0.006495, 0.006495];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.000001);
+    /// }
+    /// ```
+    pub fn tracking_error(&self) -> Option<T> {
+        let (cov, var_x, var_y) = self.population_moments()?;
+        let mut var = Float::max(var_x + var_y - (cov + cov), T::zero());
+        if self.ddof() {
+            let n = T::from(self.period())?;
+            if n <= T::one() {
+                return None;
+            }
+            var = var * n / (n - T::one());
+        }
+        Some(var.sqrt())
+    }
+
+    /// Returns the information ratio of `x` against the benchmark `y`
+    ///
+    /// The information ratio divides the mean active return `x̄ - ȳ` by the
+    /// [`tracking_error`](Self::tracking_error), per period:
+    ///
+    /// - Measures active return per unit of active risk
+    /// - Compares managers benchmarked against the same index
+    /// - Flags active bets that are not paid for their tracking risk
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The information ratio in the window, or `None` if the window is not full or the active returns are constant
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = PairedStatistics::new(4);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///      (0.015, 0.010),
+    ///      (0.025, 0.015),
+    ///      (-0.010, -0.005),
+    ///      (0.030, 0.020),
+    ///      (0.005, 0.010),
+    ///      (-0.015, -0.010),
+    ///      (0.020, 0.015),
+    /// ];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).information_ratio().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 4] = [0.816497, 0.333333, -0.19245, 0.19245];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.000001);
+    /// }
+    /// ```
+    pub fn information_ratio(&self) -> Option<T> {
+        let (mean_x, mean_y) = self.mean()?;
+        let tracking_error = self.tracking_error()?;
+        if tracking_error.is_zero() {
+            return None;
+        }
+        Some((mean_x - mean_y) / tracking_error)
+    }
+
+    /// Returns the Engle-Granger cointegration test of `x` and `y`
+    ///
+    /// The test fits the hedge ratio of `x` on `y` with a constant by ordinary least squares,
+    /// then runs an augmented Dickey-Fuller regression without constant on the residuals
+    /// of the window. The t-statistic of the lagged residual is compared against the
+    /// MacKinnon (1994) response surface for two series to get an approximate p-value of
+    /// the null hypothesis of no cointegration, like `statsmodels.tsa.stattools.coint`
+    /// with a fixed number of lags:
+    ///
+    /// - Gates pairs trading entries on a stationary spread rather than on correlation
+    /// - Detects the breakdown of a cointegrating relationship as the window rolls
+    /// - Screens candidate pairs before estimating their spread dynamics
+    ///
+    /// Every call scans the window and solves the lagged regression, which is
+    /// O(n · lags²) with an allocation of the residuals.
+    ///
+    /// # Arguments
+    ///
+    /// * `lags` - The number of lagged residual changes in the Dickey-Fuller regression
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(T, T)>` - A tuple containing (statistic, p-value), or `None` if the window
+    ///   is not full, is expanding, holds too few pairs for the lags or `y` is constant
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = PairedStatistics::new(10);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///     (100.0, 50.0),
+    ///     (102.5, 51.0),
+    ///     (101.6, 50.5),
+    ///     (104.16, 52.0),
+    ///     (105.7, 53.0),
+    ///     (104.92, 52.5),
+    ///     (107.65, 54.0),
+    ///     (110.19, 55.0),
+    ///     (109.31, 54.5),
+    ///     (112.09, 56.0),
+    ///     (113.65, 57.0),
+    ///     (112.09, 56.0),
+    ///     (116.16, 58.0),
+    ///     (117.89, 59.0),
+    ///     (117.24, 58.5),
+    ///     (120.04, 60.0),
+    /// ];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).engle_granger(0).map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [(f64, f64); 7] = [
+    ///     (-2.181028, 0.434167),
+    ///     (-1.862126, 0.599085),
+    ///     (-2.491876, 0.282664),
+    ///     (-3.234182, 0.064395),
+    ///     (-3.087282, 0.090886),
+    ///     (-3.149091, 0.078862),
+    ///     (-4.016754, 0.006796),
+    /// ];
+    /// for (i, (stat, p)) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(stat, results[i].0, 0.000001);
+    ///     assert_approx_eq!(p, results[i].1, 0.000001);
+    /// }
+    /// ```
+    pub fn engle_granger(&self, lags: usize) -> Option<(T, T)> {
+        if self.moments_x.is_expanding() {
+            return None;
+        }
+
+        let alpha = self.alpha()?;
+        let beta = self.beta()?;
+        let residuals: Vec<T> = self
+            .moments_x
+            .iter()
+            .zip(self.moments_y.iter())
+            .map(|(&x, &y)| x - alpha - beta * y)
+            .collect();
+
+        let stat = adf_tstat(&residuals, lags)?;
+        let pvalue = T::from(engle_granger_pvalue(stat.to_f64()?))?;
+        Some((stat, pvalue))
+    }
+
+    /// Returns the upside capture ratio of `x` relative to the benchmark `y`
+    ///
+    /// The upside capture `Σ x / Σ y` over the periods in which the benchmark rose measures
+    /// how much of the benchmark's gains the series participated in, using the arithmetic
+    /// definition over the window:
+    ///
+    /// - Values above 1 show amplified participation in rallies
+    /// - Compared with the downside capture, reveals asymmetric payoff profiles
+    /// - Screens managers and hedges for the convexity of their exposure
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The upside capture ratio, or `None` if the window is not full or the
+    ///   benchmark never rose
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = PairedStatistics::new(3);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///      (0.015, 0.010),
+    ///      (0.025, 0.015),
+    ///      (-0.010, -0.005),
+    ///      (0.030, 0.020),
+    ///      (0.005, 0.010),
+    ///      (-0.015, -0.010),
+    ///      (0.020, 0.015),
+    /// ];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).upside_capture().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 5] = [1.6, 1.5714, 1.1667, 1.1667, 1.0];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn upside_capture(&self) -> Option<T> {
+        if !self.moments_x.is_ready() {
+            return None;
+        }
+        let up_y = self.capture.up_y.total();
+        (up_y > T::zero()).then(|| self.capture.up_x.total() / up_y)
+    }
+
+    /// Returns the downside capture ratio of `x` relative to the benchmark `y`
+    ///
+    /// The downside capture `Σ x / Σ y` over the periods in which the benchmark fell
+    /// measures how much of the benchmark's losses the series suffered. Values below 1
+    /// indicate downside protection, and negative values gains while the benchmark fell.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The downside capture ratio, or `None` if the window is not full or
+    ///   the benchmark never fell
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = PairedStatistics::new(3);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///      (0.015, 0.010),
+    ///      (0.025, 0.015),
+    ///      (-0.010, -0.005),
+    ///      (0.030, 0.020),
+    ///      (0.005, 0.010),
+    ///      (-0.015, -0.010),
+    ///      (0.020, 0.015),
+    /// ];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).downside_capture().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 5] = [2.0, 2.0, 2.0, 1.5, 1.5];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn downside_capture(&self) -> Option<T> {
+        if !self.moments_x.is_ready() {
+            return None;
+        }
+        let down_y = self.capture.down_y.total();
+        (down_y < T::zero()).then(|| self.capture.down_x.total() / down_y)
+    }
+
+    /// Returns the capture ratio, the upside capture over the downside capture
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The capture ratio, or `None` if either capture is undefined or the
+    ///   downside capture is zero
+    pub fn capture_ratio(&self) -> Option<T> {
+        let down = self.downside_capture()?;
+        if down.is_zero() {
+            return None;
+        }
+        Some(self.upside_capture()? / down)
+    }
+
+    /// Returns the Spearman rank correlation of paired values in the rolling window
+    ///
+    /// The Spearman correlation is the Pearson correlation of the ranks of the values, so it
+    /// measures how consistently the two series move in the same order rather than how
+    /// linearly they move together. Ties share the average of their ranks, and pairs with a
+    /// NaN value are left out:
+    ///
+    /// - Stays robust to the outliers and fat tails of return series
+    /// - Captures monotonic relationships that are not linear
+    /// - Confirms or questions Pearson correlations distorted by a single extreme bar
+    ///
+    /// Both series are kept in order-statistic trees as the window slides, so each update is
+    /// O(log n) and the correlation ranks every pair against the trees in O(n log n) without
+    /// sorting the window. Requires [`with_ranks`](PairedStatisticsBuilder::with_ranks).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Spearman correlation in the window, or `None` if the window is not
+    ///   full, either series is constant, or ranks are not enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = PairedStatistics::builder(5).with_ranks().build();
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///     (0.012, 0.010),
+    ///     (-0.008, -0.006),
+    ///     (0.015, 0.011),
+    ///     (-0.022, -0.015),
+    ///     (0.005, 0.004),
+    ///     (0.018, 0.009),
+    ///     (-0.031, -0.020),
+    ///     (0.007, 0.006),
+    ///     (0.150, -0.004),
+    ///     (0.011, 0.012),
+    /// ];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).spearman().map(|v| results.push(v));
+    /// });
+    /// let expected: [f64; 6] = [1.0, 0.9, 0.9, 1.0, 0.4, 0.3];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn spearman(&self) -> Option<T> {
+        if !self.moments_x.is_ready() {
+            return None;
+        }
+        self.ranks.as_ref()?.spearman()
+    }
+
+    /// Returns the Kendall rank correlation (tau-b) of paired values in the rolling window
+    ///
+    /// Kendall's tau compares every two pairs of the window and scores them as concordant
+    /// when both series move in the same direction and discordant otherwise. The tau-b
+    /// variant corrects the denominator for pairs tied in either series:
+    ///
+    /// - Measures robust dependence between spreads and fat-tailed returns
+    /// - Reads directly as the probability of concordance minus that of discordance
+    /// - Estimates copula parameters, such as `θ = 2 / (1 - τ)` of the Clayton copula
+    ///
+    /// The tie counts are kept up to date from order-statistic trees in O(log n) per update,
+    /// and the concordance score is counted when read by sorting the pairs and inserting them
+    /// into a tree in O(n log n). Requires [`with_ranks`](PairedStatisticsBuilder::with_ranks).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Kendall tau-b in the window, or `None` if the window is not full,
+    ///   either series is constant, or ranks are not enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PairedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = PairedStatistics::builder(5).with_ranks().build();
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///     (0.012, 0.010),
+    ///     (-0.008, -0.006),
+    ///     (0.015, 0.011),
+    ///     (-0.022, -0.015),
+    ///     (0.005, 0.004),
+    ///     (0.018, 0.009),
+    ///     (-0.031, -0.020),
+    ///     (0.007, 0.006),
+    ///     (0.150, -0.004),
+    ///     (0.011, 0.012),
+    /// ];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).kendall().map(|v| results.push(v));
+    /// });
+    /// let expected: [f64; 6] = [1.0, 0.8, 0.8, 1.0, 0.4, 0.2];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    ///
+    /// let mut stats = PairedStatistics::builder(4).with_ranks().build();
+    /// let inputs: [(f64, f64); 4] = [(1.0, 2.0), (2.0, 1.0), (2.0, 3.0), (3.0, 3.0)];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i);
+    /// });
+    /// assert_approx_eq!(stats.kendall().unwrap(), 0.4, 0.0001);
+    /// ```
+    pub fn kendall(&self) -> Option<T> {
+        if !self.moments_x.is_ready() {
+            return None;
+        }
+        self.ranks.as_ref()?.kendall()
+    }
+}
+
+/// Builder for [`PairedStatistics`] with selectively enabled components
+///
+/// Moments, covariance, correlation, beta, regression and capture ratios are always
+/// enabled. The remaining components carry their own data structure, which is only
+/// allocated and updated when enabled:
+///
+/// - [`with_ranks`](Self::with_ranks) - Spearman and Kendall rank correlations
+///
+/// Getters of disabled components return `None`.
+#[derive(Debug, Clone)]
+pub struct PairedStatisticsBuilder<T> {
+    period: usize,
+    ranks: Option<RollingRanks<T>>,
+    ddof: bool,
+}
+
+impl<T> PairedStatisticsBuilder<T>
+where
+    T: Default + Clone + Float + PrimitiveFloat,
+{
+    /// Creates a new builder with only the O(1) statistics enabled
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The period of the statistics
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The builder
+    pub const fn new(period: usize) -> Self {
+        Self {
+            period,
+            ranks: None,
+            ddof: false,
+        }
+    }
+
+    /// Enables rank correlations by keeping both series in order-statistic trees in O(log n)
+    /// per update
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The builder
+    pub fn with_ranks(mut self) -> Self {
+        self.ranks = Some(RollingRanks::new(self.period));
+        self
+    }
+
+    /// Sets the Delta Degrees of Freedom
+    ///
+    /// # Arguments
+    ///
+    /// * `ddof` - The Delta Degrees of Freedom
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The builder
+    pub const fn with_ddof(mut self, ddof: bool) -> Self {
+        self.ddof = ddof;
+        self
+    }
+
+    /// Builds the `PairedStatistics` instance, allocating only the enabled components
+    ///
+    /// # Returns
+    ///
+    /// * `PairedStatistics<T>` - The statistics object
+    pub fn build(self) -> PairedStatistics<T> {
+        let period = self.period;
+        PairedStatistics {
+            moments_x: RollingMoments::new(period),
+            moments_y: RollingMoments::new(period),
+            sum_xy: Kbn::default(),
+            capture: CaptureSums::default(),
+            ranks: self.ranks,
+            ddof: self.ddof,
+        }
     }
 }
@@ -0,0 +1,227 @@
+use num_traits::Float;
+
+use crate::rolling::RollingPolyfit;
+
+/// # Rolling Polynomial Regression
+///
+/// Fits a least squares polynomial of degree 1 to 4 against the bar position over a rolling
+/// window, the oldest value at position 0 and the newest at `period - 1`. Degree 1 reproduces
+/// the linear regression of [`SingleStatistics`](crate::SingleStatistics), while quadratic and
+/// higher fits follow bending trends and expose their acceleration.
+///
+/// The power sums of the bar positions only depend on the period, so the normal equations are
+/// inverted once on construction. The moment sums `Σ i^k·x_i` slide with the window through a
+/// binomial expansion, so each update is O(degree²) regardless of the period.
+///
+/// # Examples
+///
+/// ```
+/// use ta_statistics::PolynomialRegression;
+/// use assert_approx_eq::assert_approx_eq;
+///
+/// let mut reg = PolynomialRegression::new(5, 2);
+/// let mut results = vec![];
+/// let inputs = [10.0, 10.5, 11.2, 10.9, 11.5, 11.9, 12.3, 12.1, 11.8, 12.5];
+/// inputs.iter().for_each(|i| {
+///     reg.next(*i).fitted().map(|v| results.push(v));
+/// });
+///
+/// let expected: [f64; 6] = [11.3857, 11.8629, 12.3714, 12.1514, 11.7943, 12.3314];
+/// for (i, e) in expected.iter().enumerate() {
+///     assert_approx_eq!(e, results[i], 0.0001);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PolynomialRegression<T>(RollingPolyfit<T>);
+
+impl<T: Default + Clone + Float> PolynomialRegression<T> {
+    /// Creates a new `PolynomialRegression` instance with the specified period and degree
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The size of the rolling window
+    /// * `degree` - The degree of the polynomial, between 1 and 4
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The regression object
+    ///
+    /// # Panics
+    ///
+    /// Panics if `degree` is not between 1 and 4 or `period` is not greater than `degree`
+    pub fn new(period: usize, degree: usize) -> Self {
+        Self(RollingPolyfit::new(period, degree))
+    }
+
+    /// Returns the period of the regression
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The period of the regression
+    pub const fn period(&self) -> usize {
+        self.0.period()
+    }
+
+    /// Returns the degree of the polynomial
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The degree of the polynomial
+    pub const fn degree(&self) -> usize {
+        self.0.degree()
+    }
+
+    /// Resets the regression
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The regression object
+    pub fn reset(&mut self) -> &mut Self {
+        self.0.reset();
+        self
+    }
+
+    /// Recomputes the regression, could be called to avoid
+    /// prolonged compounding of floating rounding errors
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The regression object
+    pub fn recompute(&mut self) -> &mut Self {
+        self.0.recompute();
+        self
+    }
+
+    /// Updates the regression with a new value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The new value to be added to the rolling window
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The regression object
+    pub fn next(&mut self, value: T) -> &mut Self {
+        self.0.next(value);
+        self
+    }
+
+    /// Returns the polynomial coefficients against the bar position, lowest power first
+    ///
+    /// The fitted value at position `i` is `c[0] + c[1]·i + c[2]·i² + c[3]·i³ + c[4]·i⁴`,
+    /// with the coefficients beyond the degree set to zero.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<[T; 5]>` - The coefficients, or `None` if the window is not full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PolynomialRegression;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut reg = PolynomialRegression::<f64>::new(5, 2);
+    /// [10.0, 10.5, 11.2, 10.9, 11.5].iter().for_each(|i| {
+    ///     reg.next(*i);
+    /// });
+    ///
+    /// let c = reg.coefficients().unwrap();
+    /// assert_approx_eq!(c[0], 10.0257, 0.0001);
+    /// assert_approx_eq!(c[1], 0.5686, 0.0001);
+    /// assert_approx_eq!(c[2], -0.0571, 0.0001);
+    /// assert_eq!(c[3], 0.0);
+    /// ```
+    pub fn coefficients(&self) -> Option<[T; 5]> {
+        self.0.coefficients()
+    }
+
+    /// Returns the fitted value at the last bar
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The fitted value, or `None` if the window is not full
+    pub fn fitted(&self) -> Option<T> {
+        self.0.eval_last().map(|(p, _, _)| p)
+    }
+
+    /// Returns the fitted polynomial extrapolated one bar past the last bar
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The one-step forecast, or `None` if the window is not full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PolynomialRegression;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut reg = PolynomialRegression::new(5, 2);
+    /// let mut results = vec![];
+    /// let inputs = [10.0, 10.5, 11.2, 10.9, 11.5, 11.9, 12.3, 12.1, 11.8, 12.5];
+    /// inputs.iter().for_each(|i| {
+    ///     reg.next(*i).forecast().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 6] = [11.44, 12.28, 13.12, 11.9, 11.16, 12.58];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn forecast(&self) -> Option<T> {
+        self.0.forecast()
+    }
+
+    /// Returns the slope of the fitted polynomial at the last bar
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The first derivative per bar, or `None` if the window is not full
+    pub fn slope(&self) -> Option<T> {
+        self.0.eval_last().map(|(_, dp, _)| dp)
+    }
+
+    /// Returns the acceleration of the fitted polynomial at the last bar
+    ///
+    /// The acceleration is the second derivative per bar squared, positive while the trend
+    /// bends upwards and negative while it bends downwards.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The second derivative per bar squared, or `None` if the window is not full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::PolynomialRegression;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut reg = PolynomialRegression::new(6, 3);
+    /// let mut results: Vec<f64> = vec![];
+    /// let inputs = [10.0, 10.5, 11.2, 10.9, 11.5, 11.9, 12.3, 12.1, 11.8, 12.5];
+    /// inputs.iter().for_each(|i| {
+    ///     reg.next(*i).acceleration().map(|v| results.push(v));
+    /// });
+    ///
+    /// assert_approx_eq!(results[3], -0.427, 0.0001);
+    /// assert_approx_eq!(results[4], 0.8563, 0.0001);
+    /// ```
+    pub fn acceleration(&self) -> Option<T> {
+        self.0.eval_last().map(|(_, _, ddp)| ddp)
+    }
+
+    /// Returns the signed curvature of the fitted polynomial at the last bar
+    ///
+    /// The curvature `p'' / (1 + p'²)^(3/2)` normalizes the acceleration by the slope, so
+    /// it measures how sharply the trend turns rather than how fast it speeds up.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The signed curvature, or `None` if the window is not full
+    pub fn curvature(&self) -> Option<T> {
+        let (_, dp, ddp) = self.0.eval_last()?;
+        let norm = (T::one() + dp * dp).sqrt();
+        Some(ddp / (norm * norm * norm))
+    }
+}
@@ -0,0 +1,249 @@
+use num_traits::{Float, float::FloatCore};
+
+/// Method used to estimate a quantile that falls between two sorted observations
+///
+/// The discrete methods pick one of the observations, the continuous methods interpolate
+/// linearly between the two observations around the quantile position. Every Hyndman–Fan
+/// definition is available, named after their numpy counterparts, so results reconcile with
+/// `numpy.quantile(method=...)`, `pandas.Series.quantile(interpolation=...)` and R's
+/// `quantile(type=...)`.
+///
+/// In the definitions below `n` is the number of observations, `p` the requested quantile
+/// and `x[1] <= ... <= x[n]` the sorted observations.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuantileMethod {
+    /// Interpolates at position `(n - 1)·p + 1`, Hyndman–Fan type 7, the numpy and pandas default
+    #[default]
+    Linear,
+    /// Takes the observation below the linear position
+    Lower,
+    /// Takes the observation above the linear position
+    Higher,
+    /// Takes the observation nearest to the linear position, ties to the even index
+    Nearest,
+    /// Averages the observations below and above the linear position
+    Midpoint,
+    /// Takes `x[ceil(n·p)]`, Hyndman–Fan type 1
+    InvertedCdf,
+    /// Like [`InvertedCdf`](Self::InvertedCdf), averaging at discontinuities, Hyndman–Fan type 2
+    AveragedInvertedCdf,
+    /// Takes the observation nearest to `n·p`, ties to the even index, Hyndman–Fan type 3
+    ClosestObservation,
+    /// Interpolates at position `n·p`, Hyndman–Fan type 4
+    InterpolatedInvertedCdf,
+    /// Interpolates at position `n·p + 1/2`, Hyndman–Fan type 5
+    Hazen,
+    /// Interpolates at position `(n + 1)·p`, Hyndman–Fan type 6
+    Weibull,
+    /// Interpolates at position `(n + 1/3)·p + 1/3`, Hyndman–Fan type 8
+    MedianUnbiased,
+    /// Interpolates at position `(n + 1/4)·p + 3/8`, Hyndman–Fan type 9
+    NormalUnbiased,
+}
+
+impl QuantileMethod {
+    /// Returns the method of the given Hyndman–Fan type
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The Hyndman–Fan type, between 1 and 9
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Self>` - The method, or `None` if the type is out of range
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::QuantileMethod;
+    ///
+    /// assert_eq!(QuantileMethod::hyndman_fan(7), Some(QuantileMethod::Linear));
+    /// assert_eq!(QuantileMethod::hyndman_fan(10), None);
+    /// ```
+    pub const fn hyndman_fan(kind: u8) -> Option<Self> {
+        match kind {
+            1 => Some(Self::InvertedCdf),
+            2 => Some(Self::AveragedInvertedCdf),
+            3 => Some(Self::ClosestObservation),
+            4 => Some(Self::InterpolatedInvertedCdf),
+            5 => Some(Self::Hazen),
+            6 => Some(Self::Weibull),
+            7 => Some(Self::Linear),
+            8 => Some(Self::MedianUnbiased),
+            9 => Some(Self::NormalUnbiased),
+            _ => None,
+        }
+    }
+
+    /// Returns the zero-based ranks of the observations around a quantile and the
+    /// interpolation weight of the upper one
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - The quantile, clamped to `[0, 1]`
+    /// * `n` - The number of observations, must be positive
+    ///
+    /// # Returns
+    ///
+    /// * `(usize, usize, f64)` - The lower rank, the upper rank and the weight of the upper observation
+    pub(crate) fn ranks(self, q: f64, n: usize) -> (usize, usize, f64) {
+        let q = q.clamp(0.0, 1.0);
+        let last = n - 1;
+        let nf = n as f64;
+        let np = snap(nf * q);
+        let clamp = |r: f64| (Float::max(r, 0.0) as usize).min(last);
+
+        let h = match self {
+            Self::Linear | Self::Lower | Self::Higher | Self::Nearest | Self::Midpoint => {
+                (nf - 1.0) * q
+            }
+            Self::InvertedCdf => {
+                let r = clamp(Float::ceil(np) - 1.0);
+                return (r, r, 0.0);
+            }
+            Self::AveragedInvertedCdf => {
+                let lo = clamp(Float::ceil(np) - 1.0);
+                let hi = clamp(Float::floor(np));
+                return (lo, hi, if lo == hi { 0.0 } else { 0.5 });
+            }
+            Self::ClosestObservation => {
+                let r = clamp(round_half_even(np) - 1.0);
+                return (r, r, 0.0);
+            }
+            Self::InterpolatedInvertedCdf => np - 1.0,
+            Self::Hazen => np - 0.5,
+            Self::Weibull => (nf + 1.0) * q - 1.0,
+            Self::MedianUnbiased => (nf + 1.0 / 3.0) * q - 2.0 / 3.0,
+            Self::NormalUnbiased => (nf + 0.25) * q - 0.625,
+        };
+
+        let h = snap(h).clamp(0.0, last as f64);
+        let lo = Float::floor(h) as usize;
+        let hi = (lo + 1).min(last);
+        let frac = h - lo as f64;
+        match self {
+            Self::Lower => (lo, lo, 0.0),
+            Self::Higher => {
+                let r = Float::ceil(h) as usize;
+                (r, r, 0.0)
+            }
+            Self::Nearest => {
+                let r = round_half_even(h) as usize;
+                (r, r, 0.0)
+            }
+            Self::Midpoint if frac > 0.0 => (lo, hi, 0.5),
+            Self::Midpoint => (lo, lo, 0.0),
+            _ => (lo, hi, frac),
+        }
+    }
+
+    /// Returns the quantile of observations given by their zero-based rank
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - The quantile, clamped to `[0, 1]`
+    /// * `n` - The number of observations
+    /// * `kth` - Returns the observation of the given zero-based rank
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The quantile, or `None` if there are no observations
+    pub(crate) fn quantile<T: FloatCore>(
+        self,
+        q: f64,
+        n: usize,
+        kth: impl Fn(usize) -> Option<T>,
+    ) -> Option<T> {
+        if n == 0 {
+            return None;
+        }
+        let (lo, hi, frac) = self.ranks(q, n);
+        let lower = kth(lo)?;
+        if lo == hi || frac == 0.0 {
+            return Some(lower);
+        }
+        let upper = kth(hi)?;
+        Some(lower + (upper - lower) * T::from(frac)?)
+    }
+}
+
+/// Snaps a position to the nearest integer if it only differs by rounding errors,
+/// so that e.g. `10 · 0.7` selects the 7th observation rather than the 8th
+fn snap(x: f64) -> f64 {
+    let r = Float::round(x);
+    if Float::abs(x - r) <= 4.0 * f64::EPSILON * Float::max(Float::abs(x), 1.0) {
+        r
+    } else {
+        x
+    }
+}
+
+/// Rounds to the nearest integer, ties to even
+fn round_half_even(x: f64) -> f64 {
+    let r = Float::round(x);
+    if Float::abs(x - Float::trunc(x)) == 0.5 && r % 2.0 != 0.0 {
+        r - Float::signum(x)
+    } else {
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+
+    #[test]
+    fn quantile_methods_match_reference() {
+        let sorted = [1.0, 2.0, 4.0, 7.0, 11.0, 16.0];
+        let kth = |k: usize| sorted.get(k).copied();
+
+        // numpy.quantile([1, 2, 4, 7, 11, 16], [0.1, 0.25, 0.5, 0.9], method=...)
+        // and R quantile(c(1, 2, 4, 7, 11, 16), c(0.1, 0.25, 0.5, 0.9), type=...)
+        let expected: [(QuantileMethod, [f64; 4]); 13] = [
+            (QuantileMethod::Linear, [1.5, 2.5, 5.5, 13.5]),
+            (QuantileMethod::Lower, [1.0, 2.0, 4.0, 11.0]),
+            (QuantileMethod::Higher, [2.0, 4.0, 7.0, 16.0]),
+            (QuantileMethod::Nearest, [1.0, 2.0, 4.0, 11.0]),
+            (QuantileMethod::Midpoint, [1.5, 3.0, 5.5, 13.5]),
+            (QuantileMethod::InvertedCdf, [1.0, 2.0, 4.0, 16.0]),
+            (QuantileMethod::AveragedInvertedCdf, [1.0, 2.0, 5.5, 16.0]),
+            (QuantileMethod::ClosestObservation, [1.0, 2.0, 4.0, 11.0]),
+            (
+                QuantileMethod::InterpolatedInvertedCdf,
+                [1.0, 1.5, 4.0, 13.0],
+            ),
+            (QuantileMethod::Hazen, [1.1, 2.0, 5.5, 15.5]),
+            (QuantileMethod::Weibull, [1.0, 1.75, 5.5, 16.0]),
+            (QuantileMethod::MedianUnbiased, [1.0, 1.9167, 5.5, 16.0]),
+            (QuantileMethod::NormalUnbiased, [1.0, 1.9375, 5.5, 16.0]),
+        ];
+
+        for (method, values) in expected {
+            for (q, e) in [0.1, 0.25, 0.5, 0.9].iter().zip(values) {
+                let v = method.quantile(*q, sorted.len(), kth).unwrap_or_default();
+                assert_approx_eq!(e, v, 0.0001);
+            }
+        }
+    }
+
+    #[test]
+    fn quantile_bounds_work() {
+        let sorted = [3.0, 5.0];
+        let kth = |k: usize| sorted.get(k).copied();
+        for kind in 1..=9 {
+            let method = QuantileMethod::hyndman_fan(kind).unwrap_or_default();
+            assert_eq!(method.quantile(0.0, 2, kth), Some(3.0));
+            assert_eq!(method.quantile(1.0, 2, kth), Some(5.0));
+        }
+        assert_eq!(QuantileMethod::Linear.quantile(0.5, 0, kth), None);
+
+        let sorted: [f64; 10] = core::array::from_fn(|i| i as f64 + 1.0);
+        let kth = |k: usize| sorted.get(k).copied();
+        assert_eq!(
+            QuantileMethod::InvertedCdf.quantile(0.7, 10, kth),
+            Some(7.0)
+        );
+    }
+}
@@ -0,0 +1,46 @@
+/// Method used to rank values that tie with other values in the window
+///
+/// Mirrors the `method` argument of pandas `rank(pct=True)`. For a value tied with
+/// `k` observations that exceed `l` others, the tied ranks run from `l + 1` to `l + k`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RankMethod {
+    /// Assigns the average of the tied ranks, `l + (k + 1) / 2`
+    #[default]
+    Average,
+    /// Assigns the lowest of the tied ranks, `l + 1`
+    Min,
+    /// Assigns the highest of the tied ranks, `l + k`
+    Max,
+}
+
+impl RankMethod {
+    /// Returns the percentile rank from the counts of smaller and equal observations
+    ///
+    /// A value that does not occur in the window has no tied ranks, so its percentile rank
+    /// is the fraction of observations below it regardless of the method.
+    ///
+    /// # Arguments
+    ///
+    /// * `less` - The number of observations less than the value
+    /// * `equal` - The number of observations equal to the value
+    /// * `n` - The number of observations
+    ///
+    /// # Returns
+    ///
+    /// * `Option<f64>` - The percentile rank in `[0, 1]`, or `None` if there are no observations
+    pub(crate) fn percentile(self, less: usize, equal: usize, n: usize) -> Option<f64> {
+        if n == 0 {
+            return None;
+        }
+        let rank = if equal == 0 {
+            less as f64
+        } else {
+            match self {
+                Self::Average => less as f64 + (equal as f64 + 1.0) / 2.0,
+                Self::Min => (less + 1) as f64,
+                Self::Max => (less + equal) as f64,
+            }
+        };
+        Some(rank / n as f64)
+    }
+}
@@ -0,0 +1,506 @@
+use num_traits::Float;
+use ordered_float::PrimitiveFloat;
+
+use crate::{
+    rolling::{RollingDrawdown, RollingMoments},
+    utils::RbTree,
+};
+
+type Kbn<T> = compensated_summation::KahanBabuskaNeumaier<T>;
+
+/// A structure that computes risk-adjusted performance ratios over a rolling window of returns.
+///
+/// `ReturnStatistics<T>` is fed periodic simple returns, e.g. `0.01` for a 1% gain, and reports
+/// the ratios of strategy tear sheets: Sharpe, Sortino, Calmar and Sterling. Alongside the
+/// rolling moments it keeps the sum of log growth factors, an order-statistic tree of the
+/// returns with subtree central moments, which serves the downside deviation below any target
+/// in O(log n), and the maximum drawdown of the log wealth curve, updated in amortized O(1).
+///
+/// Ratios are annualized with the configured number of periods per year, e.g. 252 for daily
+/// or 12 for monthly returns. The default of 1 leaves them per period. The standard deviation
+/// uses the sample (`n - 1`) normalization by default, like `empyrical` and `quantstats`.
+///
+/// Returns must be greater than -1, a loss of the full capital, for the compounded return and
+/// drawdown to be defined.
+///
+/// # Examples
+///
+/// ```
+/// use ta_statistics::ReturnStatistics;
+/// use assert_approx_eq::assert_approx_eq;
+///
+/// let mut stats = ReturnStatistics::new(5);
+/// stats.set_periods_per_year(252.0);
+/// let mut results = vec![];
+/// let inputs = [0.01, -0.02, 0.015, 0.03, -0.01, 0.005, -0.025, 0.02, 0.01, -0.005];
+/// inputs.iter().for_each(|i| {
+///     stats.next(*i).sharpe(0.0001).map(|v| results.push(v));
+/// });
+///
+/// let expected: [f64; 6] = [3.8893, 3.125, 2.1523, 2.7897, -0.0898, 0.8354];
+/// for (i, e) in expected.iter().enumerate() {
+///     assert_approx_eq!(e, results[i], 0.0001);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ReturnStatistics<T> {
+    /// Rolling moments of the returns
+    moments: RollingMoments<T>,
+    /// Returns of the window ordered by value, behind the downside deviation
+    downside: RbTree<T>,
+    /// Sum of log growth factors `Σ ln(1 + r)`
+    log_growth: Kbn<T>,
+    /// Sum of log growth factors since inception, the level of the log wealth curve
+    log_wealth: Kbn<T>,
+    /// Maximum drawdown of the log wealth levels from the start of the window
+    drawdown: RollingDrawdown<T>,
+    /// Number of periods per year used for annualization
+    periods_per_year: T,
+}
+
+impl<T: PrimitiveFloat> Clone for ReturnStatistics<T> {
+    fn clone(&self) -> Self {
+        Self {
+            moments: self.moments.clone(),
+            downside: self.downside.clone(),
+            log_growth: self.log_growth.clone(),
+            log_wealth: self.log_wealth.clone(),
+            drawdown: self.drawdown.clone(),
+            periods_per_year: self.periods_per_year,
+        }
+    }
+}
+
+impl<T> ReturnStatistics<T>
+where
+    T: Default + Clone + Float + PrimitiveFloat,
+{
+    /// Creates a new `ReturnStatistics` instance with the specified period.
+    ///
+    /// Ratios are not annualized until [`set_periods_per_year`](Self::set_periods_per_year)
+    /// is called.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The period of the statistics
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics object
+    pub fn new(period: usize) -> Self {
+        let mut moments = RollingMoments::new(period);
+        moments.set_ddof(true);
+        let mut stats = Self {
+            moments,
+            downside: RbTree::with_moments(period),
+            log_growth: Kbn::default(),
+            log_wealth: Kbn::default(),
+            drawdown: RollingDrawdown::logarithmic(period + 1),
+            periods_per_year: T::one(),
+        };
+        stats.reset();
+        stats
+    }
+
+    /// Returns the period of the statistics
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The period of the statistics
+    pub const fn period(&self) -> usize {
+        self.moments.period()
+    }
+
+    /// Returns the Delta Degrees of Freedom of the standard deviation
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True for the sample standard deviation, the default
+    pub const fn ddof(&self) -> bool {
+        self.moments.ddof()
+    }
+
+    /// Sets the Delta Degrees of Freedom of the standard deviation
+    ///
+    /// # Arguments
+    ///
+    /// * `ddof` - True for the sample standard deviation, false for the population one
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub const fn set_ddof(&mut self, ddof: bool) -> &mut Self {
+        self.moments.set_ddof(ddof);
+        self
+    }
+
+    /// Returns the number of periods per year used for annualization
+    ///
+    /// # Returns
+    ///
+    /// * `T` - The number of periods per year
+    pub const fn periods_per_year(&self) -> T {
+        self.periods_per_year
+    }
+
+    /// Sets the number of periods per year used for annualization
+    ///
+    /// # Arguments
+    ///
+    /// * `periods_per_year` - The number of periods per year, e.g. 252 for daily returns
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub const fn set_periods_per_year(&mut self, periods_per_year: T) -> &mut Self {
+        self.periods_per_year = periods_per_year;
+        self
+    }
+
+    /// Resets the statistics
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub fn reset(&mut self) -> &mut Self {
+        self.moments.reset();
+        self.downside.reset();
+        self.log_growth = Kbn::default();
+        self.log_wealth = Kbn::default();
+        self.drawdown.reset();
+        // The wealth curve starts at 1 at the beginning of the window
+        self.drawdown.push(T::zero());
+        self
+    }
+
+    /// Recomputes the statistics, could be called to avoid
+    /// prolonged compounding of floating rounding errors
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub fn recompute(&mut self) -> &mut Self {
+        self.moments.recompute();
+        self.downside.reset();
+        self.log_growth = Kbn::default();
+        self.log_wealth = Kbn::default();
+        self.drawdown.reset();
+        self.drawdown.push(T::zero());
+        for &r in self.moments.iter() {
+            self.downside.insert(r);
+            self.log_growth += r.ln_1p();
+            self.log_wealth += r.ln_1p();
+            self.drawdown.push(self.log_wealth.total());
+        }
+        self
+    }
+
+    /// Updates the statistics with a new return
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The new simple return of the period
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub fn next(&mut self, value: T) -> &mut Self {
+        self.moments.next(value);
+        if let Some(popped) = self.moments.popped() {
+            self.downside.remove(popped);
+            self.log_growth -= popped.ln_1p();
+            self.drawdown.pop();
+        }
+        self.downside.insert(value);
+        self.log_growth += value.ln_1p();
+        self.log_wealth += value.ln_1p();
+        self.drawdown.push(self.log_wealth.total());
+        self
+    }
+
+    /// Returns the number of returns in the window as `T`
+    fn len(&self) -> Option<T> {
+        self.moments
+            .is_ready()
+            .then(|| T::from(self.moments.count()))
+            .flatten()
+    }
+
+    /// Returns the mean return per period
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The mean return, or `None` if the window is not full
+    pub fn mean(&self) -> Option<T> {
+        self.moments.mean()
+    }
+
+    /// Returns the standard deviation of the returns per period
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The standard deviation, or `None` if the window is not full
+    pub fn stddev(&self) -> Option<T> {
+        self.moments.stddev()
+    }
+
+    /// Returns the annualized volatility, the standard deviation scaled by the square root
+    /// of the periods per year
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The annualized volatility, or `None` if the window is not full
+    pub fn annualized_volatility(&self) -> Option<T> {
+        Some(self.stddev()? * self.periods_per_year.sqrt())
+    }
+
+    /// Returns the downside deviation of the returns below a target
+    ///
+    /// The downside deviation `sqrt(Σ min(r - target, 0)² / n)` only penalizes returns below
+    /// the target, normalized by the full window length as proposed by Sortino and Price.
+    /// The squared shortfalls are expanded from the central moments of the returns below the
+    /// target, so any target is served in O(log n).
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The minimum acceptable return per period
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The downside deviation per period, or `None` if the window is not full
+    pub fn downside_deviation(&self, target: T) -> Option<T> {
+        self.len()?;
+        Some(Float::sqrt(self.downside.lower_partial_moment(2, target)?))
+    }
+
+    /// Returns the compounded return over the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The cumulative return `Π(1 + r) - 1`, or `None` if the window is not full
+    pub fn cumulative_return(&self) -> Option<T> {
+        self.len()?;
+        Some(self.log_growth.total().exp_m1())
+    }
+
+    /// Returns the compound annual growth rate over the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The annualized return `Π(1 + r)^(periods_per_year / n) - 1`, or `None`
+    ///   if the window is not full
+    pub fn annualized_return(&self) -> Option<T> {
+        let n = self.len()?;
+        Some((self.log_growth.total() * self.periods_per_year / n).exp_m1())
+    }
+
+    /// Returns the maximum drawdown of the compounded returns in the window
+    ///
+    /// The wealth curve starts at 1 at the beginning of the window, so a loss on the first
+    /// return already counts as a drawdown. The largest decline of the log wealth levels is
+    /// tracked by a rolling drawdown as returns enter and leave the window, so the call is
+    /// O(1).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The maximum drawdown as a positive fraction, or `None` if the window is not full
+    pub fn max_drawdown(&self) -> Option<T> {
+        self.len()?;
+        Some(-Float::exp_m1(-self.drawdown.get()?))
+    }
+
+    /// Returns the annualized Sharpe ratio
+    ///
+    /// The Sharpe ratio `(mean - rf) / stddev · sqrt(periods_per_year)` measures the excess
+    /// return per unit of total volatility:
+    ///
+    /// - Ranks strategies and managers on a volatility-adjusted basis
+    /// - Drives volatility-targeted capital allocation across strategies
+    /// - Flags decaying edges as the rolling ratio trends towards zero
+    ///
+    /// # Arguments
+    ///
+    /// * `rf` - The risk-free rate per period
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Sharpe ratio, or `None` if the window is not full or the returns are constant
+    pub fn sharpe(&self, rf: T) -> Option<T> {
+        let stddev = self.stddev()?;
+        if stddev <= T::zero() {
+            return None;
+        }
+        Some((self.mean()? - rf) / stddev * self.periods_per_year.sqrt())
+    }
+
+    /// Returns the annualized Sortino ratio
+    ///
+    /// The Sortino ratio `(mean - target) / downside_deviation · sqrt(periods_per_year)`
+    /// replaces the volatility of the Sharpe ratio with the
+    /// [`downside_deviation`](Self::downside_deviation) below the target, so upside volatility
+    /// is not penalized:
+    ///
+    /// - Evaluates strategies with skewed, option-like payoffs
+    /// - Separates harmful volatility from favorable dispersion
+    /// - Complements the Sharpe ratio in risk-adjusted rankings
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The minimum acceptable return per period
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Sortino ratio, or `None` if the window is not full or no return
+    ///   falls below the target
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::ReturnStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = ReturnStatistics::new(5);
+    /// stats.set_periods_per_year(252.0);
+    /// let mut results = vec![];
+    /// let inputs = [0.01, -0.02, 0.015, 0.03, -0.01, 0.005, -0.025, 0.02, 0.01, -0.005];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).sortino(0.0).map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 6] = [7.9373, 6.3498, 3.9549, 5.2732, 0.0, 1.3923];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn sortino(&self, target: T) -> Option<T> {
+        let downside = self.downside_deviation(target)?;
+        if downside <= T::zero() {
+            return None;
+        }
+        Some((self.mean()? - target) / downside * self.periods_per_year.sqrt())
+    }
+
+    /// Returns the Calmar ratio
+    ///
+    /// The Calmar ratio divides the annualized return by the maximum drawdown of the window,
+    /// relating growth to the worst loss an investor had to sit through:
+    ///
+    /// - Evaluates trend-following and managed futures programs
+    /// - Sizes leverage against the depth of historical losses
+    /// - Highlights strategies whose returns depend on surviving deep drawdowns
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Calmar ratio, or `None` if the window is not full or has no drawdown
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::ReturnStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = ReturnStatistics::new(5);
+    /// stats.set_periods_per_year(252.0);
+    /// let mut results = vec![];
+    /// let inputs = [0.01, -0.02, 0.015, 0.03, -0.01, 0.005, -0.025, 0.02, 0.01, -0.005];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).calmar().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 6] = [118.834, 81.4717, 34.484, 53.5668, -1.0406, 9.9564];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.001);
+    /// }
+    /// ```
+    pub fn calmar(&self) -> Option<T> {
+        let max_drawdown = self.max_drawdown()?;
+        if max_drawdown <= T::zero() {
+            return None;
+        }
+        Some(self.annualized_return()? / max_drawdown)
+    }
+
+    /// Returns the Sterling ratio
+    ///
+    /// The Sterling ratio divides the annualized return by the maximum drawdown plus an
+    /// excess, conventionally 10%, which keeps the ratio finite for shallow drawdowns. This
+    /// follows the definition of `PerformanceAnalytics::SterlingRatio`.
+    ///
+    /// # Arguments
+    ///
+    /// * `excess` - The excess added to the maximum drawdown, e.g. `0.1`
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Sterling ratio, or `None` if the window is not full or the
+    ///   denominator is not positive
+    pub fn sterling(&self, excess: T) -> Option<T> {
+        let denominator = self.max_drawdown()? + excess;
+        if denominator <= T::zero() {
+            return None;
+        }
+        Some(self.annualized_return()? / denominator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+
+    const RETURNS: [f64; 10] = [
+        0.01, -0.02, 0.015, 0.03, -0.01, 0.005, -0.025, 0.02, 0.01, -0.005,
+    ];
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn return_ratios_work() {
+        let mut stats = ReturnStatistics::new(5);
+        stats.set_periods_per_year(252.0);
+        let mut results = vec![];
+        RETURNS.iter().for_each(|r| {
+            stats.next(*r);
+            if let Some(dd) = stats.downside_deviation(0.0) {
+                results.push((
+                    dd,
+                    stats.max_drawdown().unwrap(),
+                    stats.annualized_return().unwrap(),
+                    stats.sterling(0.1).unwrap(),
+                ));
+            }
+        });
+
+        let expected: [(f64, f64, f64, f64); 6] = [
+            (0.01, 0.02, 2.3767, 19.8057),
+            (0.01, 0.02, 1.6294, 13.5786),
+            (0.012, 0.0299, 1.0319, 7.9423),
+            (0.012, 0.0299, 1.6029, 12.3374),
+            (0.012, 0.0299, -0.0311, -0.2397),
+            (0.0114, 0.025, 0.2489, 1.9913),
+        ];
+        for (e, r) in expected.iter().zip(results.iter()) {
+            assert_approx_eq!(e.0, r.0, 0.0001);
+            assert_approx_eq!(e.1, r.1, 0.0001);
+            assert_approx_eq!(e.2, r.2, 0.0001);
+            assert_approx_eq!(e.3, r.3, 0.0001);
+        }
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn downside_deviation_below_target() {
+        let mut stats = ReturnStatistics::new(4);
+        RETURNS.iter().for_each(|r| {
+            stats.next(*r);
+        });
+
+        // Last window [-0.025, 0.02, 0.01, -0.005], shortfalls below 0.01 are 0.035 and 0.015
+        assert_approx_eq!(stats.downside_deviation(0.01).unwrap(), 0.019039, 0.000001);
+        assert_approx_eq!(stats.cumulative_return().unwrap(), -0.000577, 0.000001);
+
+        stats.reset();
+        assert_eq!(stats.sortino(0.01), None);
+        assert_eq!(stats.max_drawdown(), None);
+    }
+}
@@ -0,0 +1,605 @@
+use num_traits::Float;
+
+/// This module provides functionality for calculating exponentially weighted moments over a time series.
+///
+/// Unlike [`RollingMoments`](super::RollingMoments), which weights every observation in a fixed
+/// window equally, exponentially weighted moments decay the weight of older observations
+/// geometrically by `1 - alpha` on every update. No observation is ever evicted, so the state
+/// is O(1) regardless of how long the series runs.
+///
+/// The weighted central moments are updated with the pairwise (Pébay) update formulas, which
+/// avoids the catastrophic cancellation of raw power sums when values are large relative to
+/// their dispersion. The weighting scheme follows pandas `ewm`:
+///
+/// - `adjust = true`: the value `t` steps back has weight `(1 - alpha)^t`
+/// - `adjust = false`: the recursive form `y_t = (1 - alpha) * y_(t-1) + alpha * x_t`
+#[derive(Debug, Clone)]
+pub struct EwmMoments<T> {
+    /// Smoothing factor
+    alpha: T,
+    /// Whether weights are normalized over the full history (pandas `adjust=True`)
+    adjust: bool,
+    /// Delta Degrees of Freedom
+    ddof: bool,
+    /// Minimum number of observations before values are reported
+    min_periods: usize,
+    /// Number of observations seen
+    count: usize,
+    /// Most recent value pushed
+    value: Option<T>,
+    /// Sum of weights
+    weight_sum: T,
+    /// Sum of squared weights
+    weight_sq_sum: T,
+    /// Current weighted mean
+    mean: T,
+    /// Weighted sum of squared deviations
+    m2: T,
+    /// Weighted sum of cubed deviations
+    m3: T,
+    /// Weighted sum of fourth power deviations
+    m4: T,
+}
+
+impl<T: Float + Default> EwmMoments<T> {
+    /// Creates a new `EwmMoments` instance with the specified smoothing factor.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The smoothing factor, `0 < alpha <= 1`
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics object
+    pub fn new(alpha: T) -> Self {
+        assert!(
+            alpha > T::zero() && alpha <= T::one(),
+            "alpha must be in (0, 1]"
+        );
+        Self {
+            alpha,
+            adjust: true,
+            ddof: false,
+            min_periods: 1,
+            count: 0,
+            value: None,
+            weight_sum: T::zero(),
+            weight_sq_sum: T::zero(),
+            mean: T::zero(),
+            m2: T::zero(),
+            m3: T::zero(),
+            m4: T::zero(),
+        }
+    }
+
+    /// Converts a span into a smoothing factor, `alpha = 2 / (span + 1)`
+    ///
+    /// # Arguments
+    ///
+    /// * `span` - The span of the decay, `span >= 1`
+    ///
+    /// # Returns
+    ///
+    /// * `T` - The smoothing factor
+    pub fn alpha_from_span(span: T) -> T {
+        assert!(span >= T::one(), "span must be >= 1");
+        let _2 = T::one() + T::one();
+        _2 / (span + T::one())
+    }
+
+    /// Converts a half-life into a smoothing factor, `alpha = 1 - exp(-ln(2) / halflife)`
+    ///
+    /// # Arguments
+    ///
+    /// * `halflife` - The number of observations after which a weight halves, `halflife > 0`
+    ///
+    /// # Returns
+    ///
+    /// * `T` - The smoothing factor
+    pub fn alpha_from_halflife(halflife: T) -> T {
+        assert!(halflife > T::zero(), "halflife must be > 0");
+        let ln_2 = (T::one() + T::one()).ln();
+        T::one() - (-ln_2 / halflife).exp()
+    }
+
+    /// Converts a center of mass into a smoothing factor, `alpha = 1 / (1 + com)`
+    ///
+    /// # Arguments
+    ///
+    /// * `com` - The center of mass of the weights, `com >= 0`
+    ///
+    /// # Returns
+    ///
+    /// * `T` - The smoothing factor
+    pub fn alpha_from_com(com: T) -> T {
+        assert!(com >= T::zero(), "com must be >= 0");
+        T::one() / (T::one() + com)
+    }
+
+    /// Returns the smoothing factor
+    ///
+    /// # Returns
+    ///
+    /// * `T` - The smoothing factor
+    #[inline]
+    pub const fn alpha(&self) -> T {
+        self.alpha
+    }
+
+    /// Returns whether the weights are adjusted
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the weights are adjusted
+    #[inline]
+    pub const fn adjust(&self) -> bool {
+        self.adjust
+    }
+
+    /// Sets whether the weights are adjusted
+    ///
+    /// # Arguments
+    ///
+    /// * `adjust` - True to normalize the weights over the full history
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    #[inline]
+    pub const fn set_adjust(&mut self, adjust: bool) -> &mut Self {
+        self.adjust = adjust;
+        self
+    }
+
+    /// Returns the Delta Degrees of Freedom
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - The Delta Degrees of Freedom
+    #[inline]
+    pub const fn ddof(&self) -> bool {
+        self.ddof
+    }
+
+    /// Sets the Delta Degrees of Freedom
+    ///
+    /// # Arguments
+    ///
+    /// * `ddof` - The Delta Degrees of Freedom
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    #[inline]
+    pub const fn set_ddof(&mut self, ddof: bool) -> &mut Self {
+        self.ddof = ddof;
+        self
+    }
+
+    /// Returns the minimum number of observations before values are reported
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The minimum number of observations
+    #[inline]
+    pub const fn min_periods(&self) -> usize {
+        self.min_periods
+    }
+
+    /// Sets the minimum number of observations before values are reported
+    ///
+    /// # Arguments
+    ///
+    /// * `min_periods` - The minimum number of observations
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    #[inline]
+    pub const fn set_min_periods(&mut self, min_periods: usize) -> &mut Self {
+        self.min_periods = min_periods;
+        self
+    }
+
+    /// Resets the exponentially weighted moments
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    #[inline]
+    pub fn reset(&mut self) -> &mut Self {
+        self.count = 0;
+        self.value = None;
+        self.weight_sum = T::zero();
+        self.weight_sq_sum = T::zero();
+        self.mean = T::zero();
+        self.m2 = T::zero();
+        self.m3 = T::zero();
+        self.m4 = T::zero();
+        self
+    }
+
+    /// Updates the exponentially weighted moments with a new value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to update the moments with
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    #[inline]
+    pub fn next(&mut self, value: T) -> &mut Self {
+        self.value = Some(value);
+        self.count += 1;
+
+        if self.count == 1 {
+            self.weight_sum = T::one();
+            self.weight_sq_sum = T::one();
+            self.mean = value;
+            self.m2 = T::zero();
+            self.m3 = T::zero();
+            self.m4 = T::zero();
+            return self;
+        }
+
+        let decay = T::one() - self.alpha;
+        let (w_a, w) = self.next_weights();
+
+        // Decaying all past weights scales the weighted central sums but keeps the mean
+        let m2_a = self.m2 * decay;
+        let m3_a = self.m3 * decay;
+        let m4_a = self.m4 * decay;
+
+        let w_t = w_a + w;
+        let delta = value - self.mean;
+        let delta_w = delta / w_t;
+        let delta_w_sq = delta_w * delta_w;
+        let term = w_a * w * delta * delta_w;
+
+        let _3 = T::from(3.0).unwrap_or_default();
+        let _4 = T::from(4.0).unwrap_or_default();
+        let _6 = T::from(6.0).unwrap_or_default();
+
+        self.mean = self.mean + w * delta_w;
+        self.m4 = m4_a
+            + term * delta_w_sq * (w_a * w_a - w_a * w + w * w)
+            + _6 * w * w * delta_w_sq * m2_a
+            - _4 * w * delta_w * m3_a;
+        self.m3 = m3_a + term * delta_w * (w_a - w) - _3 * w * delta_w * m2_a;
+        self.m2 = m2_a + term;
+
+        self.weight_sum = w_t;
+        self.weight_sq_sum = self.weight_sq_sum * decay * decay + w * w;
+
+        self
+    }
+
+    /// Returns the decayed weight of the history and the weight of the next observation
+    ///
+    /// # Returns
+    ///
+    /// * `(T, T)` - The decayed sum of weights and the weight given to the next value
+    #[inline]
+    pub fn next_weights(&self) -> (T, T) {
+        let decay = T::one() - self.alpha;
+        let w = if self.adjust || self.count == 0 {
+            T::one()
+        } else {
+            self.alpha
+        };
+        (self.weight_sum * decay, w)
+    }
+
+    /// Returns the current weighted mean regardless of `min_periods`
+    ///
+    /// # Returns
+    ///
+    /// * `T` - The current weighted mean, zero if no values were seen
+    #[inline]
+    pub const fn current_mean(&self) -> T {
+        self.mean
+    }
+
+    /// Returns the sum of weights
+    ///
+    /// # Returns
+    ///
+    /// * `T` - The sum of weights
+    #[inline]
+    pub const fn weight_sum(&self) -> T {
+        self.weight_sum
+    }
+
+    /// Returns the number of observations seen
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of observations
+    #[inline]
+    pub const fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns true if enough observations were seen
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the calculation is ready
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.count > 0 && self.count >= self.min_periods
+    }
+
+    /// Returns the effective number of observations, `(Σw)² / Σw²`
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The effective number of observations
+    #[inline]
+    pub fn effective_count(&self) -> Option<T> {
+        if !self.is_ready() || self.weight_sq_sum <= T::zero() {
+            return None;
+        }
+        Some(self.weight_sum * self.weight_sum / self.weight_sq_sum)
+    }
+
+    /// Returns the bias correction factor `(Σw)² / ((Σw)² - Σw²)` when `ddof` is set
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The bias correction factor
+    #[inline]
+    pub fn bias_correction(&self) -> Option<T> {
+        if !self.ddof {
+            return Some(T::one());
+        }
+        let numerator = self.weight_sum * self.weight_sum;
+        let denominator = numerator - self.weight_sq_sum;
+        if denominator > T::zero() {
+            Some(numerator / denominator)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the exponentially weighted mean
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The mean, or `None` if not enough observations were seen
+    #[inline]
+    pub fn mean(&self) -> Option<T> {
+        self.is_ready().then_some(self.mean)
+    }
+
+    /// Returns the exponentially weighted variance
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The variance, or `None` if not enough observations were seen
+    #[inline]
+    pub fn variance(&self) -> Option<T> {
+        if !self.is_ready() {
+            return None;
+        }
+        let var = Float::max(self.m2 / self.weight_sum, T::zero());
+        self.bias_correction().map(|c| var * c)
+    }
+
+    /// Returns the exponentially weighted standard deviation
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The standard deviation, or `None` if not enough observations were seen
+    #[inline]
+    pub fn stddev(&self) -> Option<T> {
+        self.variance().map(|var| var.sqrt())
+    }
+
+    /// Returns the z-score of the most recent value
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The z-score if the standard deviation is positive, None otherwise
+    #[inline]
+    pub fn zscore(&self) -> Option<T> {
+        let value = self.value?;
+        let mean = self.mean()?;
+        let stddev = self.stddev()?;
+
+        if stddev > T::zero() {
+            Some((value - mean) / stddev)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the exponentially weighted skewness
+    ///
+    /// When `ddof` is true, the sample skewness correction is applied using the effective
+    /// number of observations.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The skewness if the variance is positive, None otherwise
+    #[inline]
+    pub fn skew(&self) -> Option<T> {
+        if !self.is_ready() || self.m2 <= T::zero() {
+            return None;
+        }
+
+        let m2 = self.m2 / self.weight_sum;
+        let m3 = self.m3 / self.weight_sum;
+        let g1 = m3 / (m2 * m2.sqrt());
+
+        if self.ddof {
+            let n = self.effective_count()?;
+            let _2 = T::from(2.0)?;
+            if n <= _2 {
+                return None;
+            }
+            Some((n * (n - T::one())).sqrt() / (n - _2) * g1)
+        } else {
+            Some(g1)
+        }
+    }
+
+    /// Returns the exponentially weighted excess kurtosis
+    ///
+    /// When `ddof` is true, the sample kurtosis correction is applied using the effective
+    /// number of observations.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The excess kurtosis if the variance is positive, None otherwise
+    #[inline]
+    pub fn kurt(&self) -> Option<T> {
+        if !self.is_ready() || self.m2 <= T::zero() {
+            return None;
+        }
+
+        let m2 = self.m2 / self.weight_sum;
+        let m4 = self.m4 / self.weight_sum;
+
+        let _1 = T::one();
+        let _2 = T::from(2.0)?;
+        let _3 = T::from(3.0)?;
+
+        if !self.ddof {
+            Some(m4 / (m2 * m2) - _3)
+        } else {
+            let n = self.effective_count()?;
+            if n <= _3 {
+                return None;
+            }
+            let sample_var = m2 * n / (n - _1);
+            let numerator = n * n * (n + _1);
+            let denominator = (n - _1) * (n - _2) * (n - _3);
+            let correction = (_3 * (n - _1) * (n - _1)) / ((n - _2) * (n - _3));
+
+            Some((numerator / denominator) * (m4 / (sample_var * sample_var)) - correction)
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+
+    #[test]
+    fn mean_works() {
+        let mut stats = EwmMoments::new(0.5);
+        let inputs = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let mut results = vec![];
+
+        inputs.iter().for_each(|i| {
+            if let Some(v) = stats.next(*i).mean() {
+                results.push(v)
+            }
+        });
+
+        let expected: [f64; 5] = [1.0, 1.666667, 2.428571, 3.266667, 4.161290];
+        for (i, e) in expected.iter().enumerate() {
+            assert_approx_eq!(e, results[i], 0.0001);
+        }
+
+        stats.reset().set_adjust(false);
+        results = vec![];
+        inputs.iter().for_each(|i| {
+            if let Some(v) = stats.next(*i).mean() {
+                results.push(v)
+            }
+        });
+
+        let expected: [f64; 5] = [1.0, 1.5, 2.25, 3.125, 4.0625];
+        for (i, e) in expected.iter().enumerate() {
+            assert_approx_eq!(e, results[i], 0.0001);
+        }
+    }
+
+    #[test]
+    fn variance_works() {
+        let mut stats = EwmMoments::new(0.5);
+        let inputs = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let mut results = vec![];
+
+        inputs.iter().for_each(|i| {
+            if let Some(v) = stats.next(*i).variance() {
+                results.push(v)
+            }
+        });
+
+        let expected: [f64; 5] = [0.0, 0.222222, 0.530612, 0.862222, 1.167534];
+        for (i, e) in expected.iter().enumerate() {
+            assert_approx_eq!(e, results[i], 0.0001);
+        }
+
+        stats.reset().set_ddof(true);
+        results = vec![];
+        inputs.iter().for_each(|i| {
+            if let Some(v) = stats.next(*i).variance() {
+                results.push(v)
+            }
+        });
+
+        let expected: [f64; 4] = [0.5, 0.928571, 1.385714, 1.809677];
+        for (i, e) in expected.iter().enumerate() {
+            assert_approx_eq!(e, results[i], 0.0001);
+        }
+    }
+
+    #[test]
+    fn adjust_false_variance_works() {
+        let mut stats = EwmMoments::new(0.5);
+        stats.set_adjust(false).set_ddof(true);
+        let inputs = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let mut results = vec![];
+
+        inputs.iter().for_each(|i| {
+            if let Some(v) = stats.next(*i).variance() {
+                results.push(v)
+            }
+        });
+
+        let expected: [f64; 4] = [0.5, 1.1, 1.690476, 2.158824];
+        for (i, e) in expected.iter().enumerate() {
+            assert_approx_eq!(e, results[i], 0.0001);
+        }
+    }
+
+    #[test]
+    fn equal_weights_match_population_moments() {
+        // With alpha -> 0 the weights become equal, so the moments must match the rolling ones
+        let mut stats = EwmMoments::new(1e-12);
+        let inputs = [25.4, 26.2, 26.0, 26.1];
+        inputs.iter().for_each(|i| {
+            stats.next(*i);
+        });
+
+        assert_approx_eq!(stats.skew().unwrap(), -0.97941, 0.0001);
+        assert_approx_eq!(stats.kurt().unwrap(), -0.7981, 0.0001);
+
+        stats.set_ddof(true);
+        assert_approx_eq!(stats.skew().unwrap(), -1.69639, 0.0001);
+        assert_approx_eq!(stats.kurt().unwrap(), 3.0144, 0.0001);
+    }
+
+    #[test]
+    fn min_periods_works() {
+        let mut stats = EwmMoments::new(0.5);
+        stats.set_min_periods(3);
+        assert!(stats.next(1.0).mean().is_none());
+        assert!(stats.next(2.0).mean().is_none());
+        assert!(stats.next(3.0).mean().is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "alpha must be in (0, 1]")]
+    fn invalid_alpha_panics() {
+        EwmMoments::<f64>::new(0.0);
+    }
+}
@@ -1,5 +1,29 @@
+mod ewm_moments;
+pub use ewm_moments::EwmMoments;
+
 mod rolling_mode;
 pub use rolling_mode::RollingMode;
 
 mod rolling_moments;
 pub use rolling_moments::RollingMoments;
+
+mod weighted_moments;
+pub use weighted_moments::WeightedMoments;
+
+mod rolling_wma;
+pub use rolling_wma::RollingWma;
+
+mod rolling_polyfit;
+pub use rolling_polyfit::RollingPolyfit;
+
+mod rolling_partial_moments;
+pub use rolling_partial_moments::RollingPartialMoments;
+
+mod rolling_drawdown;
+pub use rolling_drawdown::RollingDrawdown;
+
+mod rolling_ranks;
+pub use rolling_ranks::RollingRanks;
+
+mod rolling_comoments;
+pub use rolling_comoments::RollingCoMoments;
@@ -0,0 +1,247 @@
+use num_traits::Float;
+
+use crate::utils::Deque;
+
+/// A structure that keeps the means and the co-moment matrix of `N` series over a rolling
+/// window with push/pop calls.
+///
+/// The co-moments `C_ij = Σ (a_i - mean_i)·(a_j - mean_j)` are updated with the rolling form
+/// of Welford's algorithm as rows enter and leave the window, so every update is O(N²) and
+/// stays clear of the cancellation of raw power sums at price levels. The covariance of any
+/// two linear combinations `wᵀ·a` and `vᵀ·a` of the series is read as `wᵀ·C·v / n`, which
+/// lets weights that change on every bar, such as a hedge ratio, be applied to the whole
+/// window at once.
+#[derive(Debug, Clone)]
+pub struct RollingCoMoments<T, const N: usize> {
+    /// Rows of the window, oldest first
+    window: Deque<[T; N]>,
+    /// Number of rows in the window
+    count: T,
+    /// Means of the series
+    mean: [T; N],
+    /// Sums of the cross products of the deviations from the means
+    comoments: [[T; N]; N],
+}
+
+impl<T, const N: usize> RollingCoMoments<T, N>
+where
+    T: Default + Float,
+    [T; N]: Default,
+{
+    /// Creates a new `RollingCoMoments` instance for a window of `period` rows
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The number of rows in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The rolling co-moments object
+    pub fn new(period: usize) -> Self {
+        Self {
+            window: Deque::new(period),
+            count: T::zero(),
+            mean: [T::zero(); N],
+            comoments: [[T::zero(); N]; N],
+        }
+    }
+
+    /// Returns the number of rows of a full window
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The period of the window
+    pub const fn period(&self) -> usize {
+        self.window.capacity()
+    }
+
+    /// Returns whether the window is full
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True once the window holds `period` rows
+    pub const fn is_ready(&self) -> bool {
+        self.window.is_full()
+    }
+
+    /// Resets the window
+    pub fn reset(&mut self) {
+        self.window.reset();
+        self.count = T::zero();
+        self.mean = [T::zero(); N];
+        self.comoments = [[T::zero(); N]; N];
+    }
+
+    /// Adds a row to the window, evicting the oldest row once the window is full
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The values of the series
+    pub fn next(&mut self, row: [T; N]) {
+        if let Some(evicted) = self.window.push_back(row) {
+            self.update(evicted, false);
+        }
+        self.update(row, true);
+    }
+
+    /// Recomputes the means and co-moments from the rows of the window
+    pub fn recompute(&mut self) {
+        self.count = T::zero();
+        self.mean = [T::zero(); N];
+        self.comoments = [[T::zero(); N]; N];
+        for i in 0..self.window.len() {
+            if let Some(&row) = self.window.get(i) {
+                self.update(row, true);
+            }
+        }
+    }
+
+    /// Moves the means and co-moments as a row enters or leaves the window
+    ///
+    /// Both directions apply `C ± (a - mean_before)·(a - mean_after)ᵀ`, which is symmetric
+    /// as the two deviations are parallel.
+    fn update(&mut self, row: [T; N], entering: bool) {
+        self.count = if entering {
+            self.count + T::one()
+        } else {
+            self.count - T::one()
+        };
+        if self.count <= T::zero() {
+            self.count = T::zero();
+            self.mean = [T::zero(); N];
+            self.comoments = [[T::zero(); N]; N];
+            return;
+        }
+
+        let mut delta = [T::zero(); N];
+        for ((d, m), &a) in delta.iter_mut().zip(self.mean.iter_mut()).zip(&row) {
+            *d = a - *m;
+            *m = if entering {
+                *m + *d / self.count
+            } else {
+                *m - *d / self.count
+            };
+        }
+        for (c_row, &d) in self.comoments.iter_mut().zip(&delta) {
+            for ((c, &a), &m) in c_row.iter_mut().zip(&row).zip(&self.mean) {
+                *c = if entering {
+                    *c + d * (a - m)
+                } else {
+                    *c - d * (a - m)
+                };
+            }
+        }
+    }
+
+    /// Returns the mean of a linear combination of the series
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - The weights of the series
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The mean `wᵀ·mean`, or `None` if the window is not full
+    pub fn mean(&self, weights: &[T; N]) -> Option<T> {
+        if !self.is_ready() {
+            return None;
+        }
+        Some(
+            weights
+                .iter()
+                .zip(&self.mean)
+                .fold(T::zero(), |acc, (&w, &m)| acc + w * m),
+        )
+    }
+
+    /// Returns the covariance of two linear combinations of the series
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The weights of the first combination
+    /// * `b` - The weights of the second combination
+    /// * `ddof` - True to divide by `n - 1` instead of `n`
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The covariance `aᵀ·C·b / n`, or `None` if the window is not full
+    pub fn covariance(&self, a: &[T; N], b: &[T; N], ddof: bool) -> Option<T> {
+        if !self.is_ready() {
+            return None;
+        }
+        let n = if ddof {
+            self.count - T::one()
+        } else {
+            self.count
+        };
+        if n <= T::zero() {
+            return None;
+        }
+        let sum = self
+            .comoments
+            .iter()
+            .zip(a)
+            .fold(T::zero(), |acc, (c_row, &w)| {
+                acc + w * c_row
+                    .iter()
+                    .zip(b)
+                    .fold(T::zero(), |acc, (&c, &v)| acc + c * v)
+            });
+        Some(sum / n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn rolling_comoments_match_window() {
+        let inputs = [
+            [10_001.0, 5_000.5],
+            [10_003.5, 5_001.0],
+            [10_002.0, 5_002.5],
+            [10_006.0, 5_001.5],
+            [10_004.5, 5_003.0],
+            [10_007.0, 5_004.0],
+            [10_005.5, 5_002.0],
+            [10_009.0, 5_005.5],
+        ];
+        let period = 4;
+        let (a, b) = ([1.0, -2.0], [0.5, 1.0]);
+
+        let mut comoments = RollingCoMoments::new(period);
+        for (i, &row) in inputs.iter().enumerate() {
+            comoments.next(row);
+            if i + 1 < period {
+                assert_eq!(comoments.mean(&a), None);
+                continue;
+            }
+
+            let window = &inputs[i + 1 - period..=i];
+            let combine = |w: &[f64; 2], r: &[f64; 2]| w[0] * r[0] + w[1] * r[1];
+            let n = period as f64;
+            let mean_a = window.iter().map(|r| combine(&a, r)).sum::<f64>() / n;
+            let mean_b = window.iter().map(|r| combine(&b, r)).sum::<f64>() / n;
+            let cov: f64 = window
+                .iter()
+                .map(|r| (combine(&a, r) - mean_a) * (combine(&b, r) - mean_b))
+                .sum();
+
+            assert_approx_eq!(comoments.mean(&a).unwrap(), mean_a, 1e-9);
+            assert_approx_eq!(comoments.covariance(&a, &b, false).unwrap(), cov / n, 1e-9);
+            assert_approx_eq!(
+                comoments.covariance(&a, &b, true).unwrap(),
+                cov / (n - 1.0),
+                1e-9
+            );
+        }
+
+        let before = comoments.covariance(&a, &a, false).unwrap();
+        comoments.recompute();
+        assert_approx_eq!(comoments.covariance(&a, &a, false).unwrap(), before, 1e-9);
+    }
+}
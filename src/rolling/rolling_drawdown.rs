@@ -0,0 +1,240 @@
+use alloc::vec::Vec;
+use num_traits::Float;
+
+/// Peak, trough and maximum drawdown of a contiguous run of values
+#[derive(Debug, Clone, Copy)]
+struct Segment<T> {
+    /// Largest value of the run
+    max: T,
+    /// Smallest positive value of the run, infinite if there is none
+    min: T,
+    /// Largest peak-to-trough decline within the run
+    max_drawdown: T,
+}
+
+impl<T: Float> Segment<T> {
+    /// Creates the segment of a single value
+    fn new(value: T, logarithmic: bool) -> Self {
+        Self {
+            max: value,
+            min: if logarithmic || value > T::zero() {
+                value
+            } else {
+                T::infinity()
+            },
+            max_drawdown: T::zero(),
+        }
+    }
+
+    /// Joins the segment with the run that immediately follows it
+    ///
+    /// The deepest drawdown either lies within one of the runs or falls from the peak of
+    /// the earlier run to the trough of the later one.
+    fn then(self, later: Self, logarithmic: bool) -> Self {
+        let across = if logarithmic {
+            Float::max(self.max - later.min, T::zero())
+        } else if self.max > T::zero() && later.min.is_finite() {
+            Float::max((self.max - later.min) / self.max, T::zero())
+        } else {
+            T::zero()
+        };
+        Self {
+            max: Float::max(self.max, later.max),
+            min: Float::min(self.min, later.min),
+            max_drawdown: Float::max(Float::max(self.max_drawdown, later.max_drawdown), across),
+        }
+    }
+}
+
+/// A structure that tracks the maximum peak-to-trough drawdown of a rolling window with
+/// push/pop calls.
+///
+/// Drawdowns are relative declines `(peak - trough) / peak` where the peak precedes the
+/// trough, measured like [`SingleStatistics::drawdown`](crate::SingleStatistics::drawdown),
+/// so non-positive values never form a drawdown. In logarithmic mode the values are log
+/// levels, such as the cumulative log growth of an equity curve, and drawdowns are the
+/// declines `peak - trough` of any sign of level. As the maximum drawdown of two adjacent
+/// runs can be combined from their peaks, troughs and inner drawdowns, the window is kept
+/// as a two-stack queue: new values are folded into the aggregate of the back stack, and
+/// the front stack holds the aggregates of every suffix of the oldest values. Both updates
+/// and queries are amortized O(1).
+#[derive(Debug, Clone)]
+pub struct RollingDrawdown<T> {
+    /// Aggregates from each of the oldest values to the end of the front run, oldest last
+    front: Vec<Segment<T>>,
+    /// Values pushed since the last transfer to the front stack
+    back: Vec<T>,
+    /// Aggregate of the back stack
+    back_segment: Option<Segment<T>>,
+    /// Whether the values are log levels
+    logarithmic: bool,
+}
+
+impl<T: Float> RollingDrawdown<T> {
+    /// Creates a new `RollingDrawdown` instance for windows of up to `capacity` values
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The expected number of values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The rolling drawdown object
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            front: Vec::with_capacity(capacity),
+            back: Vec::with_capacity(capacity),
+            back_segment: None,
+            logarithmic: false,
+        }
+    }
+
+    /// Creates a new `RollingDrawdown` instance over log levels for windows of up to
+    /// `capacity` values
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The expected number of values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The rolling drawdown object, whose drawdowns are log declines
+    pub fn logarithmic(capacity: usize) -> Self {
+        Self {
+            logarithmic: true,
+            ..Self::new(capacity)
+        }
+    }
+
+    /// Resets the window
+    pub fn reset(&mut self) {
+        self.front.clear();
+        self.back.clear();
+        self.back_segment = None;
+    }
+
+    /// Adds a value entering the window
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to add
+    pub fn push(&mut self, value: T) {
+        self.back.push(value);
+        self.push_cumulative(value);
+    }
+
+    /// Adds a value to a window that never evicts, without retaining it
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to add
+    pub fn push_cumulative(&mut self, value: T) {
+        let segment = Segment::new(value, self.logarithmic);
+        self.back_segment = Some(match self.back_segment {
+            Some(back) => back.then(segment, self.logarithmic),
+            None => segment,
+        });
+    }
+
+    /// Removes the oldest value of the window
+    pub fn pop(&mut self) {
+        if self.front.is_empty() {
+            let mut suffix: Option<Segment<T>> = None;
+            for &value in self.back.iter().rev() {
+                let segment = Segment::new(value, self.logarithmic);
+                let joined = match suffix {
+                    Some(later) => segment.then(later, self.logarithmic),
+                    None => segment,
+                };
+                self.front.push(joined);
+                suffix = Some(joined);
+            }
+            self.back.clear();
+            self.back_segment = None;
+        }
+        self.front.pop();
+    }
+
+    /// Returns the maximum drawdown of the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The maximum drawdown, or `None` if the window is empty
+    pub fn get(&self) -> Option<T> {
+        let segment = match (self.front.last(), self.back_segment) {
+            (Some(&front), Some(back)) => front.then(back, self.logarithmic),
+            (Some(&front), None) => front,
+            (None, back) => back?,
+        };
+        Some(segment.max_drawdown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+
+    fn brute_force(window: &[f64]) -> f64 {
+        let mut worst: f64 = 0.0;
+        for (i, &peak) in window.iter().enumerate() {
+            for &trough in &window[i..] {
+                if peak > 0.0 && trough > 0.0 {
+                    worst = worst.max((peak - trough) / peak);
+                }
+            }
+        }
+        worst
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn rolling_drawdown_matches_brute_force() {
+        let inputs = [
+            100.0, 110.0, 105.0, 115.0, 100.0, 95.0, 105.0, 110.0, 100.0, 120.0, -5.0, 90.0, 130.0,
+            80.0, 85.0, 125.0,
+        ];
+        let period = 5;
+        let mut drawdown = RollingDrawdown::new(period);
+
+        for (i, &v) in inputs.iter().enumerate() {
+            drawdown.push(v);
+            if i >= period {
+                drawdown.pop();
+            }
+            let start = (i + 1).saturating_sub(period);
+            assert_approx_eq!(
+                drawdown.get().unwrap(),
+                brute_force(&inputs[start..=i]),
+                1e-12
+            );
+        }
+
+        drawdown.reset();
+        assert_eq!(drawdown.get(), None);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn rolling_drawdown_of_log_levels() {
+        let inputs = [0.0, 0.3, -0.2, 0.1, -0.5, -0.4, 0.2, -0.1, 0.6, -0.3];
+        let period = 4;
+        let mut drawdown = RollingDrawdown::logarithmic(period);
+
+        for (i, &v) in inputs.iter().enumerate() {
+            drawdown.push(v);
+            if i >= period {
+                drawdown.pop();
+            }
+            let window = &inputs[(i + 1).saturating_sub(period)..=i];
+            let mut worst: f64 = 0.0;
+            for (j, &peak) in window.iter().enumerate() {
+                for &trough in &window[j..] {
+                    worst = worst.max(peak - trough);
+                }
+            }
+            assert_approx_eq!(drawdown.get().unwrap(), worst, 1e-12);
+        }
+    }
+}
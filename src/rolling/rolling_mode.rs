@@ -153,13 +153,13 @@ where
 
     /// Helper method to update frequency buckets when a value's frequency changes
     fn update_freq_buckets(&mut self, value: OrderedFloat<T>, old_freq: usize, new_freq: usize) {
-        if old_freq > 0 {
-            if let Some(bucket) = self.freq_bucket.get_mut(&old_freq) {
-                bucket.remove(&value);
+        if old_freq > 0
+            && let Some(bucket) = self.freq_bucket.get_mut(&old_freq)
+        {
+            bucket.remove(&value);
 
-                if bucket.is_empty() {
-                    self.freq_bucket.remove(&old_freq);
-                }
+            if bucket.is_empty() {
+                self.freq_bucket.remove(&old_freq);
             }
         }
 
@@ -1,23 +1,72 @@
 use num_traits::Float;
 
-use crate::utils::Deque;
+use crate::utils::{Deque, TimeWindow};
 
 type Kbn<T> = compensated_summation::KahanBabuskaNeumaier<T>;
 
+/// Buffer holding the observations of a rolling window
+#[derive(Debug, Clone)]
+enum Window<T> {
+    /// Fixed number of most recent observations
+    Count(Deque<T>),
+    /// Observations within a trailing time span
+    Time(TimeWindow<T>),
+    /// Every observation since inception, only the count is kept
+    Expanding(usize),
+}
+
+impl<T: Default + Clone> Window<T> {
+    #[inline]
+    const fn len(&self) -> usize {
+        match self {
+            Self::Count(buf) => buf.len(),
+            Self::Time(buf) => buf.len(),
+            Self::Expanding(count) => *count,
+        }
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        match self {
+            Self::Count(buf) => {
+                buf.reset();
+            }
+            Self::Time(buf) => buf.reset(),
+            Self::Expanding(count) => *count = 0,
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        let (count, time) = match self {
+            Self::Count(buf) => (Some(buf.iter()), None),
+            Self::Time(buf) => (None, Some(buf.iter())),
+            Self::Expanding(_) => (None, None),
+        };
+        count
+            .into_iter()
+            .flatten()
+            .chain(time.into_iter().flatten())
+    }
+}
+
 /// This module provides functionality for calculating rolling statistical moments over a time series.
 ///
 /// Rolling moments are essential statistics that help analyze the characteristics of data over
 /// a moving window. These include measures like mean, variance, skewness, and kurtosis, which
 /// provide insights into the distribution and behavior of financial time series data.
 ///
+/// The window is either a fixed number of observations, a trailing time span, in which
+/// case observations are evicted as their timestamps age out of the window, or an expanding
+/// window that never evicts. The expanding window keeps no observations and updates the
+/// central moments in place with Welford/Pébay recurrences, so its memory is O(1).
+///
 /// The implementation uses Kahan-Babuska-Neumaier summation algorithm for numerical stability
 /// when computing these statistics over potentially large datasets with floating-point values.
 #[derive(Debug, Clone)]
 pub struct RollingMoments<T> {
-    /// Statistics period
-    period: usize,
-    /// Ring buffer to maintain the window
-    buf: Deque<T>,
+    /// Buffer to maintain the window
+    buf: Window<T>,
     /// Most recent value pushed into the rolling window.
     value: Option<T>,
     /// Most recent value popped out of the rolling window (if full).
@@ -32,6 +81,8 @@ pub struct RollingMoments<T> {
     sum_cube: Kbn<T>,
     /// Sum of fourth powers
     sum_quad: Kbn<T>,
+    /// Sum of position-weighted inputs `Σ i·x_i`, the oldest value at position 0
+    sum_ix: Kbn<T>,
     /// Current mean
     mean: T,
     /// Second central moment
@@ -53,9 +104,35 @@ impl<T: Float + Default> RollingMoments<T> {
     ///
     /// * `Self` - The statistics object
     pub fn new(period: usize) -> Self {
+        Self::with_window(Window::Count(Deque::new(period)))
+    }
+
+    /// Creates a new `RollingMoments` instance over a trailing time span.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - The length of the window in timestamp units
+    /// * `max_capacity` - The optional upper bound on the number of observations held
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics object
+    pub fn with_time_window(duration: u64, max_capacity: Option<usize>) -> Self {
+        Self::with_window(Window::Time(TimeWindow::new(duration, max_capacity)))
+    }
+
+    /// Creates a new `RollingMoments` instance over an expanding window that never evicts.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics object
+    pub fn expanding() -> Self {
+        Self::with_window(Window::Expanding(0))
+    }
+
+    fn with_window(buf: Window<T>) -> Self {
         Self {
-            period,
-            buf: Deque::new(period),
+            buf,
             value: None,
             popped: None,
             ddof: false,
@@ -63,6 +140,7 @@ impl<T: Float + Default> RollingMoments<T> {
             sum_sq: Kbn::default(),
             sum_cube: Kbn::default(),
             sum_quad: Kbn::default(),
+            sum_ix: Kbn::default(),
             mean: T::zero(),
             m2: T::zero(),
             m3: T::zero(),
@@ -77,6 +155,7 @@ impl<T: Float + Default> RollingMoments<T> {
         self.sum_sq = Kbn::default();
         self.sum_cube = Kbn::default();
         self.sum_quad = Kbn::default();
+        self.sum_ix = Kbn::default();
     }
 
     /// Resets the moments
@@ -121,6 +200,53 @@ impl<T: Float + Default> RollingMoments<T> {
         Some(())
     }
 
+    /// Updates the central moments of an expanding window with a new value
+    ///
+    /// Uses the single-pass recurrences of Welford and Pébay, which stay accurate over
+    /// arbitrarily long series where raw power sums would lose precision.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value just added to the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<()>` - `None` if the window is empty, `Some(())` otherwise
+    fn update_expanding_moments(&mut self, value: T) -> Option<()> {
+        let n = T::from(self.buf.len())?;
+        if n == T::zero() {
+            self.reset_moments();
+            return None;
+        }
+
+        let _1 = T::one();
+        let _2 = T::from(2.0)?;
+        let _3 = T::from(3.0)?;
+        let _4 = T::from(4.0)?;
+        let _6 = T::from(6.0)?;
+
+        let n1 = n - _1;
+        let sum_m2 = self.m2 * n1;
+        let sum_m3 = self.m3 * n1;
+        let sum_m4 = self.m4 * n1;
+
+        let delta = value - self.mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term1 = delta * delta_n * n1;
+
+        self.mean = self.mean + delta_n;
+        let sum_m4 = sum_m4 + term1 * delta_n2 * (n * n - _3 * n + _3) + _6 * delta_n2 * sum_m2
+            - _4 * delta_n * sum_m3;
+        let sum_m3 = sum_m3 + term1 * delta_n * (n - _2) - _3 * delta_n * sum_m2;
+        let sum_m2 = sum_m2 + term1;
+
+        self.m2 = sum_m2 / n;
+        self.m3 = sum_m3 / n;
+        self.m4 = sum_m4 / n;
+        Some(())
+    }
+
     /// Returns the Delta Degrees of Freedom
     ///
     /// # Returns
@@ -173,40 +299,161 @@ impl<T: Float + Default> RollingMoments<T> {
     ///
     #[inline]
     pub fn next(&mut self, value: T) -> &mut Self {
+        let popped = match &mut self.buf {
+            Window::Count(buf) => buf.push_back(value),
+            Window::Time(buf) => buf.push(buf.latest().unwrap_or_default(), value),
+            Window::Expanding(count) => {
+                *count += 1;
+                None
+            }
+        };
+        self.push(value, popped)
+    }
+
+    /// Updates the rolling moments with a new timestamped value
+    ///
+    /// For time windows, expired values should be removed with [`expire`](Self::expire)
+    /// first. For count and expanding windows the timestamp is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `ts` - The timestamp of the value, a stale timestamp is treated as the latest one
+    /// * `value` - The value to update the rolling moments with
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The rolling moments object
+    #[inline]
+    pub fn next_at(&mut self, ts: u64, value: T) -> &mut Self {
+        let popped = match &mut self.buf {
+            Window::Count(buf) => buf.push_back(value),
+            Window::Time(buf) => buf.push(ts, value),
+            Window::Expanding(count) => {
+                *count += 1;
+                None
+            }
+        };
+        self.push(value, popped)
+    }
+
+    /// Removes the oldest value if it has aged out of a time window
+    ///
+    /// Should be called repeatedly until it returns `None` before adding a value at `now`.
+    /// Count and expanding windows never expire values.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current timestamp
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The expired value, or `None` if nothing expired
+    #[inline]
+    pub fn expire(&mut self, now: u64) -> Option<T> {
+        let Window::Time(buf) = &mut self.buf else {
+            return None;
+        };
+        let expired = buf.pop_expired(now)?;
+        self.remove_sums(expired);
+        self.update_central_moments();
+        Some(expired)
+    }
+
+    /// Returns the sequence number of the oldest value in a time window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The sequence number, or `None` for count and expanding windows
+    #[inline]
+    pub const fn head(&self) -> Option<usize> {
+        match &self.buf {
+            Window::Time(buf) => Some(buf.head()),
+            _ => None,
+        }
+    }
+
+    /// Returns the length of the time window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u64>` - The length of the window in timestamp units, or `None` for count and expanding windows
+    #[inline]
+    pub const fn duration(&self) -> Option<u64> {
+        match &self.buf {
+            Window::Time(buf) => Some(buf.duration()),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the window is expanding and never evicts values
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True for expanding windows
+    #[inline]
+    pub const fn is_expanding(&self) -> bool {
+        matches!(self.buf, Window::Expanding(_))
+    }
+
+    /// Adds a value to the sums, removing the popped value if any
+    #[inline]
+    fn push(&mut self, value: T, popped: Option<T>) -> &mut Self {
         self.value = Some(value);
-        self.popped = self.buf.push_back(value);
+        self.popped = popped;
         if let Some(popped) = self.popped {
-            self.sum -= popped;
-            self.sum_sq -= popped * popped;
-            self.sum_cube -= popped * popped * popped;
-            self.sum_quad -= popped * popped * popped * popped;
+            self.remove_sums(popped);
         }
 
         self.sum += value;
         self.sum_sq += value * value;
         self.sum_cube += value * value * value;
         self.sum_quad += value * value * value * value;
+        self.sum_ix += T::from(self.buf.len() - 1).unwrap_or_else(T::nan) * value;
 
-        self.update_central_moments();
+        if self.is_expanding() {
+            self.update_expanding_moments(value);
+        } else {
+            self.update_central_moments();
+        }
 
         self
     }
 
+    /// Removes the oldest value from the sums
+    ///
+    /// Every remaining value moves one position closer to the front, which lowers the
+    /// position-weighted sum by the sum of the remaining values.
+    #[inline]
+    fn remove_sums(&mut self, value: T) {
+        self.sum -= value;
+        self.sum_sq -= value * value;
+        self.sum_cube -= value * value * value;
+        self.sum_quad -= value * value * value * value;
+        self.sum_ix -= self.sum.total();
+    }
+
     /// Recomputes the rolling statistics, could be called to avoid
     /// prolonged compounding of floating rounding errors
     ///
+    /// Expanding windows keep no observations and are left untouched.
+    ///
     /// # Returns
     ///
     /// * `&mut Self` - The rolling moments object
     #[inline]
     pub fn recompute(&mut self) {
+        if self.is_expanding() {
+            return;
+        }
+
         self.reset_sums();
 
-        for &v in self.buf.iter() {
+        for (i, &v) in self.buf.iter().enumerate() {
             self.sum += v;
             self.sum_sq += v * v;
             self.sum_cube += v * v * v;
             self.sum_quad += v * v * v * v;
+            self.sum_ix += T::from(i).unwrap_or_else(T::nan) * v;
         }
 
         self.update_central_moments();
@@ -232,12 +479,18 @@ impl<T: Float + Default> RollingMoments<T> {
 
     /// Returns the window period
     ///
+    /// For time and expanding windows this is the number of values currently in the window.
+    ///
     /// # Returns
     ///
     /// * `usize` - The window period
     #[inline]
     pub const fn period(&self) -> usize {
-        self.period
+        match &self.buf {
+            Window::Count(buf) => buf.capacity(),
+            Window::Time(buf) => buf.len(),
+            Window::Expanding(count) => *count,
+        }
     }
 
     /// Returns true of the calculation was ready
@@ -247,7 +500,11 @@ impl<T: Float + Default> RollingMoments<T> {
     /// * `bool` - True if the calculation was ready
     #[inline]
     pub const fn is_ready(&self) -> bool {
-        self.buf.is_full()
+        match &self.buf {
+            Window::Count(buf) => buf.is_full(),
+            Window::Time(buf) => !buf.is_empty(),
+            Window::Expanding(count) => *count > 0,
+        }
     }
 
     /// Returns the number of elements in the buffer
@@ -261,6 +518,8 @@ impl<T: Float + Default> RollingMoments<T> {
     }
 
     /// Returns an iterator over the elements in the ring buffer
+    ///
+    /// Expanding windows keep no observations and yield nothing.
     #[inline]
     pub fn iter(&self) -> impl Iterator<Item = &T> {
         self.buf.iter()
@@ -286,6 +545,60 @@ impl<T: Float + Default> RollingMoments<T> {
         self.is_ready().then_some(self.sum_sq.total())
     }
 
+    /// Returns the slope of the least squares line through the values against their positions
+    ///
+    /// Positions run from 0 for the oldest value to `n - 1` for the newest, so the centered
+    /// sums follow in closed form from the running `Σ x_i` and `Σ i·x_i`:
+    ///
+    /// `slope = (Σ i·x_i - (n - 1) / 2 · Σ x_i) / (n (n² - 1) / 12)`
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The slope if the window is ready and holds at least two values, None otherwise
+    #[inline]
+    pub fn linreg_slope(&self) -> Option<T> {
+        if !self.is_ready() || self.count() < 2 {
+            return None;
+        }
+        let n = T::from(self.count())?;
+        let _1 = T::one();
+        let mean_i = (n - _1) / T::from(2)?;
+        let sxx = n * (n * n - _1) / T::from(12)?;
+        Some((self.sum_ix.total() - mean_i * self.sum.total()) / sxx)
+    }
+
+    /// Returns the residual sum of squares of the least squares line against positions
+    ///
+    /// The explained sum of squares is `slope² · n (n² - 1) / 12`, so the residual sum of
+    /// squares is the total sum of squares `n · m2` less the explained part. Rounding can
+    /// push the difference slightly below zero for a perfect fit, hence the clamp.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The residual sum of squares if the slope is available, None otherwise
+    #[inline]
+    pub fn linreg_sse(&self) -> Option<T> {
+        let slope = self.linreg_slope()?;
+        let n = T::from(self.count())?;
+        let sxx = n * (n * n - T::one()) / T::from(12)?;
+        Some((n * self.m2 - slope * slope * sxx).max(T::zero()))
+    }
+
+    /// Returns the coefficient of determination of the least squares line against positions
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The R² if the slope is available and the values vary, None otherwise
+    #[inline]
+    pub fn linreg_r2(&self) -> Option<T> {
+        let sse = self.linreg_sse()?;
+        let sst = T::from(self.count())? * self.m2;
+        if sst <= T::zero() {
+            return None;
+        }
+        Some((T::one() - sse / sst).max(T::zero()))
+    }
+
     /// Returns the mean of all values in the rolling window
     ///
     /// # Returns
@@ -725,4 +1038,153 @@ mod tests {
             assert_approx_eq!(e, results[i], 0.0001);
         }
     }
+    #[test]
+    fn time_window_works() {
+        let mut stats = RollingMoments::with_time_window(60, None);
+        let inputs = [
+            (0, 10.0),
+            (20, 11.0),
+            (40, 12.0),
+            (60, 13.0),
+            (70, 9.0),
+            (130, 14.0),
+        ];
+        let mut results = vec![];
+
+        inputs.iter().for_each(|&(ts, value)| {
+            while stats.expire(ts).is_some() {}
+            if let Some(v) = stats.next_at(ts, value).mean() {
+                results.push((stats.count(), v))
+            }
+        });
+
+        let expected = [
+            (1, 10.0),
+            (2, 10.5),
+            (3, 11.0),
+            (3, 12.0),
+            (4, 11.25),
+            (1, 14.0),
+        ];
+        assert_eq!(&results, &expected);
+        assert_eq!(stats.head(), Some(5));
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn linreg_slope_works() {
+        let mut stats = RollingMoments::new(5);
+        let inputs = [10.0, 10.5, 11.2, 10.9, 11.5, 11.9, 12.3, 12.1, 11.8, 12.5];
+        let mut results = vec![];
+
+        inputs.iter().for_each(|i| {
+            if let Some(v) = stats.next(*i).linreg_slope() {
+                results.push(v)
+            }
+        });
+
+        let expected: [f64; 6] = [0.34, 0.31, 0.32, 0.32, 0.08, 0.07];
+        for (i, e) in expected.iter().enumerate() {
+            assert_approx_eq!(e, results[i], 1e-12);
+        }
+
+        stats.recompute();
+        assert_approx_eq!(stats.linreg_slope().unwrap(), 0.07, 1e-12);
+
+        let mut stats = RollingMoments::with_time_window(60, None);
+        for (ts, value) in [(0, 10.0), (20, 11.0), (40, 12.0), (60, 13.0), (70, 9.0)] {
+            while stats.expire(ts).is_some() {}
+            stats.next_at(ts, value);
+        }
+        assert_approx_eq!(stats.linreg_slope().unwrap(), -0.5, 1e-12);
+        while stats.expire(130).is_some() {}
+        assert_eq!(stats.next_at(130, 14.0).linreg_slope(), None);
+
+        let mut stats = RollingMoments::expanding();
+        [25.4, 26.2, 26.0, 26.1, 25.8, 25.9, 26.3, 26.2, 26.5]
+            .iter()
+            .for_each(|&v| {
+                stats.next(v);
+            });
+        assert_approx_eq!(stats.linreg_slope().unwrap(), 0.08, 1e-12);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn linreg_sse_r2_works() {
+        let mut stats = RollingMoments::new(5);
+        let inputs = [10.0, 10.5, 11.2, 10.9, 11.5, 11.9, 12.3, 12.1, 11.8, 12.5];
+        let mut results = vec![];
+
+        inputs.iter().for_each(|i| {
+            if let Some(v) = stats.next(*i).linreg_sse().zip(stats.linreg_r2()) {
+                results.push(v)
+            }
+        });
+
+        let expected: [(f64, f64); 6] = [
+            (0.232, 0.8329),
+            (0.199, 0.8284),
+            (0.208, 0.8312),
+            (0.208, 0.8312),
+            (0.304, 0.1739),
+            (0.279, 0.1494),
+        ];
+        for (i, e) in expected.iter().enumerate() {
+            assert_approx_eq!(e.0, results[i].0, 1e-9);
+            assert_approx_eq!(e.1, results[i].1, 1e-4);
+        }
+
+        stats.reset();
+        [1.0, 3.0, 5.0, 7.0, 9.0].iter().for_each(|&v| {
+            stats.next(v);
+        });
+        assert_approx_eq!(stats.linreg_sse().unwrap(), 0.0, 1e-12);
+        assert_approx_eq!(stats.linreg_r2().unwrap(), 1.0, 1e-12);
+
+        stats.reset();
+        [4.0; 5].iter().for_each(|&v| {
+            stats.next(v);
+        });
+        assert_eq!(stats.linreg_r2(), None);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn expanding_works() {
+        let mut stats = RollingMoments::expanding();
+        let inputs = [25.4, 26.2, 26.0, 26.1, 25.8, 25.9, 26.3, 26.2, 26.5];
+        let mut reference = RollingMoments::new(inputs.len());
+
+        for (i, &v) in inputs.iter().enumerate() {
+            stats.next(v);
+            assert_eq!(stats.count(), i + 1);
+            assert!(stats.is_ready());
+            assert_eq!(stats.popped(), None);
+        }
+        inputs.iter().for_each(|&v| {
+            reference.next(v);
+        });
+
+        assert_approx_eq!(stats.mean().unwrap(), reference.mean().unwrap(), 1e-12);
+        assert_approx_eq!(
+            stats.variance().unwrap(),
+            reference.variance().unwrap(),
+            1e-12
+        );
+        assert_approx_eq!(stats.skew().unwrap(), -0.6696414260692249, 1e-12);
+        assert_approx_eq!(stats.kurt().unwrap(), -0.004579985390796202, 1e-12);
+        assert_approx_eq!(stats.sum().unwrap(), reference.sum().unwrap(), 1e-9);
+
+        stats.recompute();
+        assert_approx_eq!(
+            stats.variance().unwrap(),
+            reference.variance().unwrap(),
+            1e-12
+        );
+
+        stats.reset();
+        assert_eq!(stats.count(), 0);
+        assert_eq!(stats.mean(), None);
+    }
 }
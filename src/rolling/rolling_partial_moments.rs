@@ -0,0 +1,178 @@
+use num_traits::Float;
+
+type Kbn<T> = compensated_summation::KahanBabuskaNeumaier<T>;
+
+/// Highest order of the partial moments
+const MAX_ORDER: usize = 4;
+
+/// A structure that tracks lower and upper partial moments relative to a fixed threshold
+/// with push/pop calls from a rolling window.
+///
+/// The partial sums `Σ max(t - x, 0)^k` and `Σ max(x - t, 0)^k` are kept for the orders
+/// `k = 0..=4`, where order 0 counts the values strictly below or above the threshold. As the
+/// threshold does not move, a value contributes the same terms when it leaves the window as
+/// when it entered, so eviction subtracts them exactly and every update is O(1).
+///
+/// The owner of the window feeds the values in and out, and passes the window length when
+/// the moments are read.
+#[derive(Debug, Clone)]
+pub struct RollingPartialMoments<T> {
+    /// Threshold the moments are measured from
+    threshold: T,
+    /// Lower partial sums `Σ max(t - x, 0)^k`
+    lower: [Kbn<T>; MAX_ORDER + 1],
+    /// Upper partial sums `Σ max(x - t, 0)^k`
+    upper: [Kbn<T>; MAX_ORDER + 1],
+}
+
+impl<T: Float + Default> RollingPartialMoments<T> {
+    /// Creates a new `RollingPartialMoments` instance with the specified threshold
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - The threshold the moments are measured from
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The partial moments object
+    pub fn new(threshold: T) -> Self {
+        Self {
+            threshold,
+            lower: Default::default(),
+            upper: Default::default(),
+        }
+    }
+
+    /// Returns the threshold the moments are measured from
+    ///
+    /// # Returns
+    ///
+    /// * `T` - The threshold
+    pub const fn threshold(&self) -> T {
+        self.threshold
+    }
+
+    /// Resets the partial sums
+    pub fn reset(&mut self) {
+        self.lower = Default::default();
+        self.upper = Default::default();
+    }
+
+    /// Adds a value entering the window
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to add
+    pub fn push(&mut self, value: T) {
+        self.update(value, T::one());
+    }
+
+    /// Removes a value leaving the window
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to remove
+    pub fn pop(&mut self, value: T) {
+        self.update(value, -T::one());
+    }
+
+    /// Adds the partial powers of a value, scaled by `sign`, to the sums on its side
+    fn update(&mut self, value: T, sign: T) {
+        let (sums, distance) = if value < self.threshold {
+            (&mut self.lower, self.threshold - value)
+        } else if value > self.threshold {
+            (&mut self.upper, value - self.threshold)
+        } else {
+            return;
+        };
+
+        let mut power = sign;
+        for s in sums.iter_mut() {
+            *s += power;
+            power = power * distance;
+        }
+    }
+
+    /// Returns the lower partial moment `Σ max(t - x, 0)^k / n`
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - The order `k` of the moment, between 0 and 4
+    /// * `n` - The number of values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The lower partial moment, or `None` if the order is out of range or
+    ///   the window is empty
+    pub fn lower(&self, order: usize, n: usize) -> Option<T> {
+        Self::moment(self.lower.get(order)?, n)
+    }
+
+    /// Returns the upper partial moment `Σ max(x - t, 0)^k / n`
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - The order `k` of the moment, between 0 and 4
+    /// * `n` - The number of values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The upper partial moment, or `None` if the order is out of range or
+    ///   the window is empty
+    pub fn upper(&self, order: usize, n: usize) -> Option<T> {
+        Self::moment(self.upper.get(order)?, n)
+    }
+
+    /// Normalizes a partial sum by the window length
+    fn moment(sum: &Kbn<T>, n: usize) -> Option<T> {
+        if n == 0 {
+            return None;
+        }
+        Some(Float::max(sum.total(), T::zero()) / T::from(n)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn partial_moments_evict_exactly() {
+        let inputs = [0.5, -1.0, 2.0, 0.0, -3.0, 1.5, -0.5];
+        let period = 4;
+        let mut pm = RollingPartialMoments::new(0.0);
+
+        for (i, &v) in inputs.iter().enumerate() {
+            pm.push(v);
+            if i >= period {
+                pm.pop(inputs[i - period]);
+            }
+            if i + 1 < period {
+                continue;
+            }
+
+            let window = &inputs[i + 1 - period..=i];
+            for k in 0..=MAX_ORDER {
+                let lower: f64 = window
+                    .iter()
+                    .filter(|&&x| x < 0.0)
+                    .map(|&x| (-x).powi(k as i32))
+                    .sum();
+                let upper: f64 = window
+                    .iter()
+                    .filter(|&&x| x > 0.0)
+                    .map(|&x| x.powi(k as i32))
+                    .sum();
+                assert_approx_eq!(pm.lower(k, period).unwrap(), lower / 4.0, 1e-12);
+                assert_approx_eq!(pm.upper(k, period).unwrap(), upper / 4.0, 1e-12);
+            }
+        }
+
+        assert_eq!(pm.lower(5, period), None);
+        pm.reset();
+        assert_eq!(pm.upper(2, period), Some(0.0));
+    }
+}
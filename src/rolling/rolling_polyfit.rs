@@ -0,0 +1,437 @@
+use num_traits::Float;
+
+use crate::utils::Deque;
+
+type Kbn<T> = compensated_summation::KahanBabuskaNeumaier<T>;
+
+/// Highest supported polynomial degree
+const MAX_DEGREE: usize = 4;
+
+/// Number of coefficients of a polynomial of the highest supported degree
+const MAX_TERMS: usize = MAX_DEGREE + 1;
+
+/// Binomial coefficients `C(k, j)` for `k, j <= MAX_DEGREE`
+const BINOMIAL: [[u8; MAX_TERMS]; MAX_TERMS] = [
+    [1, 0, 0, 0, 0],
+    [1, 1, 0, 0, 0],
+    [1, 2, 1, 0, 0],
+    [1, 3, 3, 1, 0],
+    [1, 4, 6, 4, 1],
+];
+
+/// This module provides the running sums behind rolling least squares polynomial fits.
+///
+/// Positions in the window are mapped onto `t ∈ [-1, 1]`, the oldest value at -1 and the
+/// newest at 1, which keeps the normal equations well conditioned for every period. The
+/// Gram matrix `Σ t^(a+b)` only depends on the period, so it is inverted once on construction.
+///
+/// The moment sums `Σ t^k·x` are kept for every power up to the degree. When the window
+/// slides, every remaining value moves one step `h` towards the front, and the binomial
+/// expansion of `(t - h)^k` rewrites each sum from the lower-order sums, so an update costs
+/// O(degree²) regardless of the period. The coefficients are then solved in O(degree²)
+/// from the precomputed inverse.
+///
+/// The implementation uses Kahan-Babuska-Neumaier summation algorithm for numerical stability
+/// when computing these statistics over potentially large datasets with floating-point values.
+#[derive(Debug, Clone)]
+pub struct RollingPolyfit<T> {
+    /// Buffer to maintain the window
+    buf: Deque<T>,
+    /// Degree of the polynomial
+    degree: usize,
+    /// Center of the positions, `(period - 1) / 2`
+    center: T,
+    /// Half-range of the positions, `(period - 1) / 2`, also the inverse of the step `h`
+    scale: T,
+    /// Moment sums `Σ t^k·x` for `k = 0..=degree`
+    sums: [Kbn<T>; MAX_TERMS],
+    /// Inverse of the Gram matrix `Σ t^(a+b)` over the full window
+    gram_inv: [[T; MAX_TERMS]; MAX_TERMS],
+    /// Coefficients against the scaled position `t`, lowest power first
+    coeffs: [T; MAX_TERMS],
+}
+
+impl<T: Float + Default> RollingPolyfit<T> {
+    /// Creates a new `RollingPolyfit` instance with the specified period and degree.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The period of the statistics
+    /// * `degree` - The degree of the polynomial
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics object
+    ///
+    /// # Panics
+    ///
+    /// Panics if `degree` is not between 1 and 4 or `period` is not greater than `degree`
+    pub fn new(period: usize, degree: usize) -> Self {
+        assert!(
+            (1..=MAX_DEGREE).contains(&degree),
+            "degree must be between 1 and 4"
+        );
+        assert!(period > degree, "period must be > degree");
+
+        let half = T::from(period - 1).unwrap_or_else(T::nan) / (T::one() + T::one());
+        let mut stats = Self {
+            buf: Deque::new(period),
+            degree,
+            center: half,
+            scale: half,
+            sums: Default::default(),
+            gram_inv: [[T::zero(); MAX_TERMS]; MAX_TERMS],
+            coeffs: [T::zero(); MAX_TERMS],
+        };
+        stats.gram_inv = stats.invert_gram();
+        stats
+    }
+
+    /// Returns the scaled position `t` of the i-th value of a full window
+    #[inline]
+    fn position(&self, i: usize) -> T {
+        (T::from(i).unwrap_or_else(T::nan) - self.center) / self.scale
+    }
+
+    /// Inverts the Gram matrix `Σ t^(a+b)` with Gauss-Jordan elimination and partial pivoting
+    ///
+    /// # Returns
+    ///
+    /// * `[[T; MAX_TERMS]; MAX_TERMS]` - The inverse, padded with zeros beyond the degree
+    fn invert_gram(&self) -> [[T; MAX_TERMS]; MAX_TERMS] {
+        let m = self.degree + 1;
+
+        let mut power_sums = [T::zero(); 2 * MAX_DEGREE + 1];
+        for i in 0..self.period() {
+            let t = self.position(i);
+            let mut p = T::one();
+            for s in power_sums.iter_mut().take(2 * self.degree + 1) {
+                *s = *s + p;
+                p = p * t;
+            }
+        }
+
+        let mut a = [[T::zero(); MAX_TERMS]; MAX_TERMS];
+        let mut inv = [[T::zero(); MAX_TERMS]; MAX_TERMS];
+        for r in 0..m {
+            a[r][..m].copy_from_slice(&power_sums[r..r + m]);
+            inv[r][r] = T::one();
+        }
+
+        for col in 0..m {
+            let pivot = (col..m)
+                .max_by(|&i, &j| {
+                    a[i][col]
+                        .abs()
+                        .partial_cmp(&a[j][col].abs())
+                        .unwrap_or(core::cmp::Ordering::Equal)
+                })
+                .unwrap_or(col);
+            a.swap(col, pivot);
+            inv.swap(col, pivot);
+
+            let d = a[col][col];
+            for c in 0..m {
+                a[col][c] = a[col][c] / d;
+                inv[col][c] = inv[col][c] / d;
+            }
+
+            for r in (0..m).filter(|&r| r != col) {
+                let f = a[r][col];
+                for c in 0..m {
+                    a[r][c] = a[r][c] - f * a[col][c];
+                    inv[r][c] = inv[r][c] - f * inv[col][c];
+                }
+            }
+        }
+
+        inv
+    }
+
+    /// Solves the normal equations for the coefficients against `t`
+    #[inline]
+    fn solve(&mut self) {
+        let m = self.degree + 1;
+        for r in 0..m {
+            self.coeffs[r] = (0..m).fold(T::zero(), |acc, c| {
+                acc + self.gram_inv[r][c] * self.sums[c].total()
+            });
+        }
+    }
+
+    /// Adds a value at scaled position `t` to the moment sums
+    #[inline]
+    fn add_sums(&mut self, t: T, value: T) {
+        let mut p = value;
+        for s in self.sums.iter_mut().take(self.degree + 1) {
+            *s += p;
+            p = p * t;
+        }
+    }
+
+    /// Moves every value in the moment sums one step `h` towards the front
+    ///
+    /// Uses `Σ (t - h)^k·x = Σ_j C(k, j)·(-h)^(k-j)·Σ t^j·x`, updating the highest power
+    /// first so the lower-order sums are still unshifted when they are read.
+    #[inline]
+    fn shift_sums(&mut self) {
+        let neg_h = -self.scale.recip();
+        for k in (1..=self.degree).rev() {
+            let mut delta = T::zero();
+            let mut h_pow = T::one();
+            for j in (0..k).rev() {
+                h_pow = h_pow * neg_h;
+                let c = T::from(BINOMIAL[k][j]).unwrap_or_else(T::nan);
+                delta = delta + c * h_pow * self.sums[j].total();
+            }
+            self.sums[k] += delta;
+        }
+    }
+
+    /// Resets the running sums
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    #[inline]
+    pub fn reset(&mut self) -> &mut Self {
+        self.buf.reset();
+        self.sums = Default::default();
+        self.coeffs = [T::zero(); MAX_TERMS];
+        self
+    }
+
+    /// Updates the running sums with a new value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to update the running sums with
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    #[inline]
+    pub fn next(&mut self, value: T) -> &mut Self {
+        if let Some(popped) = self.buf.push_back(value) {
+            self.add_sums(-T::one(), -popped);
+            self.shift_sums();
+        }
+
+        let t = self.position(self.buf.len() - 1);
+        self.add_sums(t, value);
+
+        if self.is_ready() {
+            self.solve();
+        }
+
+        self
+    }
+
+    /// Recomputes the running sums, could be called to avoid
+    /// prolonged compounding of floating rounding errors
+    #[inline]
+    pub fn recompute(&mut self) {
+        self.sums = Default::default();
+
+        for i in 0..self.buf.len() {
+            let t = self.position(i);
+            let value = self.buf.get(i).copied().unwrap_or_else(T::nan);
+            self.add_sums(t, value);
+        }
+
+        if self.is_ready() {
+            self.solve();
+        }
+    }
+
+    /// Returns the window period
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The window period
+    #[inline]
+    pub const fn period(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Returns the degree of the polynomial
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The degree of the polynomial
+    #[inline]
+    pub const fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Returns true of the calculation was ready
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the calculation was ready
+    #[inline]
+    pub const fn is_ready(&self) -> bool {
+        self.buf.is_full()
+    }
+
+    /// Returns the coefficients against the bar position, lowest power first
+    ///
+    /// Positions run from 0 for the oldest value to `period - 1` for the newest. The
+    /// coefficients against `t = (i - c) / s` are expanded with the binomial theorem.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<[T; MAX_TERMS]>` - The coefficients padded with zeros beyond the degree if the window is ready, None otherwise
+    #[inline]
+    pub fn coefficients(&self) -> Option<[T; MAX_TERMS]> {
+        if !self.is_ready() {
+            return None;
+        }
+
+        let inv_s = self.scale.recip();
+        let neg_c = -self.center;
+        let mut out = [T::zero(); MAX_TERMS];
+        let mut s_pow = T::one();
+        for (k, binomial) in BINOMIAL.iter().enumerate().take(self.degree + 1) {
+            let b = self.coeffs[k] * s_pow;
+            let mut c_pow = T::one();
+            for j in (0..=k).rev() {
+                let c = T::from(binomial[j])?;
+                out[j] = out[j] + b * c * c_pow;
+                c_pow = c_pow * neg_c;
+            }
+            s_pow = s_pow * inv_s;
+        }
+        Some(out)
+    }
+
+    /// Returns the fitted polynomial and its first two derivatives at scaled position `t`
+    ///
+    /// Derivatives are taken with respect to the bar position, one bar being `1 / s` in `t`.
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - The scaled position
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(T, T, T)>` - A tuple containing (value, first, second derivative) if the window is ready, None otherwise
+    #[inline]
+    pub fn eval(&self, t: T) -> Option<(T, T, T)> {
+        if !self.is_ready() {
+            return None;
+        }
+
+        let (mut p, mut dp, mut ddp) = (T::zero(), T::zero(), T::zero());
+        for k in (0..=self.degree).rev() {
+            ddp = ddp * t + dp + dp;
+            dp = dp * t + p;
+            p = p * t + self.coeffs[k];
+        }
+
+        let inv_s = self.scale.recip();
+        Some((p, dp * inv_s, ddp * inv_s * inv_s))
+    }
+
+    /// Returns the fitted polynomial and its first two derivatives at the newest value
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(T, T, T)>` - A tuple containing (value, first, second derivative) if the window is ready, None otherwise
+    #[inline]
+    pub fn eval_last(&self) -> Option<(T, T, T)> {
+        self.eval(T::one())
+    }
+
+    /// Returns the fitted polynomial extrapolated one bar past the newest value
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The one-step forecast if the window is ready, None otherwise
+    #[inline]
+    pub fn forecast(&self) -> Option<T> {
+        self.eval(T::one() + self.scale.recip()).map(|(p, _, _)| p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn quadratic_fit_works() {
+        let mut stats = RollingPolyfit::new(5, 2);
+        let inputs = [10.0, 10.5, 11.2, 10.9, 11.5, 11.9, 12.3, 12.1, 11.8, 12.5];
+        let mut results = vec![];
+
+        inputs.iter().for_each(|i| {
+            if let Some(v) = stats.next(*i).coefficients() {
+                results.push(v)
+            }
+        });
+
+        let expected: [[f64; 3]; 6] = [
+            [10.0257, 0.5686, -0.0571],
+            [10.6229, 0.2243, 0.0214],
+            [11.0914, -0.0229, 0.0857],
+            [10.8714, 0.7771, -0.1143],
+            [11.4743, 0.6514, -0.1429],
+            [12.0514, -0.0729, 0.0357],
+        ];
+        for (i, e) in expected.iter().enumerate() {
+            for (j, c) in e.iter().enumerate() {
+                assert_approx_eq!(c, results[i][j], 0.0001);
+            }
+            assert_eq!(results[i][3], 0.0);
+        }
+
+        let (fitted, slope, accel) = stats.eval_last().unwrap();
+        assert_approx_eq!(fitted, 12.3314, 0.0001);
+        assert_approx_eq!(slope, 0.2129, 0.0001);
+        assert_approx_eq!(accel, 0.0714, 0.0001);
+        assert_approx_eq!(stats.forecast().unwrap(), 12.58, 0.0001);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn exact_polynomial_works() {
+        let poly = |i: f64| 3.0 - 2.0 * i + 0.5 * i * i - 0.25 * i * i * i + 0.01 * i * i * i * i;
+        let mut stats = RollingPolyfit::new(30, 4);
+        for i in 0..200 {
+            stats.next(poly(i as f64));
+        }
+
+        let last = 199.0;
+        let (fitted, _, _) = stats.eval_last().unwrap();
+        assert_approx_eq!(fitted, poly(last), 1e-6 * poly(last).abs());
+        assert_approx_eq!(
+            stats.forecast().unwrap(),
+            poly(last + 1.0),
+            1e-6 * poly(last).abs()
+        );
+
+        stats.recompute();
+        let c = stats.coefficients().unwrap();
+        let origin = last - 29.0;
+        let expected = [poly(origin), 0.0, 0.0, 0.0, 0.01];
+        assert_approx_eq!(c[0], expected[0], 1e-6 * expected[0].abs());
+        assert_approx_eq!(c[4], expected[4], 1e-9);
+    }
+
+    #[test]
+    fn recompute_works() {
+        let mut stats = RollingPolyfit::new(20, 3);
+        for i in 0..1_000 {
+            stats.next(1_000.0 + (i as f64 * 0.37).sin());
+        }
+        let before = stats.coefficients().unwrap_or_default();
+        stats.recompute();
+        let after = stats.coefficients().unwrap_or_default();
+        for (b, a) in before.iter().zip(after.iter()) {
+            assert_approx_eq!(b, a, 1e-6);
+        }
+    }
+}
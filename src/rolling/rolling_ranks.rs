@@ -0,0 +1,362 @@
+use alloc::vec::Vec;
+use num_traits::Float;
+use ordered_float::{OrderedFloat, PrimitiveFloat};
+
+use crate::utils::{Deque, MIN_GROWABLE_CAPACITY, RbTree};
+
+/// A structure that keeps the values of both sides of a rolling window of pairs in
+/// order-statistic trees with push/pop calls, behind the Spearman and Kendall rank
+/// correlations.
+///
+/// Ties share the average of the ranks they span, like pandas `rank()`. Each side is held
+/// in an [`RbTree`], so a pair enters or leaves the window in O(log n), and the rank of any
+/// value is read from the counts of the smaller and equal values with `rank_counts` in
+/// O(log n). The tie counts of both sides move by the number of equal values found the same
+/// way.
+///
+/// The rank sums behind the Spearman correlation and the concordance behind the Kendall
+/// correlation depend on the joint order of both sides, which a tree over one side cannot
+/// aggregate, so they are computed when read: Spearman ranks every pair of the window
+/// against the trees, and Kendall sorts the pairs by `x` and counts the smaller and larger
+/// `y` values seen before each of them in a tree, both in O(n log n).
+///
+/// Pairs with a NaN value are held so that pops stay aligned with the window, but they
+/// are left out of the trees and both correlations.
+#[derive(Debug, Clone)]
+pub struct RollingRanks<T> {
+    /// Pairs of the window, oldest first
+    pairs: Deque<(T, T)>,
+    /// Whether the buffers grow and shrink with the window
+    growable: bool,
+    /// Ranked `x` values of the window in order
+    xs: RbTree<T>,
+    /// Ranked `y` values of the window in order
+    ys: RbTree<T>,
+    /// Number of pairs of pairs tied in `x`
+    ties_x: usize,
+    /// Number of pairs of pairs tied in `y`
+    ties_y: usize,
+}
+
+impl<T> RollingRanks<T>
+where
+    T: Default + Clone + Float + PrimitiveFloat,
+{
+    /// Creates a new `RollingRanks` instance for a window of `period` pairs
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The number of pairs in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The rolling ranks object
+    pub fn new(period: usize) -> Self {
+        Self::with_capacity(period, false)
+    }
+
+    /// Creates a new `RollingRanks` instance whose buffers grow and shrink with the window
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The rolling ranks object
+    pub fn growable() -> Self {
+        Self::with_capacity(MIN_GROWABLE_CAPACITY, true)
+    }
+
+    fn with_capacity(capacity: usize, growable: bool) -> Self {
+        Self {
+            pairs: Deque::new(capacity),
+            growable,
+            xs: RbTree::new(capacity),
+            ys: RbTree::new(capacity),
+            ties_x: 0,
+            ties_y: 0,
+        }
+    }
+
+    /// Resets the window
+    pub fn reset(&mut self) {
+        self.pairs.reset();
+        self.xs.reset();
+        self.ys.reset();
+        self.ties_x = 0;
+        self.ties_y = 0;
+    }
+
+    /// Returns the number of ranked pairs in the window
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of pairs in the window without a NaN value
+    pub const fn len(&self) -> usize {
+        self.xs.total_count()
+    }
+
+    /// Returns whether a pair takes part in the ranks
+    fn is_ranked(x: T, y: T) -> bool {
+        !Float::is_nan(x) && !Float::is_nan(y)
+    }
+
+    /// Adds a pair entering the window
+    ///
+    /// The oldest pair must be popped first once a fixed-size window is full. A pair with a
+    /// NaN value is held unranked.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The first value of the pair
+    /// * `y` - The second value of the pair
+    pub fn push(&mut self, x: T, y: T) {
+        if self.growable && self.pairs.is_full() {
+            self.pairs.resize(self.pairs.capacity() * 2);
+        }
+        self.pairs.push_back((x, y));
+
+        if Self::is_ranked(x, y) {
+            // Every equal value already in the window ties with the new one
+            self.ties_x += self.xs.rank_counts(x).1;
+            self.ties_y += self.ys.rank_counts(y).1;
+            if self.growable {
+                self.xs.insert_growing(x);
+                self.ys.insert_growing(y);
+            } else {
+                self.xs.insert(x);
+                self.ys.insert(y);
+            }
+        }
+    }
+
+    /// Removes the oldest pair of the window
+    pub fn pop(&mut self) {
+        let Some((x, y)) = self.pairs.pop_front() else {
+            return;
+        };
+
+        let cap = self.pairs.capacity();
+        if self.growable && cap > MIN_GROWABLE_CAPACITY && self.pairs.len() <= cap / 4 {
+            self.pairs.resize(Ord::max(cap / 2, MIN_GROWABLE_CAPACITY));
+        }
+
+        if Self::is_ranked(x, y) {
+            if self.growable {
+                self.xs.remove_shrinking(x);
+                self.ys.remove_shrinking(y);
+            } else {
+                self.xs.remove(x);
+                self.ys.remove(y);
+            }
+            // Every equal value left in the window tied with the evicted one
+            self.ties_x -= self.xs.rank_counts(x).1;
+            self.ties_y -= self.ys.rank_counts(y).1;
+        }
+    }
+
+    /// Returns the average rank of a value among the values of a tree, starting at 1
+    ///
+    /// Every smaller value adds one to the rank and every other equal value one half.
+    fn average_rank(values: &RbTree<T>, value: T) -> Option<T> {
+        let (less, equal) = values.rank_counts(value);
+        let half = T::one() / (T::one() + T::one());
+        Some(T::from(less)? + (T::from(equal)? + T::one()) * half)
+    }
+
+    /// Returns the Spearman rank correlation of the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Pearson correlation of the ranks, or `None` if the window holds
+    ///   fewer than two pairs or either side is constant
+    pub fn spearman(&self) -> Option<T> {
+        let n = T::from(self.len())?;
+        if self.len() < 2 {
+            return None;
+        }
+
+        let (mut sum_xy, mut sum_x_sq, mut sum_y_sq) = (T::zero(), T::zero(), T::zero());
+        for &(x, y) in self.pairs.iter() {
+            if Self::is_ranked(x, y) {
+                let rank_x = Self::average_rank(&self.xs, x)?;
+                let rank_y = Self::average_rank(&self.ys, y)?;
+                sum_xy = sum_xy + rank_x * rank_y;
+                sum_x_sq = sum_x_sq + rank_x * rank_x;
+                sum_y_sq = sum_y_sq + rank_y * rank_y;
+            }
+        }
+
+        let mean = (n + T::one()) / (T::one() + T::one());
+        let correction = n * mean * mean;
+        let cov = sum_xy - correction;
+        let var_x = sum_x_sq - correction;
+        let var_y = sum_y_sq - correction;
+        if var_x <= T::zero() || var_y <= T::zero() {
+            return None;
+        }
+        Some(cov / Float::sqrt(var_x * var_y))
+    }
+
+    /// Returns the Kendall tau-b rank correlation of the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The concordance score over the tie-corrected number of pairs of
+    ///   pairs, or `None` if the window holds fewer than two pairs or either side is constant
+    pub fn kendall(&self) -> Option<T> {
+        let n = self.len();
+        let total = n * n.saturating_sub(1) / 2;
+        let untied_x = T::from(total - self.ties_x)?;
+        let untied_y = T::from(total - self.ties_y)?;
+        if untied_x <= T::zero() || untied_y <= T::zero() {
+            return None;
+        }
+
+        let mut sorted: Vec<(T, T)> = self
+            .pairs
+            .iter()
+            .copied()
+            .filter(|&(x, y)| Self::is_ranked(x, y))
+            .collect();
+        sorted.sort_unstable_by_key(|&(x, _)| OrderedFloat(x));
+
+        // Pairs with a smaller `x` are concordant if their `y` is smaller, and discordant if
+        // it is larger. Pairs tied in `x` enter the tree together, so they count neither way.
+        let mut below = RbTree::new(n);
+        let (mut concordant, mut discordant) = (0, 0);
+        for group in sorted.chunk_by(|a, b| a.0 == b.0) {
+            for &(_, y) in group {
+                let (less, equal) = below.rank_counts(y);
+                concordant += less;
+                discordant += below.total_count() - less - equal;
+            }
+            for &(_, y) in group {
+                below.insert(y);
+            }
+        }
+        let score = T::from(concordant)? - T::from(discordant)?;
+        Some(score / Float::sqrt(untied_x * untied_y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+
+    fn average_ranks(values: &[f64]) -> Vec<f64> {
+        values
+            .iter()
+            .map(|&v| {
+                let less = values.iter().filter(|&&o| o < v).count() as f64;
+                let equal = values.iter().filter(|&&o| o == v).count() as f64;
+                less + (equal + 1.0) / 2.0
+            })
+            .collect()
+    }
+
+    fn pearson(x: &[f64], y: &[f64]) -> f64 {
+        let n = x.len() as f64;
+        let (mx, my) = (x.iter().sum::<f64>() / n, y.iter().sum::<f64>() / n);
+        let cov: f64 = x.iter().zip(y).map(|(a, b)| (a - mx) * (b - my)).sum();
+        let vx: f64 = x.iter().map(|a| (a - mx).powi(2)).sum();
+        let vy: f64 = y.iter().map(|b| (b - my).powi(2)).sum();
+        cov / (vx * vy).sqrt()
+    }
+
+    fn kendall_tau_b(x: &[f64], y: &[f64]) -> f64 {
+        let n = x.len();
+        let (mut score, mut ties_x, mut ties_y) = (0.0, 0.0, 0.0);
+        for i in 0..n {
+            for j in i + 1..n {
+                score += ((x[i] - x[j]).signum() * (y[i] - y[j]).signum())
+                    * f64::from(x[i] != x[j] && y[i] != y[j]);
+                ties_x += f64::from(x[i] == x[j]);
+                ties_y += f64::from(y[i] == y[j]);
+            }
+        }
+        let total = (n * (n - 1) / 2) as f64;
+        score / ((total - ties_x) * (total - ties_y)).sqrt()
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn rolling_ranks_match_ranked_window() {
+        let inputs = [
+            (1.0, 3.0),
+            (4.0, 1.0),
+            (2.0, 2.0),
+            (4.0, 5.0),
+            (3.0, 5.0),
+            (5.0, 4.0),
+            (2.0, 6.0),
+            (4.0, 2.0),
+            (6.0, 7.0),
+            (1.0, 1.0),
+        ];
+        let period = 5;
+
+        for growable in [false, true] {
+            let mut ranks = if growable {
+                RollingRanks::growable()
+            } else {
+                RollingRanks::new(period)
+            };
+            for (i, &(x, y)) in inputs.iter().enumerate() {
+                if i >= period {
+                    ranks.pop();
+                }
+                ranks.push(x, y);
+
+                let window = &inputs[(i + 1).saturating_sub(period)..=i];
+                let xs: Vec<f64> = window.iter().map(|p| p.0).collect();
+                let ys: Vec<f64> = window.iter().map(|p| p.1).collect();
+                let (rx, ry) = (average_ranks(&xs), average_ranks(&ys));
+                for (&(x, y), (&ex, &ey)) in ranks.pairs.iter().zip(rx.iter().zip(&ry)) {
+                    assert_eq!(RollingRanks::average_rank(&ranks.xs, x).unwrap(), ex);
+                    assert_eq!(RollingRanks::average_rank(&ranks.ys, y).unwrap(), ey);
+                }
+                if i > 0 {
+                    assert_approx_eq!(ranks.spearman().unwrap(), pearson(&rx, &ry), 1e-12);
+                    assert_approx_eq!(ranks.kendall().unwrap(), kendall_tau_b(&xs, &ys), 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn rolling_ranks_skip_nan_pairs() {
+        let inputs = [
+            (1.0, 3.0),
+            (f64::NAN, 1.0),
+            (2.0, 2.0),
+            (4.0, f64::NAN),
+            (3.0, 5.0),
+            (5.0, 4.0),
+            (2.0, 6.0),
+        ];
+        let period = 4;
+
+        let mut ranks = RollingRanks::new(period);
+        for (i, &(x, y)) in inputs.iter().enumerate() {
+            if i >= period {
+                ranks.pop();
+            }
+            ranks.push(x, y);
+
+            let window: Vec<(f64, f64)> = inputs[(i + 1).saturating_sub(period)..=i]
+                .iter()
+                .copied()
+                .filter(|p| !p.0.is_nan() && !p.1.is_nan())
+                .collect();
+            assert_eq!(ranks.len(), window.len());
+            if window.len() > 1 {
+                let xs: Vec<f64> = window.iter().map(|p| p.0).collect();
+                let ys: Vec<f64> = window.iter().map(|p| p.1).collect();
+                let (rx, ry) = (average_ranks(&xs), average_ranks(&ys));
+                assert_approx_eq!(ranks.spearman().unwrap(), pearson(&rx, &ry), 1e-12);
+                assert_approx_eq!(ranks.kendall().unwrap(), kendall_tau_b(&xs, &ys), 1e-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use num_traits::Float;
+
+use crate::utils::Deque;
+
+type Kbn<T> = compensated_summation::KahanBabuskaNeumaier<T>;
+
+/// This module provides the running sums behind linearly weighted moving averages.
+///
+/// Alongside the plain sum of the window it keeps the position-weighted sum
+/// `Σ i·x_i`, where the oldest value has weight 1 and the newest value has weight `n`.
+/// When the window slides, every remaining value loses exactly one unit of weight,
+/// so the weighted sum is updated in O(1) by subtracting the plain sum before adding
+/// the new value with weight `n`.
+///
+/// The implementation uses Kahan-Babuska-Neumaier summation algorithm for numerical stability
+/// when computing these statistics over potentially large datasets with floating-point values.
+#[derive(Debug, Clone)]
+pub struct RollingWma<T> {
+    /// Buffer to maintain the window
+    buf: Deque<T>,
+    /// Sum of inputs
+    sum: Kbn<T>,
+    /// Position-weighted sum of inputs
+    weighted_sum: Kbn<T>,
+}
+
+impl<T: Float + Default> RollingWma<T> {
+    /// Creates a new `RollingWma` instance with the specified period.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The period of the statistics
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics object
+    pub fn new(period: usize) -> Self {
+        Self {
+            buf: Deque::new(period),
+            sum: Kbn::default(),
+            weighted_sum: Kbn::default(),
+        }
+    }
+
+    /// Resets the running sums
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    #[inline]
+    pub fn reset(&mut self) -> &mut Self {
+        self.buf.reset();
+        self.sum = Kbn::default();
+        self.weighted_sum = Kbn::default();
+        self
+    }
+
+    /// Updates the running sums with a new value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to update the running sums with
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    #[inline]
+    pub fn next(&mut self, value: T) -> &mut Self {
+        if let Some(popped) = self.buf.push_back(value) {
+            self.weighted_sum -= self.sum.total();
+            self.sum -= popped;
+        }
+
+        let weight = T::from(self.buf.len()).unwrap_or_else(T::nan);
+        self.sum += value;
+        self.weighted_sum += weight * value;
+
+        self
+    }
+
+    /// Recomputes the running sums, could be called to avoid
+    /// prolonged compounding of floating rounding errors
+    #[inline]
+    pub fn recompute(&mut self) {
+        self.sum = Kbn::default();
+        self.weighted_sum = Kbn::default();
+
+        for (i, &v) in self.buf.iter().enumerate() {
+            let weight = T::from(i + 1).unwrap_or_else(T::nan);
+            self.sum += v;
+            self.weighted_sum += weight * v;
+        }
+    }
+
+    /// Returns the window period
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The window period
+    #[inline]
+    pub const fn period(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Returns true of the calculation was ready
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the calculation was ready
+    #[inline]
+    pub const fn is_ready(&self) -> bool {
+        self.buf.is_full()
+    }
+
+    /// Returns the simple mean of the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The mean if the window is ready, None otherwise
+    #[inline]
+    pub fn mean(&self) -> Option<T> {
+        if !self.is_ready() {
+            return None;
+        }
+        let n = T::from(self.period())?;
+        Some(self.sum.total() / n)
+    }
+
+    /// Returns the linearly weighted mean of the window, newest value weighted highest
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The linearly weighted mean if the window is ready, None otherwise
+    #[inline]
+    pub fn wma(&self) -> Option<T> {
+        if !self.is_ready() {
+            return None;
+        }
+        let n = T::from(self.period())?;
+        let norm = n * (n + T::one()) / T::from(2.0)?;
+        Some(self.weighted_sum.total() / norm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+
+    #[test]
+    fn wma_works() {
+        let mut stats = RollingWma::new(4);
+        let inputs = [10.0, 10.5, 11.2, 10.9, 11.5, 11.9, 12.3, 12.1, 11.8, 12.5];
+        let mut results = vec![];
+
+        inputs.iter().for_each(|i| {
+            if let Some(v) = stats.next(*i).wma() {
+                results.push(v)
+            }
+        });
+
+        let expected: [f64; 7] = [10.82, 11.16, 11.51, 11.88, 12.06, 12.0, 12.19];
+        for (i, e) in expected.iter().enumerate() {
+            assert_approx_eq!(e, results[i], 0.0001);
+        }
+    }
+
+    #[test]
+    fn recompute_works() {
+        let mut stats = RollingWma::new(3);
+        for i in 0..1_000 {
+            stats.next(1_000_000.0 + i as f64 * 0.1);
+        }
+        let (wma, mean) = (stats.wma(), stats.mean());
+        stats.recompute();
+        assert_approx_eq!(
+            wma.unwrap_or_default(),
+            stats.wma().unwrap_or_default(),
+            1e-6
+        );
+        assert_approx_eq!(
+            mean.unwrap_or_default(),
+            stats.mean().unwrap_or_default(),
+            1e-6
+        );
+    }
+}
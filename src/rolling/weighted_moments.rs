@@ -0,0 +1,333 @@
+use num_traits::Float;
+
+use crate::utils::Deque;
+
+type Kbn<T> = compensated_summation::KahanBabuskaNeumaier<T>;
+
+/// This module provides functionality for calculating weighted rolling moments over a time series.
+///
+/// Every observation carries its own non-negative weight, such as the traded volume of a
+/// price, and contributes to the statistics in proportion to it. Weights are treated as
+/// frequency weights: an observation with weight `w` counts as `w` identical observations,
+/// which is the usual interpretation for volume-weighted statistics like VWAP.
+///
+/// The implementation uses Kahan-Babuska-Neumaier summation algorithm for numerical stability
+/// when computing these statistics over potentially large datasets with floating-point values.
+#[derive(Debug, Clone)]
+pub struct WeightedMoments<T> {
+    /// Buffer of (value, weight) pairs to maintain the window
+    buf: Deque<(T, T)>,
+    /// Most recent value pushed into the rolling window.
+    value: Option<T>,
+    /// Most recent (value, weight) pair popped out of the rolling window (if full).
+    popped: Option<(T, T)>,
+    /// Delta Degrees of Freedom
+    ddof: bool,
+    /// Sum of weights
+    sum_w: Kbn<T>,
+    /// Sum of weighted inputs
+    sum_wx: Kbn<T>,
+    /// Sum of weighted squares
+    sum_wx2: Kbn<T>,
+}
+
+impl<T: Float + Default> WeightedMoments<T> {
+    /// Creates a new `WeightedMoments` instance with the specified period.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The period of the statistics
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics object
+    pub fn new(period: usize) -> Self {
+        Self {
+            buf: Deque::new(period),
+            value: None,
+            popped: None,
+            ddof: false,
+            sum_w: Kbn::default(),
+            sum_wx: Kbn::default(),
+            sum_wx2: Kbn::default(),
+        }
+    }
+
+    /// Returns the Delta Degrees of Freedom
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - The Delta Degrees of Freedom
+    #[inline]
+    pub const fn ddof(&self) -> bool {
+        self.ddof
+    }
+
+    /// Sets the Delta Degrees of Freedom
+    ///
+    /// # Arguments
+    ///
+    /// * `ddof` - The Delta Degrees of Freedom
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    #[inline]
+    pub const fn set_ddof(&mut self, ddof: bool) -> &mut Self {
+        self.ddof = ddof;
+        self
+    }
+
+    /// Resets the sums
+    #[inline]
+    fn reset_sums(&mut self) {
+        self.sum_w = Kbn::default();
+        self.sum_wx = Kbn::default();
+        self.sum_wx2 = Kbn::default();
+    }
+
+    /// Resets the weighted moments
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The weighted moments object
+    #[inline]
+    pub fn reset(&mut self) -> &mut Self {
+        self.buf.reset();
+        self.value = None;
+        self.popped = None;
+        self.reset_sums();
+        self
+    }
+
+    /// Updates the weighted moments with a new value and its weight
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to update the weighted moments with
+    /// * `weight` - The non-negative weight of the value
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The weighted moments object
+    #[inline]
+    pub fn next(&mut self, value: T, weight: T) -> &mut Self {
+        debug_assert!(weight >= T::zero(), "weight must be non-negative");
+
+        self.value = Some(value);
+        self.popped = self.buf.push_back((value, weight));
+        if let Some((popped, popped_weight)) = self.popped {
+            self.sum_w -= popped_weight;
+            self.sum_wx -= popped_weight * popped;
+            self.sum_wx2 -= popped_weight * popped * popped;
+        }
+
+        self.sum_w += weight;
+        self.sum_wx += weight * value;
+        self.sum_wx2 += weight * value * value;
+
+        self
+    }
+
+    /// Recomputes the weighted sums, could be called to avoid
+    /// prolonged compounding of floating rounding errors
+    #[inline]
+    pub fn recompute(&mut self) {
+        self.reset_sums();
+
+        for &(v, w) in self.buf.iter() {
+            self.sum_w += w;
+            self.sum_wx += w * v;
+            self.sum_wx2 += w * v * v;
+        }
+    }
+
+    /// Returns the (value, weight) pair that was removed from the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(T, T)>` - The (value, weight) pair that was removed from the window
+    pub const fn popped(&self) -> Option<(T, T)> {
+        self.popped
+    }
+
+    /// Returns the window period
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The window period
+    #[inline]
+    pub const fn period(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Returns true of the calculation was ready
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the calculation was ready
+    #[inline]
+    pub const fn is_ready(&self) -> bool {
+        self.buf.is_full()
+    }
+
+    /// Returns the sum of weights in the rolling window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The sum of weights if the window is ready, None otherwise
+    #[inline]
+    pub fn total_weight(&self) -> Option<T> {
+        self.is_ready().then_some(self.sum_w.total())
+    }
+
+    /// Returns the weighted sum of values in the rolling window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The sum of `weight * value` if the window is ready, None otherwise
+    #[inline]
+    pub fn sum(&self) -> Option<T> {
+        self.is_ready().then_some(self.sum_wx.total())
+    }
+
+    /// Returns the weighted mean of values in the rolling window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The weighted mean if the window is ready and the total weight is positive, None otherwise
+    #[inline]
+    pub fn mean(&self) -> Option<T> {
+        let sum_w = self.total_weight()?;
+        if sum_w <= T::zero() {
+            return None;
+        }
+        Some(self.sum_wx.total() / sum_w)
+    }
+
+    /// Returns the weighted variance of values in the rolling window
+    ///
+    /// When `ddof` is true, the sum of weights minus one is used as the denominator,
+    /// matching the sample variance of frequency weighted data.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The weighted variance, or `None` if the window is not full
+    #[inline]
+    pub fn variance(&self) -> Option<T> {
+        let mean = self.mean()?;
+        let sum_w = self.sum_w.total();
+        let m2 = Float::max(self.sum_wx2.total() / sum_w - mean * mean, T::zero());
+
+        if !self.ddof {
+            return Some(m2);
+        }
+
+        let denom = sum_w - T::one();
+        if denom > T::zero() {
+            Some(m2 * sum_w / denom)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the weighted standard deviation of values in the rolling window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The weighted standard deviation, or `None` if the window is not full
+    #[inline]
+    pub fn stddev(&self) -> Option<T> {
+        self.variance().map(Float::sqrt)
+    }
+
+    /// Returns the Zscore of the most recent value against the weighted mean and standard deviation
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Zscore if the window is ready and standard deviation is positive, None otherwise
+    #[inline]
+    pub fn zscore(&self) -> Option<T> {
+        let value = self.value?;
+        let mean = self.mean()?;
+        let stddev = self.stddev()?;
+
+        if stddev > T::zero() {
+            Some((value - mean) / stddev)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+
+    #[test]
+    fn mean_and_variance_work() {
+        let mut stats = WeightedMoments::new(3);
+        let inputs = [
+            (10.0, 100.0),
+            (11.0, 200.0),
+            (12.0, 100.0),
+            (11.0, 300.0),
+            (10.0, 100.0),
+        ];
+        let mut means = vec![];
+        let mut variances = vec![];
+
+        inputs.iter().for_each(|&(v, w)| {
+            if let Some(m) = stats.next(v, w).mean() {
+                means.push(m);
+            }
+            if let Some(var) = stats.variance() {
+                variances.push(var);
+            }
+        });
+
+        let expected: [f64; 3] = [11.0, 11.1667, 11.0];
+        for (i, e) in expected.iter().enumerate() {
+            assert_approx_eq!(e, means[i], 0.0001);
+        }
+        let expected: [f64; 3] = [0.5, 0.1389, 0.4];
+        for (i, e) in expected.iter().enumerate() {
+            assert_approx_eq!(e, variances[i], 0.0001);
+        }
+
+        assert_eq!(stats.popped(), Some((11.0, 200.0)));
+        assert_eq!(stats.total_weight(), Some(500.0));
+    }
+
+    #[test]
+    fn equal_weights_match_unweighted() {
+        let mut stats = WeightedMoments::new(3);
+        let mut results = vec![];
+        let inputs = [25.4, 26.2, 26.0, 26.1, 25.8, 25.9, 26.3, 26.2, 26.5];
+        stats.set_ddof(true);
+        inputs.iter().for_each(|&v| {
+            if let Some(var) = stats.next(v, 1.0).variance() {
+                results.push(var)
+            }
+        });
+
+        let expected: [f64; 7] = [0.1733, 0.01, 0.0233, 0.0233, 0.07, 0.0433, 0.0233];
+        for (i, e) in expected.iter().enumerate() {
+            assert_approx_eq!(e, results[i], 0.0001);
+        }
+    }
+
+    #[test]
+    fn zero_weight_is_none() {
+        let mut stats = WeightedMoments::new(2);
+        stats.next(1.0, 0.0).next(2.0, 0.0);
+        assert_eq!(stats.mean(), None);
+        assert_eq!(stats.variance(), None);
+
+        stats.next(3.0, 1.0).recompute();
+        assert_eq!(stats.mean(), Some(3.0));
+        assert_eq!(stats.variance(), Some(0.0));
+    }
+}
@@ -0,0 +1,253 @@
+use alloc::vec::Vec;
+use num_traits::Float;
+
+use crate::KllSketch;
+use crate::kll_sketch::weighted_quantile;
+
+/// # Rolling Quantile Sketch
+///
+/// Approximates quantiles over a rolling window too large to hold every observation. The
+/// window is split into `blocks` consecutive blocks of `period / blocks` observations (rounded
+/// up), each summarized by a [`KllSketch`]. When the newest block fills up, the oldest block
+/// is discarded and recycled, so the window slides one block at a time.
+///
+/// Once `period` observations have been seen, the window covers between
+/// `period - period / blocks + 1` and `period` of the most recent observations. More blocks
+/// give a finer window edge at the cost of memory, which is O(blocks · k · log(period / (blocks · k))).
+///
+/// # Error bounds
+///
+/// Each block keeps the rank error of its sketch, so the quantile over the covered
+/// observations has a rank error of about `1.7 / k` of the covered count with high
+/// probability, e.g. ±0.85% for the default `k = 200`. Windows of the same layout built on
+/// separate shards can be merged block by block with [`merge`](Self::merge).
+///
+/// # Examples
+///
+/// ```
+/// use ta_statistics::RollingQuantileSketch;
+///
+/// let mut sketch = RollingQuantileSketch::new(100_000, 10, 200);
+/// for i in 0..300_000 {
+///     sketch.next(i as f64);
+/// }
+///
+/// // The window covers the most recent 90_001 to 100_000 observations
+/// let median = sketch.quantile(0.5).unwrap();
+/// assert!((median - 250_000.0).abs() < 0.02 * 100_000.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingQuantileSketch<T> {
+    /// Number of observations in the window
+    period: usize,
+    /// Number of observations per block
+    block_len: usize,
+    /// Ring of block sketches
+    blocks: Vec<KllSketch<T>>,
+    /// Index of the block receiving new observations
+    head: usize,
+    /// Number of observations seen, saturating at the period
+    seen: usize,
+}
+
+impl<T: Float> RollingQuantileSketch<T> {
+    /// Creates a new `RollingQuantileSketch` instance
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The size of the rolling window
+    /// * `blocks` - The number of blocks the window is split into
+    /// * `k` - The accuracy parameter of the block sketches
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The sketch
+    ///
+    /// # Panics
+    ///
+    /// Panics if `blocks` is zero or greater than `period`, or `k` is less than 8
+    pub fn new(period: usize, blocks: usize, k: usize) -> Self {
+        assert!(
+            blocks > 0 && blocks <= period,
+            "blocks must be between 1 and period"
+        );
+        Self {
+            period,
+            block_len: period.div_ceil(blocks),
+            blocks: (0..blocks).map(|_| KllSketch::new(k)).collect(),
+            head: 0,
+            seen: 0,
+        }
+    }
+
+    /// Returns the period of the window
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The period of the window
+    pub const fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Returns the number of observations currently covered by the window
+    ///
+    /// # Returns
+    ///
+    /// * `u64` - The number of covered observations
+    pub fn count(&self) -> u64 {
+        self.blocks.iter().map(KllSketch::count).sum()
+    }
+
+    /// Returns true once `period` observations have been seen
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the window is ready
+    pub const fn is_ready(&self) -> bool {
+        self.seen >= self.period
+    }
+
+    /// Resets the sketch
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The sketch
+    pub fn reset(&mut self) -> &mut Self {
+        self.blocks.iter_mut().for_each(|b| {
+            b.reset();
+        });
+        self.head = 0;
+        self.seen = 0;
+        self
+    }
+
+    /// Updates the sketch with a new value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The new value to be added to the rolling window
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The sketch
+    pub fn next(&mut self, value: T) -> &mut Self {
+        if self.blocks[self.head].count() as usize >= self.block_len {
+            self.head = (self.head + 1) % self.blocks.len();
+            self.blocks[self.head].reset();
+        }
+        self.blocks[self.head].next(value);
+        self.seen = (self.seen + 1).min(self.period);
+        self
+    }
+
+    /// Merges another rolling sketch of the same layout into this one, block by block
+    ///
+    /// Both sketches must have been fed in lockstep, e.g. one instrument per shard over the
+    /// same bars, so that their blocks cover the same intervals.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The sketch to merge
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The sketch
+    ///
+    /// # Panics
+    ///
+    /// Panics if the period or the number of blocks differ
+    pub fn merge(&mut self, other: &Self) -> &mut Self {
+        assert!(
+            self.period == other.period && self.blocks.len() == other.blocks.len(),
+            "sketch layouts must match"
+        );
+        let len = self.blocks.len();
+        for i in 0..len {
+            let theirs = &other.blocks[(other.head + len - i) % len];
+            self.blocks[(self.head + len - i) % len].merge(theirs);
+        }
+        self
+    }
+
+    /// Returns the approximate quantile of the values in the window
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - The quantile to calculate
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The quantile, or `None` if the window is not ready or `q` is outside `[0, 1]`
+    pub fn quantile(&self, q: f64) -> Option<T> {
+        if !self.is_ready() {
+            return None;
+        }
+        let items = self
+            .blocks
+            .iter()
+            .flat_map(KllSketch::weighted_items)
+            .collect();
+        let min = self.blocks.iter().filter_map(KllSketch::min).reduce(T::min);
+        let max = self.blocks.iter().filter_map(KllSketch::max).reduce(T::max);
+        weighted_quantile(items, self.count(), q, min, max)
+    }
+
+    /// Returns the approximate median of the values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The median, or `None` if the window is not ready
+    pub fn median(&self) -> Option<T> {
+        self.quantile(0.5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn rolling_sketch_tracks_window() {
+        let period = 10_000;
+        let mut sketch = RollingQuantileSketch::new(period, 4, 200);
+        for i in 0..period - 1 {
+            sketch.next(i as f64);
+        }
+        assert_eq!(sketch.quantile(0.5), None);
+
+        for i in period - 1..5 * period {
+            sketch.next(i as f64);
+            let covered = sketch.count() as usize;
+            assert!(covered > period - period / 4 && covered <= period);
+        }
+
+        // Newest value is 49_999 and the window is exactly one period after a block boundary
+        let oldest = (5 * period) as f64 - sketch.count() as f64;
+        assert_eq!(sketch.quantile(0.0), Some(oldest));
+        assert_eq!(sketch.quantile(1.0), Some((5 * period - 1) as f64));
+        let median = sketch.median().unwrap();
+        let exact = oldest + sketch.count() as f64 / 2.0;
+        assert!((median - exact).abs() <= 1.7 / 200.0 * period as f64);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn rolling_sketch_merge_works() {
+        let period = 1_000;
+        let mut left = RollingQuantileSketch::new(period, 5, 200);
+        let mut right = RollingQuantileSketch::new(period, 5, 200);
+        for i in 0..2_500 {
+            left.next(i as f64);
+            right.next(-(i as f64));
+        }
+        left.merge(&right);
+        assert_eq!(left.count(), 2 * right.count());
+        assert_eq!(left.quantile(0.0), Some(-2_499.0));
+        assert_eq!(left.quantile(1.0), Some(2_499.0));
+
+        // Both windows cover 900 values, the first quartile of the merged 1_800 is -2_050
+        assert_eq!(left.count(), 1_800);
+        assert!((left.quantile(0.25).unwrap() + 2_050.0).abs() <= 1.7 / 200.0 * 1_800.0);
+    }
+}
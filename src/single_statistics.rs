@@ -4,13 +4,15 @@ use ordered_float::PrimitiveFloat;
 use core::iter::Sum;
 
 use crate::{
-    PairedStatistics,
+    QuantileMethod, RankMethod, VarMethod,
     maximum::Maximum,
     minimum::Minimum,
-    rolling::{RollingMode, RollingMoments},
-    utils::RbTree,
+    rolling::{RollingDrawdown, RollingMode, RollingMoments, RollingPartialMoments},
+    utils::{MIN_GROWABLE_CAPACITY, RbTree, normal_inverse_cdf, normal_pdf},
 };
 
+type Kbn<T> = compensated_summation::KahanBabuskaNeumaier<T>;
+
 /// A structure that computes various statistics over a fixed-size window of values.
 /// A specialized statistics implementation for single time-series data analysis.
 ///
@@ -21,20 +23,34 @@ use crate::{
 ///
 /// The structure is particularly useful for technical analysis, risk management,
 /// and alpha generation in quantitative trading strategies.
+///
+/// [`SingleStatistics::new`] enables every component updated in O(log n) or better, leaving
+/// out the O(n) drawdown profile. Use [`SingleStatistics::builder`] to enable only the
+/// components a strategy needs; moments (sum, mean, variance, etc.)
+/// are always available, while disabled components are neither allocated nor updated
+/// and their getters return `None`.
 #[derive(Debug)]
 pub struct SingleStatistics<T> {
     /// Rolling moments
     moments: RollingMoments<T>,
     /// Minimum
-    min: Minimum<T>,
+    min: Option<Minimum<T>>,
     /// Maximum
-    max: Maximum<T>,
+    max: Option<Maximum<T>>,
     /// Maximum drawdown
-    max_drawdown: Option<T>,
+    max_drawdown: Option<RollingDrawdown<T>>,
+    /// Drawdown durations, recovery time, Ulcer Index and pain index
+    drawdown_profile: Option<DrawdownProfile<T>>,
     /// Mode
-    mode: RollingMode<T>,
+    mode: Option<RollingMode<T>>,
     /// Median/Quantile/Percentile/IQR/MAD
-    rb_tree: RbTree<T>,
+    rb_tree: Option<RbTree<T>>,
+    /// Partial moments relative to a fixed threshold
+    partial_moments: Option<RollingPartialMoments<T>>,
+    /// Method used to estimate the median, quantiles and IQR
+    quantile_method: QuantileMethod,
+    /// Method used to estimate the Value-at-Risk and Expected Shortfall
+    var_method: VarMethod,
 }
 
 impl<T> SingleStatistics<T>
@@ -51,18 +67,138 @@ where
     ///
     /// * `Self` - The statistics object
     pub fn new(period: usize) -> Self {
+        Self::builder(period)
+            .with_min_max()
+            .with_mode()
+            .with_quantiles()
+            .build()
+    }
+
+    /// Creates a builder for a `SingleStatistics` instance with the specified period.
+    ///
+    /// Only moments are enabled by default, see [`SingleStatisticsBuilder`] for the
+    /// optional components.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The period of the statistics
+    ///
+    /// # Returns
+    ///
+    /// * `SingleStatisticsBuilder<T>` - The builder
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// let mut stats = SingleStatistics::builder(3).with_quantiles().build();
+    /// let inputs = [5.0, 2.0, 8.0, 1.0, 7.0];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i);
+    /// });
+    ///
+    /// assert_eq!(stats.mean(), Some(16.0 / 3.0));
+    /// assert_eq!(stats.median(), Some(7.0));
+    /// assert_eq!(stats.max(), None);
+    /// assert_eq!(stats.mode(), None);
+    /// ```
+    pub fn builder(period: usize) -> SingleStatisticsBuilder<T> {
+        SingleStatisticsBuilder::new(period)
+    }
+
+    /// Creates a new `SingleStatistics` instance over a trailing time span.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - The length of the window in timestamp units
+    /// * `max_capacity` - The optional upper bound on the number of values held
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics object
+    pub(crate) fn with_time_window(duration: u64, max_capacity: Option<usize>) -> Self {
+        Self {
+            moments: RollingMoments::with_time_window(duration, max_capacity),
+            min: Some(Minimum::growable()),
+            max: Some(Maximum::growable()),
+            max_drawdown: Some(RollingDrawdown::new(MIN_GROWABLE_CAPACITY)),
+            drawdown_profile: None,
+            mode: Some(RollingMode::new()),
+            rb_tree: Some(RbTree::with_moments(MIN_GROWABLE_CAPACITY)),
+            partial_moments: None,
+            quantile_method: QuantileMethod::default(),
+            var_method: VarMethod::default(),
+        }
+    }
+
+    /// Enables the drawdown profile of a time window
+    ///
+    /// The profile is rebuilt from the values currently in the window.
+    pub(crate) fn enable_drawdown_profile(&mut self) {
+        let mut drawdown_profile = DrawdownProfile::new();
+        drawdown_profile.rebuild(self.moments.iter());
+        self.drawdown_profile = Some(drawdown_profile);
+    }
+
+    /// Creates a new `SingleStatistics` instance over an expanding window.
+    ///
+    /// The expanding window never evicts values, so every statistic covers all values
+    /// since inception. Moments are updated in place with O(1) memory, min and max are
+    /// running extremes, and the median and quantiles are served by an order-statistic
+    /// tree that grows with the number of distinct values.
+    ///
+    /// Statistics are reported from the first value onwards. The linear regression methods
+    /// fit the whole history from its running position-weighted sums, and the drawdown
+    /// durations, recovery time, Ulcer Index and pain index are updated in O(1) from the
+    /// running peak. As no values are retained, `diff`, `pct_change` and `log_return`
+    /// return `None`.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics object
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = SingleStatistics::expanding();
+    /// let mut results = vec![];
+    /// let inputs = [100.0, 110.0, 105.0, 115.0, 100.0, 95.0, 105.0, 110.0, 100.0];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).max_drawdown().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 9] = [0.0, 0.0, 0.0455, 0.0455, 0.1304, 0.1739, 0.1739, 0.1739, 0.1739];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    ///
+    /// assert_eq!(stats.period(), 9);
+    /// assert_eq!(stats.max(), Some(115.0));
+    /// assert_eq!(stats.min(), Some(95.0));
+    /// assert_eq!(stats.drawdown_duration(), Some(5));
+    /// assert_eq!(stats.recovery_time(), None);
+    /// ```
+    pub fn expanding() -> Self {
         Self {
-            moments: RollingMoments::new(period),
-            min: Minimum::new(period),
-            max: Maximum::new(period),
-            max_drawdown: None,
-            mode: RollingMode::new(),
-            rb_tree: RbTree::new(period),
+            moments: RollingMoments::expanding(),
+            min: Some(Minimum::cumulative()),
+            max: Some(Maximum::cumulative()),
+            max_drawdown: Some(RollingDrawdown::new(0)),
+            drawdown_profile: Some(DrawdownProfile::new()),
+            mode: Some(RollingMode::new()),
+            rb_tree: Some(RbTree::with_moments(MIN_GROWABLE_CAPACITY)),
+            partial_moments: None,
+            quantile_method: QuantileMethod::default(),
+            var_method: VarMethod::default(),
         }
     }
 
     /// Returns the period of the statistics
     ///
+    /// For expanding windows this is the number of values seen since inception.
+    ///
     /// # Returns
     ///
     /// * `usize` - The period of the statistics
@@ -70,6 +206,24 @@ where
         self.moments.period()
     }
 
+    /// Returns the length of the time window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u64>` - The length of the window in timestamp units, or `None` for count windows
+    pub(crate) const fn duration(&self) -> Option<u64> {
+        self.moments.duration()
+    }
+
+    /// Returns the number of values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of values in the window
+    pub(crate) const fn count(&self) -> usize {
+        self.moments.count()
+    }
+
     /// Resets the statistics
     ///
     /// # Returns
@@ -77,11 +231,27 @@ where
     /// * `&mut Self` - The statistics object
     pub fn reset(&mut self) -> &mut Self {
         self.moments.reset();
-        self.min.reset();
-        self.max.reset();
-        self.max_drawdown = None;
-        self.mode.reset();
-        self.rb_tree.reset();
+        if let Some(min) = &mut self.min {
+            min.reset();
+        }
+        if let Some(max) = &mut self.max {
+            max.reset();
+        }
+        if let Some(max_drawdown) = &mut self.max_drawdown {
+            max_drawdown.reset();
+        }
+        if let Some(drawdown_profile) = &mut self.drawdown_profile {
+            drawdown_profile.reset();
+        }
+        if let Some(mode) = &mut self.mode {
+            mode.reset();
+        }
+        if let Some(rb_tree) = &mut self.rb_tree {
+            rb_tree.reset();
+        }
+        if let Some(partial_moments) = &mut self.partial_moments {
+            partial_moments.reset();
+        }
         self
     }
 
@@ -93,6 +263,17 @@ where
     /// * `&mut Self` - The rolling moments object
     pub fn recompute(&mut self) -> &mut Self {
         self.moments.recompute();
+        if let Some(partial_moments) = &mut self.partial_moments
+            && !self.moments.is_expanding()
+        {
+            partial_moments.reset();
+            self.moments.iter().for_each(|&v| partial_moments.push(v));
+        }
+        if let Some(drawdown_profile) = &mut self.drawdown_profile
+            && !self.moments.is_expanding()
+        {
+            drawdown_profile.rebuild(self.moments.iter());
+        }
         self
     }
 
@@ -126,6 +307,56 @@ where
         self
     }
 
+    /// Returns the method used to estimate the median, quantiles and IQR
+    ///
+    /// # Returns
+    ///
+    /// * `QuantileMethod` - The quantile method
+    pub const fn quantile_method(&self) -> QuantileMethod {
+        self.quantile_method
+    }
+
+    /// Sets the method used to estimate the median, quantiles and IQR
+    ///
+    /// Defaults to [`QuantileMethod::Linear`], matching numpy and pandas.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The quantile method
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub const fn set_quantile_method(&mut self, method: QuantileMethod) -> &mut Self {
+        self.quantile_method = method;
+        self
+    }
+
+    /// Returns the method used to estimate the Value-at-Risk and Expected Shortfall
+    ///
+    /// # Returns
+    ///
+    /// * `VarMethod` - The Value-at-Risk method
+    pub const fn var_method(&self) -> VarMethod {
+        self.var_method
+    }
+
+    /// Sets the method used to estimate the Value-at-Risk and Expected Shortfall
+    ///
+    /// Defaults to [`VarMethod::Historical`].
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The Value-at-Risk method
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub const fn set_var_method(&mut self, method: VarMethod) -> &mut Self {
+        self.var_method = method;
+        self
+    }
+
     /// Updates the statistical calculations with a new value in the time series
     ///
     /// Incorporates a new data point into the rolling window, maintaining the specified
@@ -146,18 +377,138 @@ where
     /// * `&mut Self` - The statistics object
     pub fn next(&mut self, value: T) -> &mut Self {
         self.moments.next(value);
+        if self.moments.is_expanding() {
+            if let Some(min) = &mut self.min {
+                min.push_cumulative(value);
+            }
+            if let Some(max) = &mut self.max {
+                max.push_cumulative(value);
+            }
+            if let Some(max_drawdown) = &mut self.max_drawdown {
+                max_drawdown.push_cumulative(value);
+            }
+            if let Some(drawdown_profile) = &mut self.drawdown_profile {
+                drawdown_profile.push(value);
+            }
+            if let Some(mode) = &mut self.mode {
+                mode.push(value);
+            }
+            if let Some(rb_tree) = &mut self.rb_tree {
+                rb_tree.insert_growing(value);
+            }
+            if let Some(partial_moments) = &mut self.partial_moments {
+                partial_moments.push(value);
+            }
+            return self;
+        }
+
+        if let Some(popped) = self.moments.popped() {
+            if let Some(max_drawdown) = &mut self.max_drawdown {
+                max_drawdown.pop();
+            }
+            if let Some(mode) = &mut self.mode {
+                mode.pop(popped);
+            }
+            if let Some(rb_tree) = &mut self.rb_tree {
+                rb_tree.remove(popped);
+            }
+            if let Some(partial_moments) = &mut self.partial_moments {
+                partial_moments.pop(popped);
+            }
+        }
+        if let Some(min) = &mut self.min {
+            min.push(value);
+        }
+        if let Some(max) = &mut self.max {
+            max.push(value);
+        }
+        if let Some(max_drawdown) = &mut self.max_drawdown {
+            max_drawdown.push(value);
+        }
+        if let Some(mode) = &mut self.mode {
+            mode.push(value);
+        }
+        if let Some(rb_tree) = &mut self.rb_tree {
+            rb_tree.insert(value);
+        }
+        if let Some(partial_moments) = &mut self.partial_moments {
+            partial_moments.push(value);
+        }
+        if let Some(drawdown_profile) = &mut self.drawdown_profile {
+            drawdown_profile.rebuild(self.moments.iter());
+        }
+
+        self
+    }
+
+    /// Updates the statistical calculations with a new timestamped value
+    ///
+    /// Evicts every value that aged out of the time window before adding the new one.
+    ///
+    /// # Arguments
+    ///
+    /// * `ts` - The timestamp of the value, expected to be non-decreasing across calls. A
+    ///   timestamp older than the latest one is treated as the latest timestamp
+    /// * `value` - The new value to be added to the time series
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub(crate) fn next_at(&mut self, ts: u64, value: T) -> &mut Self {
+        while let Some(expired) = self.moments.expire(ts) {
+            self.remove_expired(expired);
+        }
+
+        self.moments.next_at(ts, value);
         if let Some(popped) = self.moments.popped() {
-            self.mode.pop(popped);
-            self.rb_tree.remove(popped);
+            self.remove_expired(popped);
+        }
+
+        let head = self.moments.head().unwrap_or_default();
+        let pos = head + self.moments.count() - 1;
+        if let Some(min) = &mut self.min {
+            min.expire(head);
+            min.push_at(value, pos);
+        }
+        if let Some(max) = &mut self.max {
+            max.expire(head);
+            max.push_at(value, pos);
+        }
+        if let Some(max_drawdown) = &mut self.max_drawdown {
+            max_drawdown.push(value);
+        }
+        if let Some(mode) = &mut self.mode {
+            mode.push(value);
+        }
+        if let Some(rb_tree) = &mut self.rb_tree {
+            rb_tree.insert_growing(value);
+        }
+        if let Some(partial_moments) = &mut self.partial_moments {
+            partial_moments.push(value);
+        }
+        if let Some(drawdown_profile) = &mut self.drawdown_profile {
+            drawdown_profile.rebuild(self.moments.iter());
         }
-        self.min.push(value);
-        self.max.push(value);
-        self.mode.push(value);
-        self.rb_tree.insert(value);
 
         self
     }
 
+    /// Removes a value that left a time window from the drawdown, mode and quantile components
+    fn remove_expired(&mut self, value: T) {
+        if let Some(max_drawdown) = &mut self.max_drawdown {
+            max_drawdown.pop();
+        }
+        if let Some(mode) = &mut self.mode {
+            mode.pop(value);
+        }
+        if let Some(rb_tree) = &mut self.rb_tree {
+            rb_tree.remove_shrinking(value);
+        }
+        if let Some(partial_moments) = &mut self.partial_moments {
+            partial_moments.pop(value);
+        }
+    }
+
     /// Returns the sum of all values in the rolling window
     ///
     /// This fundamental calculation serves as the basis for numerous higher-order statistics
@@ -331,11 +682,11 @@ where
     /// let expected: [f64; 8] = [1.0, 2.0, 1.0, 3.0, 3.0, 3.0, 2.0, 2.0];
     /// assert_eq!(&results, &expected);
     /// ```
-    pub fn mode(&mut self) -> Option<T> {
+    pub fn mode(&self) -> Option<T> {
         if !self.moments.is_ready() {
             return None;
         }
-        self.mode.mode()
+        self.mode.as_ref()?.mode()
     }
 
     /// Returns the median (middle value) of the rolling window using two balanced heaps to
@@ -349,6 +700,9 @@ where
     /// - Provides core input for non-parametric statistical models
     /// - Serves as a foundation for technical indicators like median-based envelopes
     ///
+    /// Uses the configured [`QuantileMethod`], so with the default linear method the
+    /// median of an even window is the average of the two middle values.
+    ///
     /// # Returns
     ///
     /// * `Option<T>` - The median of values in the window, or `None` if the window is not full
@@ -369,11 +723,13 @@ where
     /// let expected: [f64; 5] = [5.0, 2.0, 7.0, 3.0, 7.0];
     /// assert_eq!(&results, &expected);
     /// ```
-    pub fn median(&mut self) -> Option<T> {
+    pub fn median(&self) -> Option<T> {
         if !self.moments.is_ready() {
             return None;
         }
-        self.rb_tree.median()
+        self.rb_tree
+            .as_ref()?
+            .quantile_with(0.5, self.quantile_method)
     }
 
     /// Returns the minimum value in the rolling window
@@ -410,11 +766,11 @@ where
     /// let expected: [f64; 7] = [25.4, 26.0, 25.8, 25.8, 25.8, 25.9, 26.2];
     /// assert_eq!(&results, &expected);
     /// ```
-    pub fn min(&mut self) -> Option<T> {
+    pub fn min(&self) -> Option<T> {
         if !self.moments.is_ready() {
             return None;
         }
-        self.min.get()
+        self.min.as_ref()?.get()
     }
 
     /// Returns the maximum value in the rolling window
@@ -451,11 +807,141 @@ where
     /// let expected: [f64; 7] = [26.2, 26.2, 26.1, 26.1, 26.3, 26.3, 26.5];
     /// assert_eq!(&results, &expected);
     /// ```
-    pub fn max(&mut self) -> Option<T> {
+    pub fn max(&self) -> Option<T> {
+        if !self.moments.is_ready() {
+            return None;
+        }
+        self.max.as_ref()?.get()
+    }
+
+    /// Returns the number of bars since the maximum value of the window
+    ///
+    /// - Times breakouts by how recently the window made a new high
+    /// - Filters entries with "N bars since new high" conditions
+    /// - Drives the Aroon Up indicator, `100 · (period - 1 - bars_since_max) / (period - 1)`
+    ///
+    /// Read from the position stored alongside the maximum in its monotonic queue in O(1).
+    /// Ties resolve to the most recent occurrence of the maximum.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The bars since the maximum, 0 for the latest value, or `None` if the window is not full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// let mut stats = SingleStatistics::new(3);
+    /// let inputs = [25.4, 26.2, 26.0, 26.1, 25.8, 25.9, 26.3, 26.2, 26.5];
+    /// let mut results = vec![];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).bars_since_max().map(|v| results.push(v));
+    /// });
+    ///
+    /// assert_eq!(results, [1, 2, 1, 2, 0, 1, 0]);
+    /// ```
+    pub fn bars_since_max(&self) -> Option<usize> {
+        if !self.moments.is_ready() {
+            return None;
+        }
+        self.max.as_ref()?.bars_since()
+    }
+
+    /// Returns the number of bars since the minimum value of the window
+    ///
+    /// - Times breakdowns by how recently the window made a new low
+    /// - Filters entries with "N bars since new low" conditions
+    /// - Drives the Aroon Down indicator, `100 · (period - 1 - bars_since_min) / (period - 1)`
+    ///
+    /// Read from the position stored alongside the minimum in its monotonic queue in O(1).
+    /// Ties resolve to the most recent occurrence of the minimum.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The bars since the minimum, 0 for the latest value, or `None` if the window is not full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// let mut stats = SingleStatistics::new(3);
+    /// let inputs = [25.4, 26.2, 26.0, 26.1, 25.8, 25.9, 26.3, 26.2, 26.5];
+    /// let mut results = vec![];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).bars_since_min().map(|v| results.push(v));
+    /// });
+    ///
+    /// assert_eq!(results, [2, 1, 0, 1, 2, 2, 1]);
+    /// ```
+    pub fn bars_since_min(&self) -> Option<usize> {
         if !self.moments.is_ready() {
             return None;
         }
-        self.max.get()
+        self.min.as_ref()?.bars_since()
+    }
+
+    /// Returns the index of the maximum value within the window
+    ///
+    /// - Locates the swing high of the window for pattern detection
+    /// - Anchors retracement levels to the position of the high
+    /// - Aligns with `argmax` over the window in numpy-style analysis
+    ///
+    /// Read from the position stored alongside the maximum in its monotonic queue in O(1).
+    /// Ties resolve to the most recent occurrence of the maximum.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The index of the maximum, 0 for the oldest value, or `None` if the window is not full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// let mut stats = SingleStatistics::new(3);
+    /// let inputs = [25.4, 26.2, 26.0, 26.1, 25.8, 25.9, 26.3, 26.2, 26.5];
+    /// let mut results = vec![];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).argmax().map(|v| results.push(v));
+    /// });
+    ///
+    /// assert_eq!(results, [1, 0, 1, 0, 2, 1, 2]);
+    /// ```
+    pub fn argmax(&self) -> Option<usize> {
+        Some(self.period() - 1 - self.bars_since_max()?)
+    }
+
+    /// Returns the index of the minimum value within the window
+    ///
+    /// - Locates the swing low of the window for pattern detection
+    /// - Anchors retracement levels to the position of the low
+    /// - Aligns with `argmin` over the window in numpy-style analysis
+    ///
+    /// Read from the position stored alongside the minimum in its monotonic queue in O(1).
+    /// Ties resolve to the most recent occurrence of the minimum.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The index of the minimum, 0 for the oldest value, or `None` if the window is not full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// let mut stats = SingleStatistics::new(3);
+    /// let inputs = [25.4, 26.2, 26.0, 26.1, 25.8, 25.9, 26.3, 26.2, 26.5];
+    /// let mut results = vec![];
+    ///
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).argmin().map(|v| results.push(v));
+    /// });
+    ///
+    /// assert_eq!(results, [0, 1, 2, 1, 0, 0, 1]);
+    /// ```
+    pub fn argmin(&self) -> Option<usize> {
+        Some(self.period() - 1 - self.bars_since_min()?)
     }
 
     /// Returns the mean absolute deviation of values in the rolling window
@@ -468,6 +954,9 @@ where
     /// - Complements standard risk models with an alternative dispersion metric
     /// - Establishes more stable thresholds for adaptive signal generation
     ///
+    /// Computed in O(log n) from the count and sum of the values below the mean, kept
+    /// as subtree sums in the order-statistic tree.
+    ///
     /// # Returns
     ///
     /// * `Option<T>` - The mean absolute deviation of values, or `None` if the window is not full
@@ -495,7 +984,7 @@ where
         T: Sum,
     {
         let mean = self.mean()?;
-        self.rb_tree.mean_absolute_deviation(mean)
+        self.rb_tree.as_ref()?.mean_absolute_deviation(mean)
     }
 
     /// Returns the median absolute deviation of values in the rolling window
@@ -508,6 +997,9 @@ where
     /// - Enables stable threshold calibration for adaptive decision systems
     /// - Forms basis for robust statistical estimators in non-normal distributions
     ///
+    /// Both medians use the configured [`QuantileMethod`]. The deviations are selected
+    /// from the order-statistic tree in O(log² n) without allocating.
+    ///
     /// # Returns
     ///
     /// * `Option<T>` - The median absolute deviation of values, or `None` if the window is not full
@@ -530,11 +1022,13 @@ where
     /// }
     ///
     /// ```
-    pub fn median_absolute_deviation(&mut self) -> Option<T> {
+    pub fn median_absolute_deviation(&self) -> Option<T> {
         if !self.moments.is_ready() {
             return None;
         }
-        self.rb_tree.median_absolute_deviation()
+        self.rb_tree
+            .as_ref()?
+            .median_absolute_deviation(self.quantile_method)
     }
 
     /// Returns the variance of values in the rolling window
@@ -767,12 +1261,189 @@ where
         self.moments.kurt()
     }
 
-    /// Returns the slope of the linear regression line
+    /// Returns the lower partial moment of the given order relative to a threshold
     ///
-    /// The regression slope represents the rate of change in the best-fit linear model,
-    /// quantifying the directional movement and its magnitude within the data:
+    /// The lower partial moment `Σ max(t - x, 0)^k / n` only accounts for the values below
+    /// the threshold, which makes it the building block of downside risk measures:
     ///
-    /// - Provides precise measurement of trend strength and conviction
+    /// - Order 0 is the shortfall probability, the fraction of values below the threshold
+    /// - Order 1 is the expected shortfall below the threshold, the numerator of the Omega ratio
+    /// - Order 2 is the target semivariance behind the Sortino ratio
+    /// - Orders 3 and 4 capture the skew and tail weight of the losses
+    ///
+    /// Partial moments are enabled with
+    /// [`with_partial_moments`](SingleStatisticsBuilder::with_partial_moments). Its threshold
+    /// is served in O(1) from running partial sums. Any other threshold, such as the current
+    /// mean, is expanded from the subtree central moments of the order-statistic tree in
+    /// O(log n).
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - The order of the moment, between 0 and 4
+    /// * `threshold` - The threshold the moment is measured from
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The lower partial moment, or `None` if the window is not full, the
+    ///   order is out of range, or partial moments are not enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = SingleStatistics::builder(5).with_partial_moments(0.0).build();
+    /// let mut results = vec![];
+    /// let inputs = [0.01, -0.02, 0.015, 0.03, -0.01, 0.005, -0.025, 0.02, 0.01, -0.005];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).lower_partial_moment(2, 0.0).map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 6] = [1.0e-4, 1.0e-4, 1.45e-4, 1.45e-4, 1.45e-4, 1.3e-4];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 1e-10);
+    /// }
+    /// assert_eq!(stats.lower_partial_moment(0, 0.0), Some(0.4));
+    /// ```
+    pub fn lower_partial_moment(&self, order: usize, threshold: T) -> Option<T> {
+        if !self.moments.is_ready() {
+            return None;
+        }
+        match &self.partial_moments {
+            Some(pm) if pm.threshold() == threshold => pm.lower(order, self.moments.count()),
+            _ => self
+                .rb_tree
+                .as_ref()?
+                .lower_partial_moment(order, threshold),
+        }
+    }
+
+    /// Returns the upper partial moment of the given order relative to a threshold
+    ///
+    /// The upper partial moment `Σ max(x - t, 0)^k / n` mirrors the lower partial moment for
+    /// the values above the threshold, measuring the upside potential:
+    ///
+    /// - Order 0 is the fraction of values above the threshold, the hit rate of a strategy
+    /// - Order 1 over the lower partial moment of order 1 is the Omega ratio
+    /// - Order 1 over the square root of the lower partial moment of order 2 is the upside
+    ///   potential ratio
+    ///
+    /// The threshold is served like in [`lower_partial_moment`](Self::lower_partial_moment).
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - The order of the moment, between 0 and 4
+    /// * `threshold` - The threshold the moment is measured from
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The upper partial moment, or `None` if the window is not full, the
+    ///   order is out of range, or partial moments are not enabled
+    pub fn upper_partial_moment(&self, order: usize, threshold: T) -> Option<T> {
+        if !self.moments.is_ready() {
+            return None;
+        }
+        match &self.partial_moments {
+            Some(pm) if pm.threshold() == threshold => pm.upper(order, self.moments.count()),
+            _ => self
+                .rb_tree
+                .as_ref()?
+                .upper_partial_moment(order, threshold),
+        }
+    }
+
+    /// Returns the downside semivariance, the variance contributed by values below the mean
+    ///
+    /// Squared deviations below the mean are normalized like [`variance`](Self::variance),
+    /// so the downside and upside semivariances add up to the variance. Asymmetric
+    /// dispersion reveals:
+    ///
+    /// - Loss-heavy volatility regimes that the symmetric variance understates
+    /// - Skewed return profiles of option-selling and trend-following strategies
+    /// - Downside beta and semi-deviation inputs for risk budgets
+    ///
+    /// As the mean moves with the window, the semivariance is served by the order-statistic
+    /// tree of [`with_partial_moments`](SingleStatisticsBuilder::with_partial_moments).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The downside semivariance, or `None` if the window is not full or
+    ///   partial moments are not enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = SingleStatistics::builder(3).with_partial_moments(26.0).build();
+    /// let mut results = vec![];
+    /// let inputs = [25.4, 26.2, 26.0, 26.1, 25.8, 25.9, 26.3, 26.2, 26.5];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).downside_semivariance().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 7] = [0.0726, 0.0033, 0.0093, 0.0063, 0.0167, 0.0181, 0.0063];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn downside_semivariance(&self) -> Option<T> {
+        let mean = self.mean()?;
+        let lpm = self.rb_tree.as_ref()?.lower_partial_moment(2, mean)?;
+        self.semivariance(lpm)
+    }
+
+    /// Returns the upside semivariance, the variance contributed by values above the mean
+    ///
+    /// Squared deviations above the mean are normalized like [`variance`](Self::variance).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The upside semivariance, or `None` if the window is not full or
+    ///   partial moments are not enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = SingleStatistics::builder(3)
+    ///     .with_partial_moments(26.0)
+    ///     .with_ddof(true)
+    ///     .build();
+    /// let mut results = vec![];
+    /// let inputs = [25.4, 26.2, 26.0, 26.1, 25.8, 25.9, 26.3, 26.2, 26.5];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).upside_semivariance().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 7] = [0.0644, 0.005, 0.0094, 0.0139, 0.045, 0.0161, 0.0139];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn upside_semivariance(&self) -> Option<T> {
+        let mean = self.mean()?;
+        let upm = self.rb_tree.as_ref()?.upper_partial_moment(2, mean)?;
+        self.semivariance(upm)
+    }
+
+    /// Scales a partial moment of order 2 to the normalization of the variance
+    fn semivariance(&self, partial_moment: T) -> Option<T> {
+        if !self.ddof() {
+            return Some(partial_moment);
+        }
+        let n = T::from(self.moments.count())?;
+        let _1 = T::one();
+        (n > _1).then(|| partial_moment * n / (n - _1))
+    }
+
+    /// Returns the slope of the linear regression line
+    ///
+    /// The regression slope represents the rate of change in the best-fit linear model,
+    /// quantifying the directional movement and its magnitude within the data:
+    ///
+    /// - Provides precise measurement of trend strength and conviction
     /// - Quantifies velocity of change for optimal timing decisions
     /// - Signals potential reversion points when diverging from historical patterns
     /// - Measures the relative imbalance between supply and demand forces
@@ -799,16 +1470,7 @@ where
     /// }
     /// ```
     pub fn linreg_slope(&self) -> Option<T> {
-        if !self.moments.is_ready() {
-            return None;
-        }
-
-        let mut s = PairedStatistics::new(self.period());
-        for (i, &x) in self.moments.iter().enumerate() {
-            s.next((x, T::from(i)?));
-        }
-
-        s.beta()
+        self.moments.linreg_slope()
     }
 
     /// Returns both slope and intercept of the linear regression line
@@ -974,225 +1636,216 @@ where
             .map(|((slope, intercept), period)| slope * (period - _1) + intercept)
     }
 
-    /// Returns the current drawdown from peak
+    /// Returns the coefficient of determination (R²) of the linear regression line
     ///
-    /// Measures the percentage decline from the highest observed value to the current value,
-    /// providing crucial insights for risk management and performance evaluation:
+    /// R² is the share of the variance in the window explained by the linear trend,
+    /// ranging from 0 for no linear trend to 1 for values lying exactly on a line:
     ///
-    /// - Enables dynamic adjustment of risk exposure during challenging conditions
-    /// - Facilitates strategy rotation based on relative performance metrics
-    /// - Forms the foundation of capital preservation systems during market stress
-    /// - Identifies potential opportunities for strategic positioning during dislocations
+    /// - Separates clean trends from choppy, range-bound price action
+    /// - Gates trend-following entries on the quality of the fit
+    /// - Complements the slope, which measures direction but not reliability
     ///
     /// # Returns
     ///
-    /// * `Option<T>` - The current drawdown from peak, or `None` if the window is not full
+    /// * `Option<T>` - The R² of the regression line, or `None` if the window is not full or the values are constant
     ///
     /// # Examples
     ///
     /// ```
     /// # use ta_statistics::SingleStatistics;
     /// # use assert_approx_eq::assert_approx_eq;
-    /// let mut stats = SingleStatistics::new(3);
+    /// let mut stats = SingleStatistics::new(5);
     /// let mut results = vec![];
-    /// let inputs = [100.0, 110.0, 105.0, 115.0, 100.0, 95.0, 105.0, 110.0, 100.0];
+    /// let inputs = [10.0, 10.5, 11.2, 10.9, 11.5, 11.9, 12.3, 12.1, 11.8, 12.5];
     /// inputs.iter().for_each(|i| {
-    ///     stats.next(*i).drawdown().map(|v| results.push(v));
+    ///     stats.next(*i).linreg_r2().map(|v| results.push(v));
     /// });
     ///
-    /// let expected: [f64; 7] = [0.045, 0.0, 0.13, 0.174, 0.0, 0.0, 0.091];
+    /// let expected: [f64; 6] = [0.8329, 0.8284, 0.8312, 0.8312, 0.1739, 0.1494];
     /// for (i, e) in expected.iter().enumerate() {
-    ///     assert_approx_eq!(e, results[i], 0.1);
+    ///     assert_approx_eq!(e, results[i], 0.0001);
     /// }
     /// ```
-    pub fn drawdown(&mut self) -> Option<T> {
-        self.max().zip(self.moments.value()).map(|(max, input)| {
-            if max <= T::zero() || input <= T::zero() {
-                T::zero()
-            } else {
-                Float::max((max - input) / max, T::zero())
-            }
-        })
+    pub fn linreg_r2(&self) -> Option<T> {
+        self.moments.linreg_r2()
     }
 
-    /// Returns the maximum drawdown in the window
+    /// Returns the standard error of the estimate of the linear regression line
     ///
-    /// Maximum drawdown measures the largest peak-to-trough decline within a time series,
-    /// serving as a foundational metric for risk assessment and strategy evaluation:
+    /// The standard error of the estimate is the typical distance of the values from
+    /// the regression line, `sqrt(SSE / (n - 2))`. It always uses `n - 2` degrees of
+    /// freedom for the two fitted parameters, regardless of `ddof`:
     ///
-    /// - Establishes critical constraints for comprehensive risk management frameworks
-    /// - Provides an objective metric for evaluating strategy viability under stress
-    /// - Informs position sizing parameters to maintain proportional risk exposure
-    /// - Contributes valuable input to market regime classification models
+    /// - Measures the noise around the trend in price units
+    /// - Sets the width of linear regression channels
+    /// - Scales stop distances to the dispersion around the trend
     ///
     /// # Returns
     ///
-    /// * `Option<T>` - The maximum drawdown in the window, or `None` if the window is not full
+    /// * `Option<T>` - The standard error of the estimate, or `None` if the window is not full or holds fewer than three values
     ///
     /// # Examples
     ///
     /// ```
     /// # use ta_statistics::SingleStatistics;
     /// # use assert_approx_eq::assert_approx_eq;
-    /// let mut stats = SingleStatistics::new(3);
+    /// let mut stats = SingleStatistics::new(5);
     /// let mut results = vec![];
-    /// let inputs = [100.0, 110.0, 105.0, 115.0, 100.0, 95.0, 105.0, 110.0, 100.0];
+    /// let inputs = [10.0, 10.5, 11.2, 10.9, 11.5, 11.9, 12.3, 12.1, 11.8, 12.5];
     /// inputs.iter().for_each(|i| {
-    ///     stats.next(*i).max_drawdown().map(|v| results.push(v));
+    ///     stats.next(*i).linreg_stderr().map(|v| results.push(v));
     /// });
     ///
-    /// let expected: [f64; 7] = [0.045, 0.045, 0.13, 0.174, 0.174, 0.174, 0.174];
+    /// let expected: [f64; 6] = [0.2781, 0.2576, 0.2633, 0.2633, 0.3183, 0.305];
     /// for (i, e) in expected.iter().enumerate() {
-    ///     assert_approx_eq!(e, results[i], 0.1);
+    ///     assert_approx_eq!(e, results[i], 0.0001);
     /// }
-    ///
     /// ```
-    pub fn max_drawdown(&mut self) -> Option<T> {
-        let drawdown = self.drawdown()?;
-        self.max_drawdown = match self.max_drawdown {
-            Some(md) => Some(Float::max(md, drawdown)),
-            None => Some(drawdown),
-        };
-        self.max_drawdown
+    pub fn linreg_stderr(&self) -> Option<T> {
+        let n = T::from(self.count())?;
+        let _2 = T::from(2)?;
+        if n <= _2 {
+            return None;
+        }
+        self.moments.linreg_sse().map(|sse| (sse / (n - _2)).sqrt())
     }
 
-    /// Returns the difference between the last and first values
+    /// Returns the standard error of the slope of the linear regression line
     ///
-    /// This fundamental calculation of absolute change between two points provides
-    /// essential directional and magnitude information for time series analysis:
+    /// The standard error of the slope is the standard error of the estimate scaled by
+    /// the spread of the bar positions, `SE / sqrt(Σ (i - ī)²)`:
     ///
-    /// - Enables momentum measurement for trend strength evaluation
-    /// - Quantifies rate-of-change to optimize timing decisions
-    /// - Serves as a building block for pattern recognition in sequential data
-    /// - Provides critical inputs for calculating hedge ratios and exposure management
+    /// - Quantifies the uncertainty of the measured trend velocity
+    /// - Provides confidence intervals around the slope
+    /// - Normalizes slopes into a t-statistic for significance tests
     ///
     /// # Returns
     ///
-    /// * `Option<T>` - The difference between values, or `None` if the window is not full
+    /// * `Option<T>` - The standard error of the slope, or `None` if the window is not full or holds fewer than three values
     ///
     /// # Examples
     ///
     /// ```
     /// # use ta_statistics::SingleStatistics;
     /// # use assert_approx_eq::assert_approx_eq;
-    /// let mut stats = SingleStatistics::new(3);
+    /// let mut stats = SingleStatistics::new(5);
     /// let mut results = vec![];
-    /// let inputs = [100.0, 102.0, 105.0, 101.0, 98.0];
+    /// let inputs = [10.0, 10.5, 11.2, 10.9, 11.5, 11.9, 12.3, 12.1, 11.8, 12.5];
     /// inputs.iter().for_each(|i| {
-    ///     stats.next(*i).diff().map(|v| results.push(v));
+    ///     stats.next(*i).linreg_slope_stderr().map(|v| results.push(v));
     /// });
     ///
-    /// let expected: [f64; 2] = [1.0, -4.0];
+    /// let expected: [f64; 6] = [0.0879, 0.0814, 0.0833, 0.0833, 0.1007, 0.0964];
     /// for (i, e) in expected.iter().enumerate() {
-    ///     assert_approx_eq!(e, results[i], 0.1);
+    ///     assert_approx_eq!(e, results[i], 0.0001);
     /// }
-    ///
     /// ```
-    pub fn diff(&self) -> Option<T> {
-        self.moments
-            .value()
-            .zip(self.moments.popped())
-            .map(|(input, popped)| input - popped)
+    pub fn linreg_slope_stderr(&self) -> Option<T> {
+        let n = T::from(self.count())?;
+        let sxx = n * (n * n - T::one()) / T::from(12)?;
+        self.linreg_stderr().map(|se| se / sxx.sqrt())
     }
 
-    /// Returns the percentage change between the first and last values
+    /// Returns the t-statistic of the slope of the linear regression line
     ///
-    /// Percentage change normalizes absolute changes by the starting value, enabling
-    /// meaningful comparisons across different scales and measurement contexts:
+    /// The t-statistic divides the slope by its standard error, telling a significant
+    /// trend apart from noise. Under the null hypothesis of no trend it follows a
+    /// Student's t distribution with `n - 2` degrees of freedom:
     ///
-    /// - Facilitates cross-asset performance comparison for relative strength analysis
-    /// - Provides risk-normalized return metrics that account for initial exposure
-    /// - Enables position sizing that properly adjusts for varying volatility environments
-    /// - Serves as a key input for comparative performance evaluation across related groups
+    /// - Filters trend signals whose slope is indistinguishable from zero
+    /// - Compares trend conviction across instruments and window lengths
+    /// - Detects the onset and exhaustion of statistically meaningful trends
     ///
     /// # Returns
     ///
-    /// * `Option<T>` - The percentage change, or `None` if the window is not full
+    /// * `Option<T>` - The t-statistic of the slope, or `None` if the window is not full, holds fewer than three values or lies exactly on a line
     ///
     /// # Examples
     ///
     /// ```
     /// # use ta_statistics::SingleStatistics;
     /// # use assert_approx_eq::assert_approx_eq;
-    /// let mut stats = SingleStatistics::new(3);
+    /// let mut stats = SingleStatistics::new(5);
     /// let mut results = vec![];
-    /// let inputs = [100.0, 105.0, 103.0, 106.0, 110.0, 108.0];
+    /// let inputs = [10.0, 10.5, 11.2, 10.9, 11.5, 11.9, 12.3, 12.1, 11.8, 12.5];
     /// inputs.iter().for_each(|i| {
-    ///     stats.next(*i).pct_change().map(|v| results.push(v));
+    ///     stats.next(*i).linreg_slope_tstat().map(|v| results.push(v));
     /// });
     ///
-    /// let expected: [f64; 3] = [0.06, 0.04761905, 0.04854369];
+    /// let expected: [f64; 6] = [3.8663, 3.8062, 3.8431, 3.8431, 0.7947, 0.7259];
     /// for (i, e) in expected.iter().enumerate() {
-    ///     assert_approx_eq!(e, results[i], 0.1);
+    ///     assert_approx_eq!(e, results[i], 0.0001);
     /// }
-    ///
     /// ```
-    pub fn pct_change(&self) -> Option<T> {
-        self.diff()
-            .zip(self.moments.popped())
-            .and_then(|(diff, popped)| {
-                if popped.is_zero() {
-                    None
-                } else {
-                    Some(diff / popped)
-                }
-            })
+    pub fn linreg_slope_tstat(&self) -> Option<T> {
+        let (slope, se) = self.linreg_slope().zip(self.linreg_slope_stderr())?;
+        if se.is_zero() {
+            return None;
+        }
+        Some(slope / se)
     }
 
-    /// Returns the logarithmic return between the first and last values
+    /// Returns the linear regression channel at the last position
     ///
-    /// Logarithmic returns (continuous returns) offer mathematical advantages over simple
-    /// returns, particularly for time series analysis:
+    /// The channel bands lie `k` standard errors of the estimate below and above the
+    /// linear regression value, `linreg ± k · SE`:
     ///
-    /// - Provides time-additive metrics that can be properly aggregated across periods
-    /// - Normalizes signals in relative-value analysis of related securities
-    /// - Creates a more consistent volatility scale regardless of price levels
-    /// - Improves accuracy in long-horizon analyses through proper handling of compounding
+    /// - Marks statistically stretched prices for mean-reversion entries
+    /// - Defines breakout levels relative to the prevailing trend
+    /// - Adapts band width to the noise around the trend rather than total volatility
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The number of standard errors between the regression value and each band
     ///
     /// # Returns
     ///
-    /// * `Option<T>` - The logarithmic return, or `None` if the window is not full
+    /// * `Option<(T, T)>` - A tuple containing (lower, upper) bands, or `None` if the window is not full or holds fewer than three values
     ///
     /// # Examples
     ///
     /// ```
     /// # use ta_statistics::SingleStatistics;
     /// # use assert_approx_eq::assert_approx_eq;
-    /// let mut stats = SingleStatistics::new(3);
+    /// let mut stats = SingleStatistics::new(5);
     /// let mut results = vec![];
-    /// let inputs = [100.0, 105.0, 103.0, 106.0, 110.0, 108.0];
+    /// let inputs = [10.0, 10.5, 11.2, 10.9, 11.5, 11.9, 12.3, 12.1, 11.8, 12.5];
     /// inputs.iter().for_each(|i| {
-    ///     stats.next(*i).log_return().map(|v| results.push(v));
+    ///     stats.next(*i).linreg_bands(2.0).map(|v| results.push(v));
     /// });
     ///
-    /// let expected: [f64; 3] = [0.05827, 0.04652, 0.04727];
+    /// let expected: [(f64, f64); 6] = [
+    ///     (10.9438, 12.0562),
+    ///     (11.3049, 12.3351),
+    ///     (11.6734, 12.7266),
+    ///     (11.8534, 12.9066),
+    ///     (11.4433, 12.7167),
+    ///     (11.6501, 12.8699),
+    /// ];
     /// for (i, e) in expected.iter().enumerate() {
-    ///     assert_approx_eq!(e, results[i], 0.1);
+    ///     assert_approx_eq!(e.0, results[i].0, 0.0001);
+    ///     assert_approx_eq!(e.1, results[i].1, 0.0001);
     /// }
-    ///
     /// ```
-    pub fn log_return(&self) -> Option<T> {
-        self.moments
-            .value()
-            .zip(self.moments.popped())
-            .and_then(|(current, popped)| {
-                if popped <= T::zero() || current <= T::zero() {
-                    None
-                } else {
-                    Some(current.ln() - popped.ln())
-                }
-            })
+    pub fn linreg_bands(&self, k: T) -> Option<(T, T)> {
+        self.linreg()
+            .zip(self.linreg_stderr())
+            .map(|(value, se)| (value - k * se, value + k * se))
     }
 
-    /// Returns the quantile of the values in the window
+    /// Returns the current drawdown from peak
     ///
-    /// # Arguments
+    /// Measures the percentage decline from the highest observed value to the current value,
+    /// providing crucial insights for risk management and performance evaluation:
     ///
-    /// * `q` - The quantile to calculate
+    /// - Enables dynamic adjustment of risk exposure during challenging conditions
+    /// - Facilitates strategy rotation based on relative performance metrics
+    /// - Forms the foundation of capital preservation systems during market stress
+    /// - Identifies potential opportunities for strategic positioning during dislocations
     ///
     /// # Returns
     ///
-    /// * `Option<T>` - The quantile, or `None` if the window is not full
+    /// * `Option<T>` - The current drawdown from peak, or `None` if the window is not full
     ///
     /// # Examples
     ///
@@ -1201,21 +1854,518 @@ where
     /// # use assert_approx_eq::assert_approx_eq;
     /// let mut stats = SingleStatistics::new(3);
     /// let mut results = vec![];
-    /// let inputs = [10.0, 20.0, 30.0, 40.0, 50.0];
+    /// let inputs = [100.0, 110.0, 105.0, 115.0, 100.0, 95.0, 105.0, 110.0, 100.0];
     /// inputs.iter().for_each(|i| {
-    ///     stats.next(*i).quantile(0.5).map(|v| results.push(v));
+    ///     stats.next(*i).drawdown().map(|v| results.push(v));
     /// });
     ///
-    /// let expected: [f64; 3] = [20.0, 30.0, 40.0];
+    /// let expected: [f64; 7] = [0.045, 0.0, 0.13, 0.174, 0.0, 0.0, 0.091];
     /// for (i, e) in expected.iter().enumerate() {
     ///     assert_approx_eq!(e, results[i], 0.1);
     /// }
     /// ```
-    pub fn quantile(&mut self, q: f64) -> Option<T> {
-        if !self.moments.is_ready() || !(0.0..=1.0).contains(&q) {
-            return None;
-        }
-        self.rb_tree.quantile(q)
+    pub fn drawdown(&self) -> Option<T> {
+        self.max().zip(self.moments.value()).map(|(max, input)| {
+            if max <= T::zero() || input <= T::zero() {
+                T::zero()
+            } else {
+                Float::max((max - input) / max, T::zero())
+            }
+        })
+    }
+
+    /// Returns the maximum drawdown in the window
+    ///
+    /// Maximum drawdown measures the largest peak-to-trough decline within a time series,
+    /// serving as a foundational metric for risk assessment and strategy evaluation:
+    ///
+    /// - Establishes critical constraints for comprehensive risk management frameworks
+    /// - Provides an objective metric for evaluating strategy viability under stress
+    /// - Informs position sizing parameters to maintain proportional risk exposure
+    /// - Contributes valuable input to market regime classification models
+    ///
+    /// The decline is tracked on every [`next`](Self::next) call over the values currently
+    /// in the window, where the peak precedes the trough, so the result does not depend on
+    /// how often it is read. Updates and lookups are amortized O(1).
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The maximum drawdown in the window, or `None` if the window is not full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = SingleStatistics::new(3);
+    /// let mut results = vec![];
+    /// let inputs = [100.0, 110.0, 105.0, 115.0, 100.0, 95.0, 105.0, 110.0, 100.0];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).max_drawdown().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 7] = [0.0455, 0.0455, 0.1304, 0.1739, 0.05, 0.0, 0.0909];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn max_drawdown(&self) -> Option<T> {
+        if !self.moments.is_ready() {
+            return None;
+        }
+        self.max_drawdown.as_ref()?.get()
+    }
+
+    /// Returns the number of bars since the running peak of the window
+    ///
+    /// Counts the bars spent below the highest value seen since the start of the window,
+    /// resetting to zero whenever a new peak is made:
+    ///
+    /// - Flags strategies that are stuck under water even when the depth is shallow
+    /// - Triggers reviews or de-risking once a drawdown outlasts historical norms
+    /// - Separates quick pullbacks from prolonged stagnation
+    ///
+    /// Requires [`with_drawdown_profile`](SingleStatisticsBuilder::with_drawdown_profile),
+    /// which the five drawdown profile getters share.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The bars since the running peak, or `None` if the window is not full
+    ///   or the drawdown profile is not enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// let mut stats = SingleStatistics::builder(5).with_drawdown_profile().build();
+    /// let mut results = vec![];
+    /// let inputs = [100.0, 110.0, 105.0, 115.0, 100.0, 95.0, 105.0, 110.0, 100.0, 120.0];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).drawdown_duration().map(|v| results.push(v));
+    /// });
+    ///
+    /// assert_eq!(results, [1, 2, 3, 4, 1, 0]);
+    /// ```
+    pub fn drawdown_duration(&self) -> Option<usize> {
+        self.drawdown_profile().map(|profile| profile.duration)
+    }
+
+    /// Returns the longest drawdown duration in the window
+    ///
+    /// The longest run of consecutive bars spent below the running peak of the window,
+    /// whether or not the drawdown has since recovered:
+    ///
+    /// - Sets expectations for how long a strategy can stagnate
+    /// - Complements the maximum drawdown depth in strategy tear sheets
+    /// - Sizes the patience budget of capital allocators
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The longest drawdown duration in bars, or `None` if the window is not full
+    ///   or the drawdown profile is not enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// let mut stats = SingleStatistics::builder(5).with_drawdown_profile().build();
+    /// let mut results = vec![];
+    /// let inputs = [100.0, 110.0, 105.0, 115.0, 100.0, 95.0, 105.0, 110.0, 100.0, 120.0];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).max_drawdown_duration().map(|v| results.push(v));
+    /// });
+    ///
+    /// assert_eq!(results, [1, 2, 3, 4, 1, 1]);
+    /// ```
+    pub fn max_drawdown_duration(&self) -> Option<usize> {
+        self.drawdown_profile().map(|profile| profile.max_duration)
+    }
+
+    /// Returns the time to recovery from the deepest drawdown in the window
+    ///
+    /// Counts the bars from the trough of the maximum drawdown until the value regains the
+    /// peak that preceded it:
+    ///
+    /// - Measures the resilience of a strategy after its worst loss
+    /// - Distinguishes V-shaped recoveries from slow grinds back to the peak
+    /// - Calibrates how long capital may be tied up after a stress event
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The recovery time in bars, `Some(0)` if the window has no drawdown,
+    ///   or `None` if the window is not full, the deepest drawdown has not recovered yet or the
+    ///   drawdown profile is not enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// let mut stats = SingleStatistics::builder(5).with_drawdown_profile().build();
+    /// let mut results = vec![];
+    /// let inputs = [100.0, 110.0, 105.0, 115.0, 100.0, 95.0, 105.0, 110.0, 100.0, 120.0];
+    /// inputs.iter().for_each(|i| {
+    ///     results.push(stats.next(*i).recovery_time());
+    /// });
+    ///
+    /// assert_eq!(results[4..], [None, None, None, None, None, Some(1)]);
+    /// ```
+    pub fn recovery_time(&self) -> Option<usize> {
+        self.drawdown_profile()?.recovery
+    }
+
+    /// Returns the Ulcer Index of the window
+    ///
+    /// The Ulcer Index is the root mean square of the drawdown series, measured from the
+    /// running peak of the window, so deep and long drawdowns weigh more than brief dips:
+    ///
+    /// - Measures downside risk in terms of both depth and duration of drawdowns
+    /// - Replaces the standard deviation in the Ulcer Performance Index (Martin ratio)
+    /// - Ranks strategies by the investor stress they inflict
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Ulcer Index as a fraction, or `None` if the window is not full or the
+    ///   drawdown profile is not enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = SingleStatistics::builder(5).with_drawdown_profile().build();
+    /// let mut results = vec![];
+    /// let inputs = [100.0, 110.0, 105.0, 115.0, 100.0, 95.0, 105.0, 110.0, 100.0, 120.0];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).ulcer_index().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 6] = [0.061773, 0.099323, 0.10471, 0.1065, 0.046399, 0.040656];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.000001);
+    /// }
+    /// ```
+    pub fn ulcer_index(&self) -> Option<T> {
+        let profile = self.drawdown_profile()?;
+        Some((profile.sum_sq.total() / T::from(profile.bars)?).sqrt())
+    }
+
+    /// Returns the pain index of the window
+    ///
+    /// The pain index is the mean of the drawdown series, measured from the running peak
+    /// of the window, i.e. the average depth below the peak over every bar:
+    ///
+    /// - Summarizes the area under the underwater curve in a single number
+    /// - Serves as the denominator of the pain ratio
+    /// - Penalizes persistent shallow drawdowns that the maximum drawdown ignores
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The pain index as a fraction, or `None` if the window is not full or the
+    ///   drawdown profile is not enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = SingleStatistics::builder(5).with_drawdown_profile().build();
+    /// let mut results = vec![];
+    /// let inputs = [100.0, 110.0, 105.0, 115.0, 100.0, 95.0, 105.0, 110.0, 100.0, 120.0];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).pain_index().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 6] = [0.035178, 0.06996, 0.078261, 0.086957, 0.028182, 0.018182];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.000001);
+    /// }
+    /// ```
+    pub fn pain_index(&self) -> Option<T> {
+        let profile = self.drawdown_profile()?;
+        Some(profile.sum.total() / T::from(profile.bars)?)
+    }
+
+    /// Returns the drawdown summary of the window once it is full
+    fn drawdown_profile(&self) -> Option<&DrawdownProfile<T>> {
+        if !self.moments.is_ready() {
+            return None;
+        }
+        self.drawdown_profile.as_ref()
+    }
+
+    /// Returns the difference between the last and first values
+    ///
+    /// This fundamental calculation of absolute change between two points provides
+    /// essential directional and magnitude information for time series analysis:
+    ///
+    /// - Enables momentum measurement for trend strength evaluation
+    /// - Quantifies rate-of-change to optimize timing decisions
+    /// - Serves as a building block for pattern recognition in sequential data
+    /// - Provides critical inputs for calculating hedge ratios and exposure management
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The difference between values, or `None` if the window is not full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = SingleStatistics::new(3);
+    /// let mut results = vec![];
+    /// let inputs = [100.0, 102.0, 105.0, 101.0, 98.0];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).diff().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 2] = [1.0, -4.0];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.1);
+    /// }
+    ///
+    /// ```
+    pub fn diff(&self) -> Option<T> {
+        self.moments
+            .value()
+            .zip(self.moments.popped())
+            .map(|(input, popped)| input - popped)
+    }
+
+    /// Returns the percentage change between the first and last values
+    ///
+    /// Percentage change normalizes absolute changes by the starting value, enabling
+    /// meaningful comparisons across different scales and measurement contexts:
+    ///
+    /// - Facilitates cross-asset performance comparison for relative strength analysis
+    /// - Provides risk-normalized return metrics that account for initial exposure
+    /// - Enables position sizing that properly adjusts for varying volatility environments
+    /// - Serves as a key input for comparative performance evaluation across related groups
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The percentage change, or `None` if the window is not full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = SingleStatistics::new(3);
+    /// let mut results = vec![];
+    /// let inputs = [100.0, 105.0, 103.0, 106.0, 110.0, 108.0];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).pct_change().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 3] = [0.06, 0.04761905, 0.04854369];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.1);
+    /// }
+    ///
+    /// ```
+    pub fn pct_change(&self) -> Option<T> {
+        self.diff()
+            .zip(self.moments.popped())
+            .and_then(|(diff, popped)| {
+                if popped.is_zero() {
+                    None
+                } else {
+                    Some(diff / popped)
+                }
+            })
+    }
+
+    /// Returns the logarithmic return between the first and last values
+    ///
+    /// Logarithmic returns (continuous returns) offer mathematical advantages over simple
+    /// returns, particularly for time series analysis:
+    ///
+    /// - Provides time-additive metrics that can be properly aggregated across periods
+    /// - Normalizes signals in relative-value analysis of related securities
+    /// - Creates a more consistent volatility scale regardless of price levels
+    /// - Improves accuracy in long-horizon analyses through proper handling of compounding
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The logarithmic return, or `None` if the window is not full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = SingleStatistics::new(3);
+    /// let mut results = vec![];
+    /// let inputs = [100.0, 105.0, 103.0, 106.0, 110.0, 108.0];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).log_return().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 3] = [0.05827, 0.04652, 0.04727];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.1);
+    /// }
+    ///
+    /// ```
+    pub fn log_return(&self) -> Option<T> {
+        self.moments
+            .value()
+            .zip(self.moments.popped())
+            .and_then(|(current, popped)| {
+                if popped <= T::zero() || current <= T::zero() {
+                    None
+                } else {
+                    Some(current.ln() - popped.ln())
+                }
+            })
+    }
+
+    /// Returns the quantile of the values in the window
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - The quantile to calculate
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The quantile, or `None` if the window is not full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = SingleStatistics::new(3);
+    /// let mut results = vec![];
+    /// let inputs = [10.0, 20.0, 30.0, 40.0, 50.0];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).quantile(0.5).map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 3] = [20.0, 30.0, 40.0];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.1);
+    /// }
+    /// ```
+    pub fn quantile(&self, q: f64) -> Option<T> {
+        if !self.moments.is_ready() || !(0.0..=1.0).contains(&q) {
+            return None;
+        }
+        self.quantile_with(q, self.quantile_method)
+    }
+
+    /// Returns the quantile of the values in the window estimated with the given method
+    ///
+    /// Overrides the method set with [`set_quantile_method`](Self::set_quantile_method)
+    /// for a single call.
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - The quantile to calculate
+    /// * `method` - The quantile method
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The quantile, or `None` if the window is not full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::{QuantileMethod, SingleStatistics};
+    /// let mut stats = SingleStatistics::new(4);
+    /// let inputs = [10.0, 40.0, 20.0, 30.0];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i);
+    /// });
+    ///
+    /// assert_eq!(stats.quantile_with(0.5, QuantileMethod::Linear), Some(25.0));
+    /// assert_eq!(stats.quantile_with(0.5, QuantileMethod::Lower), Some(20.0));
+    /// assert_eq!(stats.quantile_with(0.5, QuantileMethod::Higher), Some(30.0));
+    /// assert_eq!(stats.quantile_with(0.25, QuantileMethod::Weibull), Some(12.5));
+    /// ```
+    pub fn quantile_with(&self, q: f64, method: QuantileMethod) -> Option<T> {
+        if !self.moments.is_ready() || !(0.0..=1.0).contains(&q) {
+            return None;
+        }
+        self.rb_tree.as_ref()?.quantile_with(q, method)
+    }
+
+    /// Returns the percentile rank of the most recent value within the window
+    ///
+    /// The percentile rank is the rank of the most recent value among the values in the
+    /// window divided by the number of values, matching pandas `rolling().rank(pct=True)`:
+    ///
+    /// - Normalizes features onto `(0, 1]` without assumptions about their distribution
+    /// - Stays robust to outliers that distort z-score normalization
+    /// - Flags values at the extremes of their recent history
+    ///
+    /// Counts are read from the order-statistic tree in O(log n).
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - How to rank values tied with other values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The percentile rank, or `None` if the window is not full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::{RankMethod, SingleStatistics};
+    /// let mut stats = SingleStatistics::new(4);
+    /// let mut results = vec![];
+    /// let inputs = [10.0, 20.0, 20.0, 15.0, 30.0, 20.0, 5.0];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).percentile_rank(RankMethod::Average).map(|v| results.push(v));
+    /// });
+    ///
+    /// assert_eq!(&results, &[0.5, 1.0, 0.625, 0.25]);
+    /// ```
+    pub fn percentile_rank(&self, method: RankMethod) -> Option<T> {
+        self.rank_of(self.moments.value()?, method)
+    }
+
+    /// Returns the percentile rank of any value within the window
+    ///
+    /// Ties with values in the window are ranked with the given method. A value that does
+    /// not occur in the window ranks as the fraction of the window below it.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to rank
+    /// * `method` - How to rank values tied with other values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The percentile rank, or `None` if the window is not full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::{RankMethod, SingleStatistics};
+    /// let mut stats = SingleStatistics::new(4);
+    /// let inputs = [20.0, 15.0, 30.0, 20.0];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i);
+    /// });
+    ///
+    /// assert_eq!(stats.rank_of(20.0, RankMethod::Average), Some(0.625));
+    /// assert_eq!(stats.rank_of(20.0, RankMethod::Min), Some(0.5));
+    /// assert_eq!(stats.rank_of(20.0, RankMethod::Max), Some(0.75));
+    /// assert_eq!(stats.rank_of(17.0, RankMethod::Average), Some(0.25));
+    /// assert_eq!(stats.rank_of(40.0, RankMethod::Average), Some(1.0));
+    /// ```
+    pub fn rank_of(&self, value: T, method: RankMethod) -> Option<T> {
+        if !self.moments.is_ready() {
+            return None;
+        }
+        let rb_tree = self.rb_tree.as_ref()?;
+        let (less, equal) = rb_tree.rank_counts(value);
+        method
+            .percentile(less, equal, rb_tree.total_count())
+            .and_then(T::from)
     }
 
     /// Returns the interquartile range of the values in the window
@@ -1242,14 +2392,521 @@ where
     /// }
     ///
     /// ```
-    pub fn iqr(&mut self) -> Option<T> {
+    pub fn iqr(&self) -> Option<T> {
         if !self.moments.is_ready() {
             return None;
         }
 
-        let q1 = self.rb_tree.quantile(0.25);
-        let q3 = self.rb_tree.quantile(0.75);
+        let rb_tree = self.rb_tree.as_ref()?;
+        let q1 = rb_tree.quantile_with(0.25, self.quantile_method);
+        let q3 = rb_tree.quantile_with(0.75, self.quantile_method);
 
         q1.zip(q3).map(|(q1, q3)| q3 - q1)
     }
+
+    /// Returns the Value-at-Risk of the returns in the window
+    ///
+    /// The Value-at-Risk is the loss that is only exceeded with probability `alpha`,
+    /// reported as a positive number, so `var(0.05)` is the 95% VaR of the window. Values
+    /// are expected to be returns or P&L, estimated with the configured
+    /// [`VarMethod`](Self::set_var_method):
+    ///
+    /// - Sets position limits and capital buffers from recent volatility
+    /// - Compares realized losses against the forecast in backtests of the risk model
+    /// - Contrasts the Gaussian and Cornish–Fisher estimates to expose fat tails
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The tail probability, between 0 and 1
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Value-at-Risk, or `None` if the window is not full, `alpha` is
+    ///   outside `(0, 1)`, or historical estimates are requested without quantiles enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = SingleStatistics::new(10);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///     0.012, -0.008, 0.015, -0.022, 0.005, 0.018, -0.031, 0.007, -0.004, 0.011, 0.021,
+    ///     -0.015, 0.003, -0.009, 0.025, -0.012,
+    /// ];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).var(0.2).map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 7] = [0.0108, 0.0108, 0.0164, 0.0164, 0.0102, 0.0102, 0.0126];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.000001);
+    /// }
+    /// ```
+    pub fn var(&self, alpha: f64) -> Option<T> {
+        self.var_with(alpha, self.var_method)
+    }
+
+    /// Returns the Value-at-Risk of the returns in the window estimated with the given method
+    ///
+    /// Overrides the method set with [`set_var_method`](Self::set_var_method) for a single
+    /// call, so the estimates can be reported side by side:
+    ///
+    /// - [`VarMethod::Historical`] negates the `alpha` quantile of the window, estimated with
+    ///   the configured [`QuantileMethod`]
+    /// - [`VarMethod::Gaussian`] is `-(μ + σ·z)` with `z = Φ⁻¹(alpha)`
+    /// - [`VarMethod::CornishFisher`] replaces `z` with the Cornish–Fisher expansion
+    ///   `z + (z² - 1)·S/6 + (z³ - 3z)·K/24 - (2z³ - 5z)·S²/36` for the skewness `S` and
+    ///   excess kurtosis `K` of the window
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The tail probability, between 0 and 1
+    /// * `method` - The Value-at-Risk method
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Value-at-Risk, or `None` if the window is not full, `alpha` is
+    ///   outside `(0, 1)`, or historical estimates are requested without quantiles enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::{SingleStatistics, VarMethod};
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = SingleStatistics::new(10);
+    /// let inputs: [f64; 10] = [0.021, -0.015, 0.003, -0.009, 0.025, -0.012, -0.031, 0.007, -0.004, 0.011];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i);
+    /// });
+    ///
+    /// assert_approx_eq!(stats.var_with(0.2, VarMethod::Historical).unwrap(), 0.0126, 0.000001);
+    /// assert_approx_eq!(stats.var_with(0.2, VarMethod::Gaussian).unwrap(), 0.014153, 0.000001);
+    /// assert_approx_eq!(stats.var_with(0.2, VarMethod::CornishFisher).unwrap(), 0.015079, 0.000001);
+    /// ```
+    pub fn var_with(&self, alpha: f64, method: VarMethod) -> Option<T> {
+        if !(self.moments.is_ready() && alpha > 0.0 && alpha < 1.0) {
+            return None;
+        }
+        match method {
+            VarMethod::Historical => {
+                let rb_tree = self.rb_tree.as_ref()?;
+                Some(-rb_tree.quantile_with(alpha, self.quantile_method)?)
+            }
+            VarMethod::Gaussian => {
+                let z = T::from(normal_inverse_cdf(alpha)?)?;
+                Some(-(self.mean()? + self.stddev()? * z))
+            }
+            VarMethod::CornishFisher => {
+                let z = normal_inverse_cdf(alpha)?;
+                let (s, k) = self.skew_kurt()?;
+                let z_cf = z + (z * z - 1.0) * s / 6.0 + (z * z * z - 3.0 * z) * k / 24.0
+                    - (2.0 * z * z * z - 5.0 * z) * s * s / 36.0;
+                Some(-(self.mean()? + self.stddev()? * T::from(z_cf)?))
+            }
+        }
+    }
+
+    /// Returns the Expected Shortfall of the returns in the window
+    ///
+    /// The Expected Shortfall, or Conditional Value-at-Risk, is the average loss in the
+    /// worst `alpha` fraction of outcomes, reported as a positive number. Unlike the VaR it
+    /// accounts for the size of the losses beyond the quantile and is a coherent risk measure:
+    ///
+    /// - Sizes positions against tail losses rather than a single quantile
+    /// - Meets regulatory capital frameworks built on Expected Shortfall
+    /// - Ranks strategies by the severity of their worst periods
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The tail probability, between 0 and 1
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Expected Shortfall, or `None` if the window is not full, `alpha`
+    ///   is outside `(0, 1)`, or historical estimates are requested without quantiles enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::SingleStatistics;
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = SingleStatistics::new(10);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///     0.012, -0.008, 0.015, -0.022, 0.005, 0.018, -0.031, 0.007, -0.004, 0.011, 0.021,
+    ///     -0.015, 0.003, -0.009, 0.025, -0.012,
+    /// ];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).cvar(0.2).map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 7] = [0.0265, 0.0265, 0.0265, 0.0265, 0.023, 0.023, 0.023];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.000001);
+    /// }
+    /// ```
+    pub fn cvar(&self, alpha: f64) -> Option<T> {
+        self.cvar_with(alpha, self.var_method)
+    }
+
+    /// Returns the Expected Shortfall of the returns in the window estimated with the given method
+    ///
+    /// Overrides the method set with [`set_var_method`](Self::set_var_method) for a single call:
+    ///
+    /// - [`VarMethod::Historical`] averages the worst `alpha·n` values, weighting the value
+    ///   that straddles the tail boundary by its fraction, read from the tail sums of the
+    ///   quantile tree in O(log n)
+    /// - [`VarMethod::Gaussian`] is `-(μ - σ·φ(z) / alpha)` with `z = Φ⁻¹(alpha)`
+    /// - [`VarMethod::CornishFisher`] averages the Cornish–Fisher quantile over the tail,
+    ///   integrating the expansion against the truncated normal moments in closed form
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The tail probability, between 0 and 1
+    /// * `method` - The Value-at-Risk method
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Expected Shortfall, or `None` if the window is not full, `alpha`
+    ///   is outside `(0, 1)`, or historical estimates are requested without quantiles enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ta_statistics::{SingleStatistics, VarMethod};
+    /// # use assert_approx_eq::assert_approx_eq;
+    /// let mut stats = SingleStatistics::new(10);
+    /// let inputs: [f64; 10] = [0.021, -0.015, 0.003, -0.009, 0.025, -0.012, -0.031, 0.007, -0.004, 0.011];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i);
+    /// });
+    ///
+    /// assert_approx_eq!(stats.cvar_with(0.2, VarMethod::Historical).unwrap(), 0.023, 0.000001);
+    /// assert_approx_eq!(stats.cvar_with(0.2, VarMethod::Gaussian).unwrap(), 0.023275, 0.000001);
+    /// assert_approx_eq!(stats.cvar_with(0.2, VarMethod::CornishFisher).unwrap(), 0.023917, 0.000001);
+    /// ```
+    pub fn cvar_with(&self, alpha: f64, method: VarMethod) -> Option<T> {
+        if !(self.moments.is_ready() && alpha > 0.0 && alpha < 1.0) {
+            return None;
+        }
+        match method {
+            VarMethod::Historical => {
+                let rb_tree = self.rb_tree.as_ref()?;
+                let n = rb_tree.total_count();
+                let tail = alpha * n as f64;
+                let m = Float::floor(tail + 4.0 * f64::EPSILON * tail) as usize;
+                let mut sum = rb_tree.smallest_sum(m)?;
+                if m < n {
+                    let boundary = rb_tree.smallest_sum(m + 1)? - sum;
+                    sum = sum + boundary * T::from(tail - m as f64)?;
+                }
+                Some(-sum / T::from(tail)?)
+            }
+            VarMethod::Gaussian => {
+                let z = normal_inverse_cdf(alpha)?;
+                let tail_mean = T::from(-normal_pdf(z) / alpha)?;
+                Some(-(self.mean()? + self.stddev()? * tail_mean))
+            }
+            VarMethod::CornishFisher => {
+                let z = normal_inverse_cdf(alpha)?;
+                let (s, k) = self.skew_kurt()?;
+                // Truncated moments E[Z^j; Z < z] of the standard normal
+                let phi = normal_pdf(z);
+                let (m1, m2, m3) = (-phi, alpha - z * phi, -(z * z + 2.0) * phi);
+                let tail_mean = (m1 + (m2 - alpha) * s / 6.0 + (m3 - 3.0 * m1) * k / 24.0
+                    - (2.0 * m3 - 5.0 * m1) * s * s / 36.0)
+                    / alpha;
+                Some(-(self.mean()? + self.stddev()? * T::from(tail_mean)?))
+            }
+        }
+    }
+
+    /// Returns the skewness and excess kurtosis of the window as `f64`
+    fn skew_kurt(&self) -> Option<(f64, f64)> {
+        Some((self.skew()?.to_f64()?, self.kurt()?.to_f64()?))
+    }
+}
+
+/// Summary of the drawdowns of a window from its running peak
+///
+/// Values are walked oldest first. Rolling windows rebuild the summary once per update, as
+/// evicting the oldest value moves the running peak of every later bar, while expanding
+/// windows extend it with each new value.
+#[derive(Debug)]
+struct DrawdownProfile<T> {
+    /// Number of values walked
+    bars: usize,
+    /// Running peak
+    peak: T,
+    /// Deepest drawdown walked
+    max_drawdown: T,
+    /// Peak and bar index of the deepest trough awaiting recovery
+    trough: Option<(T, usize)>,
+    /// Bars since the running peak
+    duration: usize,
+    /// Longest run of bars below the running peak
+    max_duration: usize,
+    /// Bars from the deepest trough to the recovery of its peak
+    recovery: Option<usize>,
+    /// Sum of the drawdowns
+    sum: Kbn<T>,
+    /// Sum of the squared drawdowns
+    sum_sq: Kbn<T>,
+}
+
+impl<T: Float> DrawdownProfile<T> {
+    /// Creates an empty summary
+    fn new() -> Self {
+        Self {
+            bars: 0,
+            peak: T::neg_infinity(),
+            max_drawdown: T::zero(),
+            trough: None,
+            duration: 0,
+            max_duration: 0,
+            recovery: Some(0),
+            sum: Kbn::default(),
+            sum_sq: Kbn::default(),
+        }
+    }
+
+    /// Clears the summary
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Walks the values of a window from scratch
+    fn rebuild<'a>(&mut self, values: impl Iterator<Item = &'a T>)
+    where
+        T: 'a,
+    {
+        self.reset();
+        values.for_each(|&value| self.push(value));
+    }
+
+    /// Extends the summary with the next value
+    fn push(&mut self, value: T) {
+        let i = self.bars;
+        self.bars += 1;
+        if value >= self.peak {
+            self.peak = value;
+        }
+        let drawdown = if self.peak <= T::zero() || value <= T::zero() {
+            T::zero()
+        } else {
+            ((self.peak - value) / self.peak).max(T::zero())
+        };
+
+        self.sum += drawdown;
+        self.sum_sq += drawdown * drawdown;
+        self.duration = if drawdown > T::zero() {
+            self.duration + 1
+        } else {
+            0
+        };
+        self.max_duration = self.max_duration.max(self.duration);
+
+        if drawdown > self.max_drawdown {
+            self.max_drawdown = drawdown;
+            self.trough = Some((self.peak, i));
+            self.recovery = None;
+        } else if let Some((trough_peak, trough_index)) = self.trough
+            && value >= trough_peak
+        {
+            self.recovery = Some(i - trough_index);
+            self.trough = None;
+        }
+    }
+}
+
+/// Builder for [`SingleStatistics`] with selectively enabled components
+///
+/// Moments (sum, mean, variance, standard deviation, z-score, skewness, kurtosis, linear
+/// regression and changes) are always enabled. The remaining components each carry their
+/// own data structure, which is only allocated and updated when enabled:
+///
+/// - [`with_min_max`](Self::with_min_max) - min, max, drawdown and maximum drawdown
+/// - [`with_drawdown_profile`](Self::with_drawdown_profile) - drawdown durations, recovery
+///   time, Ulcer Index and pain index
+/// - [`with_mode`](Self::with_mode) - mode
+/// - [`with_quantiles`](Self::with_quantiles) - median, quantiles, IQR and both MADs
+/// - [`with_partial_moments`](Self::with_partial_moments) - partial moments and semivariances
+///
+/// Getters of disabled components return `None`.
+#[derive(Debug, Clone)]
+pub struct SingleStatisticsBuilder<T> {
+    period: usize,
+    min_max: bool,
+    drawdown_profile: bool,
+    mode: bool,
+    quantiles: bool,
+    quantile_method: QuantileMethod,
+    var_method: VarMethod,
+    partial_moments: Option<T>,
+    ddof: bool,
+}
+
+impl<T> SingleStatisticsBuilder<T>
+where
+    T: Default + Clone + Float + PrimitiveFloat,
+{
+    /// Creates a new builder with only moments enabled
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The period of the statistics
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The builder
+    pub const fn new(period: usize) -> Self {
+        Self {
+            period,
+            min_max: false,
+            drawdown_profile: false,
+            mode: false,
+            quantiles: false,
+            quantile_method: QuantileMethod::Linear,
+            var_method: VarMethod::Historical,
+            partial_moments: None,
+            ddof: false,
+        }
+    }
+
+    /// Enables min, max, drawdown and maximum drawdown using monotonic queues
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The builder
+    pub const fn with_min_max(mut self) -> Self {
+        self.min_max = true;
+        self
+    }
+
+    /// Enables the drawdown durations, recovery time, Ulcer Index and pain index
+    ///
+    /// The drawdowns are measured from the running peak of the window, which moves for
+    /// every later bar when the oldest value is evicted, so the summary is rebuilt once per
+    /// update in O(n) and shared by all five getters. It is therefore left out of
+    /// [`SingleStatistics::new`] and only kept when enabled here.
+    /// [`SingleStatistics::expanding`] always keeps it, as it is extended in O(1) there.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The builder
+    pub const fn with_drawdown_profile(mut self) -> Self {
+        self.drawdown_profile = true;
+        self
+    }
+
+    /// Enables the mode using frequency buckets
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The builder
+    pub const fn with_mode(mut self) -> Self {
+        self.mode = true;
+        self
+    }
+
+    /// Enables median, quantiles, IQR and both MADs using an order-statistic tree
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The builder
+    pub const fn with_quantiles(mut self) -> Self {
+        self.quantiles = true;
+        self
+    }
+
+    /// Enables lower and upper partial moments and the semivariances
+    ///
+    /// The fixed threshold is served by running partial sums, updated in O(1) as values
+    /// enter and leave the window. Any other threshold, such as the mean, is served by an
+    /// order-statistic tree that keeps the central moments of its subtrees, updated in
+    /// O(log n). The tree also serves the quantile getters. Trees of
+    /// [`with_quantiles`](Self::with_quantiles) alone skip these moments.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - The threshold the partial moments are measured from
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The builder
+    pub const fn with_partial_moments(mut self, threshold: T) -> Self {
+        self.partial_moments = Some(threshold);
+        self
+    }
+
+    /// Sets the method used to estimate the median, quantiles and IQR
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The quantile method
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The builder
+    pub const fn with_quantile_method(mut self, method: QuantileMethod) -> Self {
+        self.quantile_method = method;
+        self
+    }
+
+    /// Sets the method used to estimate the Value-at-Risk and Expected Shortfall
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The Value-at-Risk method
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The builder
+    pub const fn with_var_method(mut self, method: VarMethod) -> Self {
+        self.var_method = method;
+        self
+    }
+
+    /// Sets the Delta Degrees of Freedom
+    ///
+    /// # Arguments
+    ///
+    /// * `ddof` - The Delta Degrees of Freedom
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The builder
+    pub const fn with_ddof(mut self, ddof: bool) -> Self {
+        self.ddof = ddof;
+        self
+    }
+
+    /// Builds the `SingleStatistics` instance, allocating only the enabled components
+    ///
+    /// # Returns
+    ///
+    /// * `SingleStatistics<T>` - The statistics object
+    pub fn build(self) -> SingleStatistics<T> {
+        let period = self.period;
+        let mut moments = RollingMoments::new(period);
+        moments.set_ddof(self.ddof);
+        SingleStatistics {
+            moments,
+            min: self.min_max.then(|| Minimum::new(period)),
+            max: self.min_max.then(|| Maximum::new(period)),
+            max_drawdown: self.min_max.then(|| RollingDrawdown::new(period)),
+            drawdown_profile: self.drawdown_profile.then(DrawdownProfile::new),
+            mode: self.mode.then(RollingMode::new),
+            rb_tree: match (self.quantiles, self.partial_moments) {
+                (_, Some(_)) => Some(RbTree::with_moments(period)),
+                (true, None) => Some(RbTree::new(period)),
+                (false, None) => None,
+            },
+            partial_moments: self.partial_moments.map(RollingPartialMoments::new),
+            quantile_method: self.quantile_method,
+            var_method: self.var_method,
+        }
+    }
 }
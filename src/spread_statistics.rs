@@ -0,0 +1,402 @@
+use num_traits::Float;
+use ordered_float::PrimitiveFloat;
+
+use crate::PairedStatistics;
+use crate::rolling::RollingCoMoments;
+
+/// A structure that tracks the hedged spread of a pair of instruments for statistical arbitrage.
+///
+/// `SpreadStatistics<T>` is fed pairs of prices `(x, y)` and hedges `x` with `y` using the
+/// rolling [`PairedStatistics::beta`] of `x` on `y` as the hedge ratio. The mean, standard
+/// deviation and z-score of the spread `x - β·y` cover the latest pairs of the spread window,
+/// all hedged with the current ratio. The mean-reversion half-life comes from the AR(1)
+/// regression `Δs_t = λ·s_(t-1) + c` over the same window, where the lagged spread
+/// `x_(t-1) - β·y_(t-1)` and the change `Δx_t - β·Δy_t` share the current ratio too, so a
+/// drifting hedge ratio does not leak into the spread changes.
+///
+/// The spread is `x - β·y` with `β = cov(x, y) / var(y)` from regressing `x` on `y`, not the
+/// `y - β·x` of regressing `y` on `x`, so formulas written for the latter need `x` and `y`
+/// swapped, or they hedge the wrong leg with the wrong ratio and flip the sign of the spread.
+///
+/// The spread window keeps the means and co-moments of the prices and of their changes and
+/// lagged levels, from which the statistics under any hedge ratio follow, so every update
+/// is O(1). The spread statistics use the population (`n`) normalization by default, like
+/// [`PairedStatistics`].
+///
+/// # Examples
+///
+/// ```
+/// use ta_statistics::SpreadStatistics;
+/// use assert_approx_eq::assert_approx_eq;
+///
+/// let mut stats = SpreadStatistics::new(6, 6);
+/// let mut results = vec![];
+/// let inputs = [
+///     (100.0, 50.0),
+///     (102.5, 51.0),
+///     (101.6, 50.5),
+///     (104.16, 52.0),
+///     (105.7, 53.0),
+///     (104.92, 52.5),
+///     (107.65, 54.0),
+///     (110.19, 55.0),
+///     (109.31, 54.5),
+///     (112.09, 56.0),
+///     (113.65, 57.0),
+///     (112.09, 56.0),
+/// ];
+/// inputs.iter().for_each(|i| {
+///     stats.next(*i).spread().map(|v| results.push(v));
+/// });
+///
+/// let expected: [f64; 7] = [9.67, 16.0336, 7.117027, -2.882143, -6.4172, 0.79, 2.946376];
+/// for (i, e) in expected.iter().enumerate() {
+///     assert_approx_eq!(e, results[i], 0.000001);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpreadStatistics<T> {
+    /// Regression of `x` on `y` behind the hedge ratio
+    hedge: PairedStatistics<T>,
+    /// Co-moments of the prices `(x, y)`
+    levels: RollingCoMoments<T, 2>,
+    /// Co-moments of the price changes and lagged prices `(Δx, Δy, x_(t-1), y_(t-1))`
+    changes: RollingCoMoments<T, 4>,
+    /// Latest pair of prices
+    last: Option<(T, T)>,
+    /// Delta Degrees of Freedom of the spread standard deviation
+    ddof: bool,
+}
+
+impl<T> SpreadStatistics<T>
+where
+    T: Default + Clone + Float + PrimitiveFloat,
+{
+    /// Creates a new `SpreadStatistics` instance with the specified periods.
+    ///
+    /// # Arguments
+    ///
+    /// * `hedge_period` - The number of pairs the hedge ratio is estimated over
+    /// * `spread_period` - The number of spreads the spread statistics and half-life are computed over
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics object
+    pub fn new(hedge_period: usize, spread_period: usize) -> Self {
+        Self {
+            hedge: PairedStatistics::new(hedge_period),
+            levels: RollingCoMoments::new(spread_period),
+            changes: RollingCoMoments::new(spread_period),
+            last: None,
+            ddof: false,
+        }
+    }
+
+    /// Returns the period of the hedge ratio
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of pairs the hedge ratio is estimated over
+    pub fn hedge_period(&self) -> usize {
+        self.hedge.period()
+    }
+
+    /// Returns the period of the spread statistics
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of spreads the spread statistics are computed over
+    pub const fn spread_period(&self) -> usize {
+        self.levels.period()
+    }
+
+    /// Returns the Delta Degrees of Freedom of the spread standard deviation
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True for the sample standard deviation
+    pub const fn ddof(&self) -> bool {
+        self.ddof
+    }
+
+    /// Sets the Delta Degrees of Freedom of the spread standard deviation
+    ///
+    /// # Arguments
+    ///
+    /// * `ddof` - True for the sample standard deviation, false for the population one
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub const fn set_ddof(&mut self, ddof: bool) -> &mut Self {
+        self.ddof = ddof;
+        self
+    }
+
+    /// Resets the statistics
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub fn reset(&mut self) -> &mut Self {
+        self.hedge.reset();
+        self.levels.reset();
+        self.changes.reset();
+        self.last = None;
+        self
+    }
+
+    /// Recomputes the statistics, could be called to avoid
+    /// prolonged compounding of floating rounding errors
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub fn recompute(&mut self) -> &mut Self {
+        self.hedge.recompute();
+        self.levels.recompute();
+        self.changes.recompute();
+        self
+    }
+
+    /// Updates the statistics with a new pair of prices
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A tuple containing the prices `(x, y)` of the hedged and the hedging instrument
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub fn next(&mut self, (x, y): (T, T)) -> &mut Self {
+        self.hedge.next((x, y));
+        if let Some((last_x, last_y)) = self.last {
+            self.changes.next([x - last_x, y - last_y, last_x, last_y]);
+        }
+        self.levels.next([x, y]);
+        self.last = Some((x, y));
+        self
+    }
+
+    /// Returns the hedge ratio, the rolling beta of `x` on `y`
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The number of units of `y` hedging one unit of `x`, or `None` if the
+    ///   hedge window is not full or `y` is constant
+    pub fn hedge_ratio(&self) -> Option<T> {
+        self.hedge.beta()
+    }
+
+    /// Returns the latest spread `x - β·y`
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The latest spread, or `None` if the hedge window is not full
+    pub fn spread(&self) -> Option<T> {
+        let (x, y) = self.last?;
+        Some(x - self.hedge_ratio()? * y)
+    }
+
+    /// Returns the mean of the spread
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The mean spread, or `None` if the hedge or spread window is not full
+    pub fn mean(&self) -> Option<T> {
+        self.levels.mean(&self.spread_weights()?)
+    }
+
+    /// Returns the standard deviation of the spread
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The standard deviation of the spread, or `None` if the hedge or spread
+    ///   window is not full
+    pub fn stddev(&self) -> Option<T> {
+        let weights = self.spread_weights()?;
+        let var = self.levels.covariance(&weights, &weights, self.ddof)?;
+        Some(Float::max(var, T::zero()).sqrt())
+    }
+
+    /// Returns the weights `(1, -β)` of the prices in the spread under the current hedge ratio
+    fn spread_weights(&self) -> Option<[T; 2]> {
+        Some([T::one(), -self.hedge_ratio()?])
+    }
+
+    /// Returns the z-score of the latest spread
+    ///
+    /// The z-score measures how many standard deviations the latest spread lies away from
+    /// its rolling mean:
+    ///
+    /// - Opens positions when the spread is stretched beyond an entry threshold
+    /// - Closes positions as the spread reverts towards its mean
+    /// - Stops out pairs whose spread keeps diverging
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The z-score of the latest spread, or `None` if the spread window is not
+    ///   full or the spread is constant
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::SpreadStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = SpreadStatistics::new(6, 6);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///     (100.0, 50.0),
+    ///     (102.5, 51.0),
+    ///     (101.6, 50.5),
+    ///     (104.16, 52.0),
+    ///     (105.7, 53.0),
+    ///     (104.92, 52.5),
+    ///     (107.65, 54.0),
+    ///     (110.19, 55.0),
+    ///     (109.31, 54.5),
+    ///     (112.09, 56.0),
+    ///     (113.65, 57.0),
+    ///     (112.09, 56.0),
+    ///     (116.16, 58.0),
+    ///     (117.89, 59.0),
+    ///     (117.24, 58.5),
+    ///     (120.04, 60.0),
+    /// ];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).zscore().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 6] = [-0.166839, 1.433494, 1.579486, 1.086421, -0.494993, -1.091662];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.000001);
+    /// }
+    /// ```
+    pub fn zscore(&self) -> Option<T> {
+        let stddev = self.stddev()?;
+        if stddev > T::zero() {
+            Some((self.spread()? - self.mean()?) / stddev)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the mean-reversion half-life of the spread
+    ///
+    /// The spread is modelled as a discretized Ornstein–Uhlenbeck process: the slope `λ` of
+    /// the spread changes on the lagged spread gives the AR(1) coefficient `1 + λ`, and the
+    /// half-life `-ln(2) / ln(1 + λ)` is the number of periods a deviation from the mean
+    /// takes to halve:
+    ///
+    /// - Sizes the holding period and time stops of spread trades
+    /// - Tunes the lookback of the spread z-score to the speed of reversion
+    /// - Filters out pairs that revert too slowly to trade
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The half-life in periods, or `None` if the hedge window or the spread
+    ///   window of changes is not full, or the spread does not revert monotonically, i.e. `λ`
+    ///   lies outside `(-1, 0)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::SpreadStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = SpreadStatistics::new(6, 6);
+    /// let mut results = vec![];
+    /// let inputs = [
+    ///     (100.0, 50.0),
+    ///     (102.5, 51.0),
+    ///     (101.6, 50.5),
+    ///     (104.16, 52.0),
+    ///     (105.7, 53.0),
+    ///     (104.92, 52.5),
+    ///     (107.65, 54.0),
+    ///     (110.19, 55.0),
+    ///     (109.31, 54.5),
+    ///     (112.09, 56.0),
+    ///     (113.65, 57.0),
+    ///     (112.09, 56.0),
+    ///     (116.16, 58.0),
+    ///     (117.89, 59.0),
+    ///     (117.24, 58.5),
+    ///     (120.04, 60.0),
+    /// ];
+    /// inputs.iter().for_each(|i| {
+    ///     stats.next(*i).half_life().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 5] = [0.374997, 0.36959, 0.39719, 0.28868, 0.219566];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.000001);
+    /// }
+    /// ```
+    pub fn half_life(&self) -> Option<T> {
+        let beta = self.hedge_ratio()?;
+        let change = [T::one(), -beta, T::zero(), T::zero()];
+        let lagged = [T::zero(), T::zero(), T::one(), -beta];
+        let var = self.changes.covariance(&lagged, &lagged, false)?;
+        if var <= T::zero() {
+            return None;
+        }
+        let lambda = self.changes.covariance(&change, &lagged, false)? / var;
+        if !(lambda > -T::one() && lambda < T::zero()) {
+            return None;
+        }
+        Some(-T::from(core::f64::consts::LN_2)? / lambda.ln_1p())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+
+    /// Deterministic standard normal draws from a xorshift generator and Box-Muller
+    struct Normal(u64);
+
+    impl Normal {
+        fn uniform(&mut self) -> f64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 >> 11) as f64 / (1u64 << 53) as f64
+        }
+
+        fn sample(&mut self) -> f64 {
+            let (u, v) = (1.0 - self.uniform(), self.uniform());
+            (-2.0 * u.ln()).sqrt() * (2.0 * core::f64::consts::PI * v).cos()
+        }
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn half_life_of_ornstein_uhlenbeck_spread() {
+        // x = 2·y + e with a random walk y and an AR(1) spread e of coefficient 0.8
+        let phi: f64 = 0.8;
+        let mut normal = Normal(2);
+        let mut stats = SpreadStatistics::new(2000, 2000);
+        let (mut y, mut e) = (100.0, 0.0);
+        for _ in 0..2001 {
+            y += normal.sample();
+            e = phi * e + normal.sample();
+            stats.next((2.0 * y + e, y));
+        }
+
+        assert_approx_eq!(stats.hedge_ratio().unwrap(), 2.0, 0.01);
+        assert_approx_eq!(
+            stats.stddev().unwrap(),
+            (1.0 - phi * phi).sqrt().recip(),
+            0.1
+        );
+        assert_approx_eq!(
+            stats.half_life().unwrap(),
+            -core::f64::consts::LN_2 / phi.ln(),
+            0.1
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use num_traits::Float;
+
+use crate::{Minimum, utils::TimeWindow};
+
+/// # Minimum Value Calculation for Time-Based Windows
+///
+/// Tracks the minimum value over a trailing time span rather than a fixed number of values.
+/// The window holds every value whose timestamp lies within `(now - duration, now]`, where
+/// `now` is the timestamp passed to the latest [`push`](Self::push) call.
+///
+/// Lookups are constant-time and updates amortized constant-time, as with [`Minimum`]. The
+/// internal buffers grow and shrink with the number of values in the window, and an optional
+/// upper bound evicts the oldest value once reached.
+///
+/// # Examples
+///
+/// ```
+/// use ta_statistics::TimeMinimum;
+///
+/// let mut min = TimeMinimum::new(60);
+/// let inputs = [(0, 10.0), (20, 11.0), (40, 12.0), (60, 13.0), (70, 9.0), (130, 14.0)];
+/// let mut results = vec![];
+///
+/// inputs.iter().for_each(|&(ts, value)| {
+///     min.push(ts, value);
+///     min.get().map(|v| results.push(v));
+/// });
+///
+/// assert_eq!(&results, &[10.0, 10.0, 10.0, 11.0, 9.0, 14.0]);
+/// ```
+#[derive(Debug)]
+pub struct TimeMinimum<T> {
+    window: TimeWindow<()>,
+    min: Minimum<T>,
+}
+
+impl<T: Default + Clone + Float> TimeMinimum<T> {
+    /// Creates a new TimeMinimum instance with the specified duration
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - The length of the window in timestamp units
+    ///
+    /// # Returns
+    ///
+    /// A new TimeMinimum instance
+    pub fn new(duration: u64) -> Self {
+        Self {
+            window: TimeWindow::new(duration, None),
+            min: Minimum::growable(),
+        }
+    }
+
+    /// Creates a new TimeMinimum instance holding at most `max_capacity` values
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - The length of the window in timestamp units
+    /// * `max_capacity` - The maximum number of values held, the oldest value is evicted beyond it
+    ///
+    /// # Returns
+    ///
+    /// A new TimeMinimum instance
+    pub fn with_max_capacity(duration: u64, max_capacity: usize) -> Self {
+        Self {
+            window: TimeWindow::new(duration, Some(max_capacity)),
+            min: Minimum::growable(),
+        }
+    }
+
+    /// Returns the length of the window in timestamp units
+    pub const fn duration(&self) -> u64 {
+        self.window.duration()
+    }
+
+    /// Pushes a new timestamped value into the window
+    ///
+    /// Evicts every value whose timestamp is at or before `ts - duration` first.
+    ///
+    /// # Arguments
+    ///
+    /// * `ts` - The timestamp of the value, expected to be non-decreasing across calls. A
+    ///   timestamp older than the latest one is treated as the latest timestamp
+    /// * `value` - The new value to be added to the window
+    pub fn push(&mut self, ts: u64, value: T) {
+        while self.window.pop_expired(ts).is_some() {}
+        let pos = self.window.head() + self.window.len();
+        self.window.push(ts, ());
+        self.min.expire(self.window.head());
+        self.min.push_at(value, pos);
+    }
+
+    /// Returns the minimum value in the window
+    ///
+    /// # Returns
+    ///
+    /// None if the window is empty, otherwise returns the minimum value
+    pub fn get(&self) -> Option<T> {
+        self.min.get()
+    }
+
+    /// Resets the window
+    pub fn reset(&mut self) {
+        self.window.reset();
+        self.min.reset();
+    }
+}
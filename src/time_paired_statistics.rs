@@ -0,0 +1,392 @@
+use num_traits::Float;
+use ordered_float::PrimitiveFloat;
+
+use crate::PairedStatistics;
+
+/// A structure that computes various statistics over a trailing time span of paired values.
+///
+/// `TimePairedStatistics<T>` is the time-windowed counterpart of [`PairedStatistics`].
+/// It holds every pair whose timestamp lies within `(now - duration, now]`, where `now`
+/// is the timestamp passed to the latest [`next`](Self::next) call.
+///
+/// Timestamps are plain `u64` values in any unit and should be non-decreasing; a timestamp
+/// older than the latest one is treated as the latest. The internal buffers grow and shrink
+/// with the number of pairs in the window, and an optional upper bound evicts the oldest
+/// pair once reached.
+///
+/// # Examples
+///
+/// ```
+/// use ta_statistics::TimePairedStatistics;
+/// use assert_approx_eq::assert_approx_eq;
+///
+/// let mut stats = TimePairedStatistics::new(3);
+/// let mut results = vec![];
+/// let inputs = [(0, (2.0, 1.0)), (1, (4.0, 3.0)), (2, (6.0, 2.0)), (3, (8.0, 5.0)), (4, (10.0, 7.0))];
+/// inputs.iter().for_each(|&(ts, pair)| {
+///     stats.next(ts, pair).cov().map(|v| results.push(v));
+/// });
+///
+/// let expected: [f64; 5] = [0.0, 1.0, 0.6667, 1.3333, 3.3333];
+/// for (i, e) in expected.iter().enumerate() {
+///     assert_approx_eq!(e, results[i], 0.0001);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimePairedStatistics<T> {
+    stats: PairedStatistics<T>,
+}
+
+impl<T> TimePairedStatistics<T>
+where
+    T: Default + Clone + Float + PrimitiveFloat,
+{
+    /// Creates a new `TimePairedStatistics` instance with the specified duration.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - The length of the window in timestamp units
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The `TimePairedStatistics` instance
+    ///
+    /// # Panics
+    ///
+    /// Panics if `duration` is zero
+    pub fn new(duration: u64) -> Self {
+        Self {
+            stats: PairedStatistics::with_time_window(duration, None),
+        }
+    }
+
+    /// Creates a new `TimePairedStatistics` instance holding at most `max_capacity` pairs.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - The length of the window in timestamp units
+    /// * `max_capacity` - The maximum number of pairs held, the oldest pair is evicted beyond it
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The `TimePairedStatistics` instance
+    ///
+    /// # Panics
+    ///
+    /// Panics if `duration` or `max_capacity` is zero
+    pub fn with_max_capacity(duration: u64, max_capacity: usize) -> Self {
+        Self {
+            stats: PairedStatistics::with_time_window(duration, Some(max_capacity)),
+        }
+    }
+
+    /// Enables the Spearman and Kendall rank correlations
+    ///
+    /// Ranks are disabled by default, as keeping both series in order-statistic trees costs
+    /// O(log n) per update instead of O(1), and reading a rank correlation O(n log n).
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The `TimePairedStatistics` instance with ranks enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::TimePairedStatistics;
+    ///
+    /// let mut stats = TimePairedStatistics::new(3).with_ranks();
+    /// let inputs = [(0, (2.0, 1.0)), (1, (4.0, 3.0)), (2, (6.0, 2.0)), (3, (8.0, 5.0))];
+    /// inputs.iter().for_each(|&(ts, pair)| {
+    ///     stats.next(ts, pair);
+    /// });
+    ///
+    /// assert_eq!(stats.kendall(), Some(1.0 / 3.0));
+    /// assert_eq!(TimePairedStatistics::<f64>::new(3).spearman(), None);
+    /// ```
+    pub fn with_ranks(mut self) -> Self {
+        self.stats.enable_ranks();
+        self
+    }
+
+    /// Returns the length of the window
+    ///
+    /// # Returns
+    ///
+    /// * `u64` - The length of the window in timestamp units
+    pub fn duration(&self) -> u64 {
+        self.stats.duration().unwrap_or_default()
+    }
+
+    /// Returns the number of pairs currently in the window
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of pairs in the window
+    pub fn count(&self) -> usize {
+        self.stats.count()
+    }
+
+    /// Resets the statistics
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub fn reset(&mut self) -> &mut Self {
+        self.stats.reset();
+        self
+    }
+
+    /// Recomputes the paired statistics, could be called to avoid
+    /// prolonged compounding of floating rounding errors
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub fn recompute(&mut self) -> &mut Self {
+        self.stats.recompute();
+        self
+    }
+
+    /// Returns the Delta Degrees of Freedom
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - The Delta Degrees of Freedom
+    pub const fn ddof(&self) -> bool {
+        self.stats.ddof()
+    }
+
+    /// Sets the Delta Degrees of Freedom
+    ///
+    /// # Arguments
+    ///
+    /// * `ddof` - The Delta Degrees of Freedom
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub const fn set_ddof(&mut self, ddof: bool) -> &mut Self {
+        self.stats.set_ddof(ddof);
+        self
+    }
+
+    /// Updates the paired statistical calculations with a new timestamped value pair
+    ///
+    /// Evicts every pair whose timestamp is at or before `ts - duration`, then adds
+    /// the new pair to the window.
+    ///
+    /// # Arguments
+    ///
+    /// * `ts` - The timestamp of the pair, expected to be non-decreasing across calls. A
+    ///   timestamp older than the latest one is treated as the latest timestamp
+    /// * `value` - A tuple containing the paired values (x, y) to incorporate into calculations
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The updated statistics object for method chaining
+    pub fn next(&mut self, ts: u64, value: (T, T)) -> &mut Self {
+        self.stats.next_at(ts, value);
+        self
+    }
+
+    /// Returns the covariance of the paired values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The covariance of the values in the window, or `None` if the window is empty
+    pub fn cov(&self) -> Option<T> {
+        self.stats.cov()
+    }
+
+    /// Returns the correlation coefficient (Pearson's r) of paired values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The correlation coefficient in the window, or `None` if either series has zero variance
+    pub fn corr(&self) -> Option<T> {
+        self.stats.corr()
+    }
+
+    /// Returns the Spearman rank correlation of paired values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Spearman correlation in the window, or `None` if either series is
+    ///   constant or ranks are not enabled with [`with_ranks`](Self::with_ranks)
+    pub fn spearman(&self) -> Option<T> {
+        self.stats.spearman()
+    }
+
+    /// Returns the Kendall rank correlation (tau-b) of paired values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Kendall tau-b in the window, or `None` if either series is
+    ///   constant or ranks are not enabled with [`with_ranks`](Self::with_ranks)
+    pub fn kendall(&self) -> Option<T> {
+        self.stats.kendall()
+    }
+
+    /// Returns the beta coefficient of the paired values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The beta coefficient in the window, or `None` if the benchmark has zero variance
+    pub fn beta(&self) -> Option<T> {
+        self.stats.beta()
+    }
+
+    /// Returns the coefficient of determination (R²) of the regression in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The R² in the window, or `None` if either series has zero variance
+    pub fn r2(&self) -> Option<T> {
+        self.stats.r2()
+    }
+
+    /// Returns the standard error of the regression in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The standard error of the residuals, or `None` if the window holds fewer than three pairs
+    pub fn regression_stderr(&self) -> Option<T> {
+        self.stats.regression_stderr()
+    }
+
+    /// Returns the standard error of the beta coefficient
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The standard error of beta, or `None` if the window holds fewer than three pairs
+    pub fn beta_stderr(&self) -> Option<T> {
+        self.stats.beta_stderr()
+    }
+
+    /// Returns the t-statistic of the beta coefficient
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The t-statistic of beta, or `None` if the window holds fewer than three pairs or the fit is exact
+    pub fn beta_tstat(&self) -> Option<T> {
+        self.stats.beta_tstat()
+    }
+
+    /// Returns the alpha (intercept) of the regression of the paired values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The alpha in the window, or `None` if the benchmark has zero variance
+    pub fn alpha(&self) -> Option<T> {
+        self.stats.alpha()
+    }
+
+    /// Returns the standard error of the alpha coefficient
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The standard error of alpha, or `None` if the window holds fewer than three pairs
+    pub fn alpha_stderr(&self) -> Option<T> {
+        self.stats.alpha_stderr()
+    }
+
+    /// Returns the t-statistic of the alpha coefficient
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The t-statistic of alpha, or `None` if the window holds fewer than three pairs or the fit is exact
+    pub fn alpha_tstat(&self) -> Option<T> {
+        self.stats.alpha_tstat()
+    }
+
+    /// Returns the residual of the latest pair from the regression line
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The latest residual, or `None` if the benchmark has zero variance
+    pub fn residual(&self) -> Option<T> {
+        self.stats.residual()
+    }
+
+    /// Returns the z-score of the latest residual
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The z-score of the latest residual, or `None` if the window holds fewer than three pairs or the fit is exact
+    pub fn residual_zscore(&self) -> Option<T> {
+        self.stats.residual_zscore()
+    }
+
+    /// Returns Jensen's alpha of the paired values in the window for a risk-free rate
+    ///
+    /// # Arguments
+    ///
+    /// * `rf` - The risk-free rate per period
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - Jensen's alpha in the window, or `None` if the benchmark has zero variance
+    pub fn jensens_alpha(&self, rf: T) -> Option<T> {
+        self.stats.jensens_alpha(rf)
+    }
+
+    /// Returns the tracking error of the paired values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The tracking error in the window, or `None` if the window is empty
+    pub fn tracking_error(&self) -> Option<T> {
+        self.stats.tracking_error()
+    }
+
+    /// Returns the information ratio of the paired values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The information ratio in the window, or `None` if the active returns are constant
+    pub fn information_ratio(&self) -> Option<T> {
+        self.stats.information_ratio()
+    }
+
+    /// Returns the upside capture ratio of `x` relative to the benchmark `y` in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The upside capture, or `None` if the benchmark did not rise in the window
+    pub fn upside_capture(&self) -> Option<T> {
+        self.stats.upside_capture()
+    }
+
+    /// Returns the downside capture ratio of `x` relative to the benchmark `y` in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The downside capture, or `None` if the benchmark did not fall in the window
+    pub fn downside_capture(&self) -> Option<T> {
+        self.stats.downside_capture()
+    }
+
+    /// Returns the capture ratio, the upside capture over the downside capture
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The capture ratio, or `None` if either capture is undefined or the
+    ///   downside capture is zero
+    pub fn capture_ratio(&self) -> Option<T> {
+        self.stats.capture_ratio()
+    }
+
+    /// Returns the Engle-Granger cointegration test of the paired values in the window
+    ///
+    /// # Arguments
+    ///
+    /// * `lags` - The number of lagged residual changes in the Dickey-Fuller regression
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(T, T)>` - A tuple containing (statistic, p-value), or `None` if the window
+    ///   holds too few pairs for the lags or the benchmark has zero variance
+    pub fn engle_granger(&self, lags: usize) -> Option<(T, T)> {
+        self.stats.engle_granger(lags)
+    }
+}
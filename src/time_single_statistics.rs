@@ -0,0 +1,750 @@
+use num_traits::Float;
+use ordered_float::PrimitiveFloat;
+
+use core::iter::Sum;
+
+use crate::{QuantileMethod, RankMethod, SingleStatistics, VarMethod};
+
+/// A structure that computes various statistics over a trailing time span of values.
+///
+/// `TimeSingleStatistics<T>` is the time-windowed counterpart of [`SingleStatistics`].
+/// Instead of holding a fixed number of values, it holds every value whose timestamp lies
+/// within `(now - duration, now]`, where `now` is the timestamp passed to the latest
+/// [`next`](Self::next) call. This suits tick data and irregular bars, where windows such
+/// as "the last 5 minutes" contain a varying number of observations.
+///
+/// Timestamps are plain `u64` values in any unit (e.g. milliseconds since epoch) and should
+/// be non-decreasing; a timestamp older than the latest one is treated as the latest. The
+/// internal buffers grow and shrink as observations enter and age out of the window. An
+/// optional upper bound caps the number of values held, in which case the oldest value is
+/// evicted once the bound is reached.
+///
+/// Statistics are reported as soon as the window holds at least one value.
+///
+/// # Examples
+///
+/// ```
+/// use ta_statistics::TimeSingleStatistics;
+///
+/// // A window of 60 time units
+/// let mut stats = TimeSingleStatistics::new(60);
+/// let inputs = [(0, 10.0), (20, 11.0), (40, 12.0), (60, 13.0), (70, 9.0), (130, 14.0)];
+/// let mut results = vec![];
+///
+/// inputs.iter().for_each(|&(ts, value)| {
+///     stats.next(ts, value).mean().map(|v| results.push(v));
+/// });
+///
+/// assert_eq!(&results, &[10.0, 10.5, 11.0, 12.0, 11.25, 14.0]);
+/// ```
+#[derive(Debug)]
+pub struct TimeSingleStatistics<T> {
+    stats: SingleStatistics<T>,
+}
+
+impl<T> TimeSingleStatistics<T>
+where
+    T: Default + Clone + Float + PrimitiveFloat,
+{
+    /// Creates a new `TimeSingleStatistics` instance with the specified duration.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - The length of the window in timestamp units
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics object
+    ///
+    /// # Panics
+    ///
+    /// Panics if `duration` is zero
+    pub fn new(duration: u64) -> Self {
+        Self {
+            stats: SingleStatistics::with_time_window(duration, None),
+        }
+    }
+
+    /// Creates a new `TimeSingleStatistics` instance holding at most `max_capacity` values.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - The length of the window in timestamp units
+    /// * `max_capacity` - The maximum number of values held, the oldest value is evicted beyond it
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics object
+    ///
+    /// # Panics
+    ///
+    /// Panics if `duration` or `max_capacity` is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::TimeSingleStatistics;
+    ///
+    /// let mut stats = TimeSingleStatistics::with_max_capacity(60, 2);
+    /// let inputs = [(0, 10.0), (20, 11.0), (40, 12.0), (60, 13.0), (70, 9.0), (130, 14.0)];
+    /// let mut results = vec![];
+    ///
+    /// inputs.iter().for_each(|&(ts, value)| {
+    ///     stats.next(ts, value).mean().map(|v| results.push(v));
+    /// });
+    ///
+    /// assert_eq!(&results, &[10.0, 10.5, 11.5, 12.5, 11.0, 14.0]);
+    /// ```
+    pub fn with_max_capacity(duration: u64, max_capacity: usize) -> Self {
+        Self {
+            stats: SingleStatistics::with_time_window(duration, Some(max_capacity)),
+        }
+    }
+
+    /// Enables the drawdown durations, recovery time, Ulcer Index and pain index
+    ///
+    /// The drawdown profile is disabled by default, as it is rebuilt from the running peak
+    /// of the window in an O(n) pass per update.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The `TimeSingleStatistics` instance with the drawdown profile enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::TimeSingleStatistics;
+    ///
+    /// let mut stats = TimeSingleStatistics::new(4).with_drawdown_profile();
+    /// let inputs = [(0, 100.0), (1, 110.0), (2, 105.0), (3, 95.0), (4, 112.0)];
+    /// inputs.iter().for_each(|&(ts, value)| {
+    ///     stats.next(ts, value);
+    /// });
+    ///
+    /// assert_eq!(stats.drawdown_duration(), Some(0));
+    /// assert_eq!(stats.max_drawdown_duration(), Some(2));
+    /// assert_eq!(TimeSingleStatistics::<f64>::new(4).pain_index(), None);
+    /// ```
+    pub fn with_drawdown_profile(mut self) -> Self {
+        self.stats.enable_drawdown_profile();
+        self
+    }
+
+    /// Returns the length of the window
+    ///
+    /// # Returns
+    ///
+    /// * `u64` - The length of the window in timestamp units
+    pub fn duration(&self) -> u64 {
+        self.stats.duration().unwrap_or_default()
+    }
+
+    /// Returns the number of values currently in the window
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of values in the window
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::TimeSingleStatistics;
+    ///
+    /// let mut stats = TimeSingleStatistics::new(60);
+    /// let inputs = [(0, 10.0), (20, 11.0), (40, 12.0), (60, 13.0), (70, 9.0), (130, 14.0)];
+    /// let mut results = vec![];
+    ///
+    /// inputs.iter().for_each(|&(ts, value)| {
+    ///     results.push(stats.next(ts, value).count());
+    /// });
+    ///
+    /// assert_eq!(&results, &[1, 2, 3, 3, 4, 1]);
+    /// ```
+    pub fn count(&self) -> usize {
+        self.stats.count()
+    }
+
+    /// Resets the statistics
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub fn reset(&mut self) -> &mut Self {
+        self.stats.reset();
+        self
+    }
+
+    /// Recomputes the statistics, could be called to avoid
+    /// prolonged compounding of floating rounding errors
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub fn recompute(&mut self) -> &mut Self {
+        self.stats.recompute();
+        self
+    }
+
+    /// Returns the Delta Degrees of Freedom
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - The Delta Degrees of Freedom
+    pub const fn ddof(&self) -> bool {
+        self.stats.ddof()
+    }
+
+    /// Sets the Delta Degrees of Freedom
+    ///
+    /// # Arguments
+    ///
+    /// * `ddof` - The Delta Degrees of Freedom
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub const fn set_ddof(&mut self, ddof: bool) -> &mut Self {
+        self.stats.set_ddof(ddof);
+        self
+    }
+
+    /// Returns the method used to estimate the median, quantiles and IQR
+    ///
+    /// # Returns
+    ///
+    /// * `QuantileMethod` - The quantile method
+    pub const fn quantile_method(&self) -> QuantileMethod {
+        self.stats.quantile_method()
+    }
+
+    /// Sets the method used to estimate the median, quantiles and IQR
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The quantile method
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub const fn set_quantile_method(&mut self, method: QuantileMethod) -> &mut Self {
+        self.stats.set_quantile_method(method);
+        self
+    }
+
+    /// Returns the method used to estimate the Value-at-Risk and Expected Shortfall
+    ///
+    /// # Returns
+    ///
+    /// * `VarMethod` - The Value-at-Risk method
+    pub const fn var_method(&self) -> VarMethod {
+        self.stats.var_method()
+    }
+
+    /// Sets the method used to estimate the Value-at-Risk and Expected Shortfall
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The Value-at-Risk method
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub const fn set_var_method(&mut self, method: VarMethod) -> &mut Self {
+        self.stats.set_var_method(method);
+        self
+    }
+
+    /// Updates the statistical calculations with a new timestamped value
+    ///
+    /// Evicts every value whose timestamp is at or before `ts - duration`, then adds
+    /// the new value to the window.
+    ///
+    /// # Arguments
+    ///
+    /// * `ts` - The timestamp of the value, expected to be non-decreasing across calls. A
+    ///   timestamp older than the latest one is treated as the latest timestamp
+    /// * `value` - The new value to be added to the time series
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub fn next(&mut self, ts: u64, value: T) -> &mut Self {
+        self.stats.next_at(ts, value);
+        self
+    }
+
+    /// Returns the sum of all values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The sum of all values in the window, or `None` if the window is empty
+    pub fn sum(&self) -> Option<T> {
+        self.stats.sum()
+    }
+
+    /// Returns the sum of squares of all values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The sum of squares in the window, or `None` if the window is empty
+    pub fn sum_sq(&self) -> Option<T> {
+        self.stats.sum_sq()
+    }
+
+    /// Returns the mean of all values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The arithmetic mean of values in the window, or `None` if the window is empty
+    pub fn mean(&self) -> Option<T> {
+        self.stats.mean()
+    }
+
+    /// Returns the mean of squares of all values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The mean of squared values in the window, or `None` if the window is empty
+    pub fn mean_sq(&self) -> Option<T> {
+        self.stats.mean_sq()
+    }
+
+    /// Returns the mode (most frequent value) of the window
+    ///
+    /// In case of ties, returns the smallest value.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The mode of values in the window, or `None` if the window is empty
+    pub fn mode(&self) -> Option<T> {
+        self.stats.mode()
+    }
+
+    /// Returns the median of the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The median of values in the window, or `None` if the window is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::TimeSingleStatistics;
+    ///
+    /// let mut stats = TimeSingleStatistics::new(60);
+    /// let inputs = [(0, 10.0), (20, 11.0), (40, 12.0), (60, 13.0), (70, 9.0), (130, 14.0)];
+    /// let mut results = vec![];
+    ///
+    /// inputs.iter().for_each(|&(ts, value)| {
+    ///     stats.next(ts, value).median().map(|v| results.push(v));
+    /// });
+    ///
+    /// assert_eq!(&results, &[10.0, 10.5, 11.0, 12.0, 11.5, 14.0]);
+    /// ```
+    pub fn median(&self) -> Option<T> {
+        self.stats.median()
+    }
+
+    /// Returns the minimum value in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The minimum value in the window, or `None` if the window is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::TimeSingleStatistics;
+    ///
+    /// let mut stats = TimeSingleStatistics::new(60);
+    /// let inputs = [(0, 10.0), (20, 11.0), (40, 12.0), (60, 13.0), (70, 9.0), (130, 14.0)];
+    /// let mut results = vec![];
+    ///
+    /// inputs.iter().for_each(|&(ts, value)| {
+    ///     stats.next(ts, value).min().map(|v| results.push(v));
+    /// });
+    ///
+    /// assert_eq!(&results, &[10.0, 10.0, 10.0, 11.0, 9.0, 14.0]);
+    /// ```
+    pub fn min(&self) -> Option<T> {
+        self.stats.min()
+    }
+
+    /// Returns the maximum value in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The maximum value in the window, or `None` if the window is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::TimeSingleStatistics;
+    ///
+    /// let mut stats = TimeSingleStatistics::new(60);
+    /// let inputs = [(0, 10.0), (20, 11.0), (40, 12.0), (60, 13.0), (70, 9.0), (130, 14.0)];
+    /// let mut results = vec![];
+    ///
+    /// inputs.iter().for_each(|&(ts, value)| {
+    ///     stats.next(ts, value).max().map(|v| results.push(v));
+    /// });
+    ///
+    /// assert_eq!(&results, &[10.0, 11.0, 12.0, 13.0, 13.0, 14.0]);
+    /// ```
+    pub fn max(&self) -> Option<T> {
+        self.stats.max()
+    }
+
+    /// Returns the number of bars since the maximum value of the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The bars since the maximum, or `None` if the window is empty
+    pub fn bars_since_max(&self) -> Option<usize> {
+        self.stats.bars_since_max()
+    }
+
+    /// Returns the number of bars since the minimum value of the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The bars since the minimum, or `None` if the window is empty
+    pub fn bars_since_min(&self) -> Option<usize> {
+        self.stats.bars_since_min()
+    }
+
+    /// Returns the index of the maximum value within the window, 0 for the oldest value
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The index of the maximum, or `None` if the window is empty
+    pub fn argmax(&self) -> Option<usize> {
+        self.stats.argmax()
+    }
+
+    /// Returns the index of the minimum value within the window, 0 for the oldest value
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The index of the minimum, or `None` if the window is empty
+    pub fn argmin(&self) -> Option<usize> {
+        self.stats.argmin()
+    }
+
+    /// Returns the mean absolute deviation of values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The mean absolute deviation of values, or `None` if the window is empty
+    pub fn mean_absolute_deviation(&self) -> Option<T>
+    where
+        T: Sum,
+    {
+        self.stats.mean_absolute_deviation()
+    }
+
+    /// Returns the median absolute deviation of values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The median absolute deviation of values, or `None` if the window is empty
+    pub fn median_absolute_deviation(&self) -> Option<T> {
+        self.stats.median_absolute_deviation()
+    }
+
+    /// Returns the variance of values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The variance of values in the window, or `None` if the window is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::TimeSingleStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = TimeSingleStatistics::new(60);
+    /// let inputs = [(0, 10.0), (20, 11.0), (40, 12.0), (60, 13.0), (70, 9.0), (130, 14.0)];
+    /// let mut results = vec![];
+    ///
+    /// inputs.iter().for_each(|&(ts, value)| {
+    ///     stats.next(ts, value).variance().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 6] = [0.0, 0.25, 0.6667, 0.6667, 2.1875, 0.0];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn variance(&self) -> Option<T> {
+        self.stats.variance()
+    }
+
+    /// Returns the standard deviation of values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The standard deviation of values in the window, or `None` if the window is empty
+    pub fn stddev(&self) -> Option<T> {
+        self.stats.stddev()
+    }
+
+    /// Returns the z-score of the most recent value
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The z-score of the most recent value, or `None` if the standard deviation is zero
+    pub fn zscore(&self) -> Option<T> {
+        self.stats.zscore()
+    }
+
+    /// Returns the skewness of values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The skewness of values in the window, or `None` if the variance is zero
+    pub fn skew(&self) -> Option<T> {
+        self.stats.skew()
+    }
+
+    /// Returns the excess kurtosis of values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The kurtosis of values in the window, or `None` if fewer than 4 values are held
+    pub fn kurt(&self) -> Option<T> {
+        self.stats.kurt()
+    }
+
+    /// Returns the lower partial moment `Σ max(t - x, 0)^k / n` of the given order
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - The order of the moment, between 0 and 4
+    /// * `threshold` - The threshold the moment is measured from
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The lower partial moment, or `None` if the window is empty or the order is out of range
+    pub fn lower_partial_moment(&self, order: usize, threshold: T) -> Option<T> {
+        self.stats.lower_partial_moment(order, threshold)
+    }
+
+    /// Returns the upper partial moment `Σ max(x - t, 0)^k / n` of the given order
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - The order of the moment, between 0 and 4
+    /// * `threshold` - The threshold the moment is measured from
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The upper partial moment, or `None` if the window is empty or the order is out of range
+    pub fn upper_partial_moment(&self, order: usize, threshold: T) -> Option<T> {
+        self.stats.upper_partial_moment(order, threshold)
+    }
+
+    /// Returns the downside semivariance, the variance contributed by values below the mean
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The downside semivariance, or `None` if the window is empty
+    pub fn downside_semivariance(&self) -> Option<T> {
+        self.stats.downside_semivariance()
+    }
+
+    /// Returns the upside semivariance, the variance contributed by values above the mean
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The upside semivariance, or `None` if the window is empty
+    pub fn upside_semivariance(&self) -> Option<T> {
+        self.stats.upside_semivariance()
+    }
+
+    /// Returns the current drawdown from the peak of the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The current drawdown from peak, or `None` if the window is empty
+    pub fn drawdown(&self) -> Option<T> {
+        self.stats.drawdown()
+    }
+
+    /// Returns the maximum peak-to-trough drawdown in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The maximum drawdown, or `None` if the window is empty
+    pub fn max_drawdown(&self) -> Option<T> {
+        self.stats.max_drawdown()
+    }
+
+    /// Returns the number of bars since the running peak of the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The bars since the running peak, or `None` if the window is empty
+    ///   or the drawdown profile is not enabled
+    pub fn drawdown_duration(&self) -> Option<usize> {
+        self.stats.drawdown_duration()
+    }
+
+    /// Returns the longest run of bars below the running peak of the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The longest drawdown duration, or `None` if the window is empty
+    ///   or the drawdown profile is not enabled
+    pub fn max_drawdown_duration(&self) -> Option<usize> {
+        self.stats.max_drawdown_duration()
+    }
+
+    /// Returns the bars from the trough of the deepest drawdown until its peak is regained
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The recovery time, or `None` if the window is empty, has not
+    ///   recovered or the drawdown profile is not enabled
+    pub fn recovery_time(&self) -> Option<usize> {
+        self.stats.recovery_time()
+    }
+
+    /// Returns the Ulcer Index, the root mean square of the drawdowns from the running peak
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Ulcer Index, or `None` if the window is empty
+    ///   or the drawdown profile is not enabled
+    pub fn ulcer_index(&self) -> Option<T> {
+        self.stats.ulcer_index()
+    }
+
+    /// Returns the pain index, the mean of the drawdowns from the running peak
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The pain index, or `None` if the window is empty
+    ///   or the drawdown profile is not enabled
+    pub fn pain_index(&self) -> Option<T> {
+        self.stats.pain_index()
+    }
+
+    /// Returns the quantile of the values in the window
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - The quantile to calculate
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The quantile, or `None` if the window is empty
+    pub fn quantile(&self, q: f64) -> Option<T> {
+        self.stats.quantile(q)
+    }
+
+    /// Returns the quantile of the values in the window estimated with the given method
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - The quantile to calculate
+    /// * `method` - The quantile method
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The quantile, or `None` if the window is empty
+    pub fn quantile_with(&self, q: f64, method: QuantileMethod) -> Option<T> {
+        self.stats.quantile_with(q, method)
+    }
+
+    /// Returns the percentile rank of the most recent value within the window
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - How to rank values tied with other values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The percentile rank, or `None` if the window is empty
+    pub fn percentile_rank(&self, method: RankMethod) -> Option<T> {
+        self.stats.percentile_rank(method)
+    }
+
+    /// Returns the percentile rank of any value within the window
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to rank
+    /// * `method` - How to rank values tied with other values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The percentile rank, or `None` if the window is empty
+    pub fn rank_of(&self, value: T, method: RankMethod) -> Option<T> {
+        self.stats.rank_of(value, method)
+    }
+
+    /// Returns the interquartile range of the values in the window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The interquartile range, or `None` if the window is empty
+    pub fn iqr(&self) -> Option<T> {
+        self.stats.iqr()
+    }
+
+    /// Returns the Value-at-Risk of the values in the window, as a positive loss
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The tail probability, between 0 and 1
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Value-at-Risk, or `None` if the window is empty or `alpha` is outside `(0, 1)`
+    pub fn var(&self, alpha: f64) -> Option<T> {
+        self.stats.var(alpha)
+    }
+
+    /// Returns the Value-at-Risk of the values in the window estimated with the given method
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The tail probability, between 0 and 1
+    /// * `method` - The Value-at-Risk method
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Value-at-Risk, or `None` if the window is empty or `alpha` is outside `(0, 1)`
+    pub fn var_with(&self, alpha: f64, method: VarMethod) -> Option<T> {
+        self.stats.var_with(alpha, method)
+    }
+
+    /// Returns the Expected Shortfall of the values in the window, as a positive loss
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The tail probability, between 0 and 1
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Expected Shortfall, or `None` if the window is empty or `alpha` is outside `(0, 1)`
+    pub fn cvar(&self, alpha: f64) -> Option<T> {
+        self.stats.cvar(alpha)
+    }
+
+    /// Returns the Expected Shortfall of the values in the window estimated with the given method
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The tail probability, between 0 and 1
+    /// * `method` - The Value-at-Risk method
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The Expected Shortfall, or `None` if the window is empty or `alpha` is outside `(0, 1)`
+    pub fn cvar_with(&self, alpha: f64, method: VarMethod) -> Option<T> {
+        self.stats.cvar_with(alpha, method)
+    }
+}
@@ -0,0 +1,113 @@
+use num_traits::Float;
+
+use crate::rolling::RollingWma;
+
+/// # Triangular Moving Average (TMA)
+///
+/// Averages a rolling window with triangular weights that rise linearly towards the middle of
+/// the window and fall off towards both ends, e.g. `1, 2, 3, 2, 1` for a period of 5. The
+/// result is a smoother, more lagging average than the simple or linearly weighted mean.
+///
+/// Computed as a simple moving average of a simple moving average, with periods
+/// `(period + 1) / 2` and `period / 2 + 1`, so each update is O(1) regardless of the period.
+///
+/// # Examples
+///
+/// ```
+/// use ta_statistics::TriangularMovingAverage;
+/// use assert_approx_eq::assert_approx_eq;
+///
+/// let mut tma = TriangularMovingAverage::new(5);
+/// let mut results = vec![];
+/// let inputs = [10.0, 10.5, 11.2, 10.9, 11.5, 11.9, 12.3, 12.1, 11.8, 12.5];
+/// inputs.iter().for_each(|i| {
+///     tma.next(*i).get().map(|v| results.push(v));
+/// });
+///
+/// let expected: [f64; 6] = [10.8778, 11.1667, 11.5111, 11.8111, 12.0222, 12.1];
+/// for (i, e) in expected.iter().enumerate() {
+///     assert_approx_eq!(e, results[i], 0.0001);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TriangularMovingAverage<T> {
+    /// Inner simple moving average of the inputs
+    inner: RollingWma<T>,
+    /// Outer simple moving average of the inner averages
+    outer: RollingWma<T>,
+}
+
+impl<T: Default + Clone + Float> TriangularMovingAverage<T> {
+    /// Creates a new `TriangularMovingAverage` instance with the specified period
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The size of the rolling window
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The moving average object
+    pub fn new(period: usize) -> Self {
+        Self {
+            inner: RollingWma::new(period.div_ceil(2)),
+            outer: RollingWma::new(period / 2 + 1),
+        }
+    }
+
+    /// Returns the period of the moving average
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The period of the moving average
+    pub const fn period(&self) -> usize {
+        self.inner.period() + self.outer.period() - 1
+    }
+
+    /// Resets the moving average
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The moving average object
+    pub fn reset(&mut self) -> &mut Self {
+        self.inner.reset();
+        self.outer.reset();
+        self
+    }
+
+    /// Recomputes the moving average, could be called to avoid
+    /// prolonged compounding of floating rounding errors
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The moving average object
+    pub fn recompute(&mut self) -> &mut Self {
+        self.inner.recompute();
+        self.outer.recompute();
+        self
+    }
+
+    /// Updates the moving average with a new value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The new value to be added to the rolling window
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The moving average object
+    pub fn next(&mut self, value: T) -> &mut Self {
+        if let Some(mean) = self.inner.next(value).mean() {
+            self.outer.next(mean);
+        }
+        self
+    }
+
+    /// Returns the triangular moving average
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The triangular moving average, or `None` if the window is not full
+    pub fn get(&self) -> Option<T> {
+        self.outer.mean()
+    }
+}
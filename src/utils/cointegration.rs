@@ -0,0 +1,173 @@
+use alloc::vec::Vec;
+use num_traits::Float;
+
+use super::normal_cdf;
+
+/// Returns the augmented Dickey-Fuller t-statistic of a series without constant or trend
+///
+/// Regresses `Δe_t` on `e_(t-1)` and the `lags` previous changes `Δe_(t-1) .. Δe_(t-lags)`
+/// by ordinary least squares and divides the coefficient of `e_(t-1)` by its standard
+/// error. The normal equations are solved with Gauss-Jordan elimination and partial
+/// pivoting, which is O(n · lags²).
+///
+/// # Arguments
+///
+/// * `series` - The series to test for a unit root, oldest first
+/// * `lags` - The number of lagged changes in the regression
+///
+/// # Returns
+///
+/// * `Option<T>` - The t-statistic, or `None` if the series has too few values for the
+///   regression to have residual degrees of freedom or the regressors are collinear
+pub fn adf_tstat<T: Float>(series: &[T], lags: usize) -> Option<T> {
+    let m = lags + 1;
+    let nobs = series.len().checked_sub(lags + 1)?;
+    if nobs <= m {
+        return None;
+    }
+
+    let diffs: Vec<T> = series.windows(2).map(|w| w[1] - w[0]).collect();
+    let diffs = diffs.as_slice();
+    let regressors = move |t: usize| {
+        core::iter::once(series[t - 1]).chain((1..=lags).map(move |j| diffs[t - 1 - j]))
+    };
+
+    // Normal equations [X'X | I] and X'Δe
+    let mut a = vec![vec![T::zero(); 2 * m]; m];
+    let mut b = vec![T::zero(); m];
+    for t in lags + 1..series.len() {
+        let target = diffs[t - 1];
+        for (r, xr) in regressors(t).enumerate() {
+            b[r] = b[r] + xr * target;
+            for (c, xc) in regressors(t).enumerate() {
+                a[r][c] = a[r][c] + xr * xc;
+            }
+        }
+    }
+    for (r, row) in a.iter_mut().enumerate() {
+        row[m + r] = T::one();
+    }
+
+    for col in 0..m {
+        let pivot = (col..m)
+            .max_by(|&i, &j| {
+                a[i][col]
+                    .abs()
+                    .partial_cmp(&a[j][col].abs())
+                    .unwrap_or(core::cmp::Ordering::Equal)
+            })
+            .unwrap_or(col);
+        a.swap(col, pivot);
+
+        let d = a[col][col];
+        if d.is_zero() {
+            return None;
+        }
+        a[col].iter_mut().for_each(|v| *v = *v / d);
+
+        let pivot_row = a[col].clone();
+        for (_, row) in a.iter_mut().enumerate().filter(|&(r, _)| r != col) {
+            let f = row[col];
+            for (v, &p) in row.iter_mut().zip(&pivot_row) {
+                *v = *v - f * p;
+            }
+        }
+    }
+
+    let coeffs: Vec<T> = (0..m)
+        .map(|r| (0..m).fold(T::zero(), |acc, c| acc + a[r][m + c] * b[c]))
+        .collect();
+    let sse = (lags + 1..series.len()).fold(T::zero(), |acc, t| {
+        let fitted = regressors(t)
+            .zip(&coeffs)
+            .fold(T::zero(), |acc, (x, &k)| acc + x * k);
+        let e = diffs[t - 1] - fitted;
+        acc + e * e
+    });
+
+    let s2 = sse / T::from(nobs - m)?;
+    let se = (s2 * a[0][m]).sqrt();
+    if se.is_nan() || se <= T::zero() {
+        return None;
+    }
+    Some(coeffs[0] / se)
+}
+
+/// Returns the approximate p-value of the Engle-Granger cointegration test of two series
+///
+/// Evaluates the response surface of MacKinnon (1994) for the Dickey-Fuller statistic of
+/// the residuals of a regression with a constant on one other series, `Φ(Σ c_i · τ^i)`,
+/// with the coefficients of the small or large p-value region of the statistic.
+///
+/// # Arguments
+///
+/// * `stat` - The augmented Dickey-Fuller t-statistic of the residuals
+///
+/// # Returns
+///
+/// * `f64` - The p-value of the null hypothesis of no cointegration
+pub fn engle_granger_pvalue(stat: f64) -> f64 {
+    const TAU_MIN: f64 = -18.86;
+    const TAU_MAX: f64 = 0.92;
+    const TAU_STAR: f64 = -2.62;
+    const SMALL_P: [f64; 3] = [2.92, 1.5012, 0.039_796];
+    const LARGE_P: [f64; 4] = [2.1945, 0.646_95, -0.291_98, -0.042_377];
+
+    if stat > TAU_MAX {
+        return 1.0;
+    }
+    if stat < TAU_MIN {
+        return 0.0;
+    }
+
+    let coeffs: &[f64] = if stat <= TAU_STAR { &SMALL_P } else { &LARGE_P };
+    normal_cdf(coeffs.iter().rev().fold(0.0, |acc, &c| acc * stat + c))
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn adf_tstat_matches_dickey_fuller_regression() {
+        let series = [
+            0.5, -0.3, 0.8, 0.1, -0.6, 0.4, 0.9, -0.2, -0.7, 0.3, 0.6, -0.4,
+        ];
+
+        // Without lags the statistic is Σ e·Δe / Σ e² over its standard error
+        let (mut see, mut sed) = (0.0, 0.0);
+        for w in series.windows(2) {
+            see += w[0] * w[0];
+            sed += w[0] * (w[1] - w[0]);
+        }
+        let gamma = sed / see;
+        let sse: f64 = series
+            .windows(2)
+            .map(|w| (w[1] - w[0] - gamma * w[0]).powi(2))
+            .sum();
+        let se = (sse / (series.len() - 2) as f64 / see).sqrt();
+        assert_approx_eq!(adf_tstat(&series, 0).unwrap(), gamma / se, 1e-12);
+
+        // OLS fit of the lagged regression in exact rational arithmetic
+        assert_approx_eq!(
+            adf_tstat(&series, 2).unwrap(),
+            -3.809_744_414_388_268,
+            1e-12
+        );
+
+        assert_eq!(adf_tstat(&series[..4], 2), None);
+    }
+
+    #[test]
+    fn engle_granger_pvalue_matches_critical_values() {
+        // MacKinnon (2010) critical values of two series with a constant
+        for (stat, p) in [(-3.8962, 0.01), (-3.3377, 0.05), (-3.0462, 0.10)] {
+            assert_approx_eq!(engle_granger_pvalue(stat), p, 0.002);
+        }
+        assert_eq!(engle_granger_pvalue(1.0), 1.0);
+        assert_eq!(engle_granger_pvalue(-20.0), 0.0);
+    }
+}
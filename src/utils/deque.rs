@@ -83,6 +83,26 @@ where
         self.cap
     }
 
+    /// Changes the capacity of the deque, keeping the stored elements in order
+    ///
+    /// # Arguments
+    ///
+    /// * `cap` - The new capacity of the deque, must be at least the current length
+    pub fn resize(&mut self, cap: usize) {
+        assert!(cap > 0, "capacity must be > 0");
+        assert!(cap >= self.len, "capacity must be >= len");
+
+        let mut buf = vec![T::default(); cap].into_boxed_slice();
+        for (i, slot) in buf.iter_mut().take(self.len).enumerate() {
+            *slot = core::mem::take(&mut self.buf[(self.front + i) % self.cap]);
+        }
+
+        self.buf = buf;
+        self.cap = cap;
+        self.front = 0;
+        self.back = self.len % cap;
+    }
+
     /// Resets the deque, clearing all elements and resetting the indices
     #[inline]
     pub fn reset(&mut self) -> &mut Self {
@@ -489,6 +509,32 @@ mod tests {
         assert_eq!(deque.back(), None);
     }
 
+    #[test]
+    fn test_resize() {
+        let mut deque = Deque::new(3);
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.push_back(3);
+        deque.pop_front();
+        deque.push_back(4);
+
+        deque.resize(5);
+        assert_eq!(deque.capacity(), 5);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+
+        deque.push_back(5);
+        deque.push_back(6);
+        assert!(deque.is_full());
+        assert_eq!(deque.push_back(7), Some(2));
+
+        deque.pop_front();
+        deque.pop_front();
+        deque.resize(3);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), vec![5, 6, 7]);
+        assert_eq!(deque.push_back(8), Some(5));
+        assert_eq!(deque.back(), Some(&8));
+    }
+
     #[test]
     fn test_get_mut_modification() {
         let mut deque = Deque::new(3);
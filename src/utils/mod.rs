@@ -1,3 +1,6 @@
+/// Smallest capacity that growable buffers shrink down to
+pub const MIN_GROWABLE_CAPACITY: usize = 8;
+
 mod deque;
 pub use deque::Deque;
 
@@ -6,3 +9,12 @@ pub use monotonic_queue::{Max, Min, MonotonicQueue};
 
 mod rb_tree;
 pub use rb_tree::RbTree;
+
+mod time_window;
+pub use time_window::TimeWindow;
+
+mod normal;
+pub use normal::{normal_cdf, normal_inverse_cdf, normal_pdf};
+
+mod cointegration;
+pub use cointegration::{adf_tstat, engle_granger_pvalue};
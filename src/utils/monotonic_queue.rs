@@ -1,6 +1,6 @@
 use core::marker::PhantomData;
 
-use super::Deque;
+use super::{Deque, MIN_GROWABLE_CAPACITY};
 
 /// Trait for defining order policies for monotonic queue
 ///
@@ -17,24 +17,28 @@ pub trait OrderPolicy<T> {
 }
 
 /// Order policy for minimum
+///
+/// Equal values replace older ones, so the front holds the most recent occurrence of the extreme.
 #[derive(Debug, Clone)]
 pub struct Min;
 
 /// Order policy for maximum
+///
+/// Equal values replace older ones, so the front holds the most recent occurrence of the extreme.
 #[derive(Debug, Clone)]
 pub struct Max;
 
 impl<T: PartialOrd> OrderPolicy<T> for Min {
     #[inline]
     fn should_remove(existing: &T, new: &T) -> bool {
-        existing > new
+        existing >= new
     }
 }
 
 impl<T: PartialOrd> OrderPolicy<T> for Max {
     #[inline]
     fn should_remove(existing: &T, new: &T) -> bool {
-        existing < new
+        existing <= new
     }
 }
 
@@ -148,6 +152,66 @@ where
         self.element_count += 1;
     }
 
+    /// Pushes a new value with an explicit position, growing the queue when it is full
+    ///
+    /// Used for windows that are not bounded by a fixed element count, where expired
+    /// entries are removed explicitly with [`expire`](Self::expire).
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to push into the queue
+    /// * `pos` - The position of the value, must be non-decreasing across calls
+    #[inline]
+    pub fn push_at(&mut self, value: T, pos: usize) {
+        self.maintain_monotonic_property(value);
+        if self.deque.is_full() {
+            self.deque.resize(self.capacity() * 2);
+        }
+        self.deque.push_back((value, pos));
+        self.element_count = pos + 1;
+    }
+
+    /// Removes all entries positioned before `start`, shrinking the queue when it is sparse
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The position of the oldest element still inside the window
+    #[inline]
+    pub fn expire(&mut self, start: usize) {
+        while let Some(&(_, pos)) = self.deque.front() {
+            if pos < start {
+                self.deque.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        let cap = self.capacity();
+        if cap > MIN_GROWABLE_CAPACITY && self.deque.len() <= cap / 4 {
+            self.deque.resize(Ord::max(cap / 2, MIN_GROWABLE_CAPACITY));
+        }
+    }
+
+    /// Pushes a new value into an expanding window that never evicts
+    ///
+    /// Only the front entry can ever be observed once nothing expires, so the queue
+    /// keeps a single entry and replaces it when the new value dominates.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to push into the queue
+    #[inline]
+    pub fn push_cumulative(&mut self, value: T) {
+        if self
+            .front()
+            .is_none_or(|existing| O::should_remove(&existing, &value))
+        {
+            self.deque.reset();
+            self.deque.push_back((value, self.element_count));
+        }
+        self.element_count += 1;
+    }
+
     /// Returns the front element of the queue
     ///
     /// # Returns
@@ -158,6 +222,29 @@ where
         self.deque.front().map(|&(value, _)| value)
     }
 
+    /// Returns the number of elements pushed since the front element
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The age of the front element, 0 if it is the latest element, or
+    ///   `None` if the queue is empty
+    #[inline]
+    pub fn front_age(&self) -> Option<usize> {
+        self.deque
+            .front()
+            .map(|&(_, pos)| self.element_count - 1 - pos)
+    }
+
+    /// Returns the number of elements pushed into the queue
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of elements pushed, or one past the latest explicit position
+    #[inline]
+    pub const fn element_count(&self) -> usize {
+        self.element_count
+    }
+
     /// Resets the queue to its initial state
     ///
     /// # Returns
@@ -196,8 +283,32 @@ mod tests {
         mq.push(5);
         assert_eq!(mq.front(), Some(5));
 
+        assert_eq!(mq.front_age(), Some(0)); // Should pick the newer equal value
+
         mq.push(4);
-        assert_eq!(mq.front(), Some(4)); // Should pick the newer equal value
+        assert_eq!(mq.front(), Some(4));
+    }
+
+    #[test]
+    fn test_monotonic_queue_front_age() {
+        let input = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3];
+        let window = 4;
+        let mut mq_min = MonotonicQueue::<_, Min>::new(window);
+        let mut mq_max = MonotonicQueue::<_, Max>::new(window);
+
+        for (i, &val) in input.iter().enumerate() {
+            mq_min.push(val);
+            mq_max.push(val);
+
+            let start = (i + 1).saturating_sub(window);
+            let window = &input[start..=i];
+            let newest = |target: i32| window.iter().rev().position(|&v| v == target);
+            assert_eq!(mq_min.front_age(), newest(*window.iter().min().unwrap()));
+            assert_eq!(mq_max.front_age(), newest(*window.iter().max().unwrap()));
+        }
+
+        mq_max.reset();
+        assert_eq!(mq_max.front_age(), None);
     }
 
     #[test]
@@ -431,6 +542,37 @@ mod tests {
         assert_eq!(mq.front(), None);
     }
 
+    #[test]
+    fn test_push_at_expire() {
+        let input = [5, 1, 3, 8, 6, 2, 9, 4, 7, 0, 3, 5];
+        let mut mq_min = MonotonicQueue::<_, Min>::new(1);
+        let mut mq_max = MonotonicQueue::<_, Max>::new(1);
+
+        // Variable window: each step keeps the last `1 + pos % 4` elements
+        let mut start = 0;
+        for (pos, &val) in input.iter().enumerate() {
+            start = Ord::max(start, (pos + 1).saturating_sub(1 + pos % 4));
+            mq_min.expire(start);
+            mq_max.expire(start);
+            mq_min.push_at(val, pos);
+            mq_max.push_at(val, pos);
+
+            let window = &input[start..=pos];
+            assert_eq!(mq_min.front(), window.iter().min().copied());
+            assert_eq!(mq_max.front(), window.iter().max().copied());
+        }
+
+        let descending: Vec<_> = (0..100).rev().collect();
+        let mut mq_max = MonotonicQueue::<_, Max>::new(1);
+        for (pos, &val) in descending.iter().enumerate() {
+            mq_max.push_at(val, pos);
+        }
+        assert!(mq_max.capacity() >= 100);
+        mq_max.expire(99);
+        assert_eq!(mq_max.front(), Some(0));
+        assert!(mq_max.capacity() < 100);
+    }
+
     #[test]
     fn test_oscillating_values() {
         let oscillating = [10, 2, 8, 1, 9, 3, 7, 0];
@@ -460,4 +602,23 @@ mod tests {
 
         assert_eq!(max_results, vec![10, 9, 9, 9, 9]);
     }
+
+    #[test]
+    fn test_push_cumulative() {
+        let input = [5, 2, 9, 1, 7, 3];
+        let mut mq_min = MonotonicQueue::<_, Min>::new(1);
+        let mut mq_max = MonotonicQueue::<_, Max>::new(1);
+        let mut min_results = vec![];
+        let mut max_results = vec![];
+
+        for &val in &input {
+            mq_min.push_cumulative(val);
+            mq_max.push_cumulative(val);
+            min_results.push(mq_min.front().unwrap());
+            max_results.push(mq_max.front().unwrap());
+        }
+
+        assert_eq!(min_results, vec![5, 2, 2, 1, 1, 1]);
+        assert_eq!(max_results, vec![5, 5, 9, 9, 9, 9]);
+    }
 }
@@ -0,0 +1,157 @@
+use num_traits::Float;
+
+/// `1 / sqrt(2π)`
+const FRAC_1_SQRT_2PI: f64 = 0.398_942_280_401_432_7;
+
+/// Returns the density of the standard normal distribution
+///
+/// # Arguments
+///
+/// * `x` - The point to evaluate
+///
+/// # Returns
+///
+/// * `f64` - The density `φ(x)`
+pub fn normal_pdf(x: f64) -> f64 {
+    FRAC_1_SQRT_2PI * Float::exp(-0.5 * x * x)
+}
+
+/// Returns the cumulative distribution function of the standard normal distribution
+///
+/// Uses the Chebyshev approximation of the complementary error function from Numerical
+/// Recipes with a fractional error below 1.2e-7 everywhere.
+///
+/// # Arguments
+///
+/// * `x` - The point to evaluate
+///
+/// # Returns
+///
+/// * `f64` - The probability `Φ(x)`
+pub fn normal_cdf(x: f64) -> f64 {
+    const C: [f64; 10] = [
+        -1.265_512_23,
+        1.000_023_68,
+        0.374_091_96,
+        0.096_784_18,
+        -0.186_288_06,
+        0.278_868_07,
+        -1.135_203_98,
+        1.488_515_87,
+        -0.822_152_23,
+        0.170_872_77,
+    ];
+
+    let z = Float::abs(x) * core::f64::consts::FRAC_1_SQRT_2;
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = C.iter().rev().fold(0.0, |acc, &c| acc * t + c);
+    let tail = 0.5 * t * Float::exp(-z * z + poly);
+    if x >= 0.0 { 1.0 - tail } else { tail }
+}
+
+/// Returns the quantile function of the standard normal distribution
+///
+/// Uses the rational approximation of Peter Acklam with a relative error below 1.15e-9
+/// over the whole domain.
+///
+/// # Arguments
+///
+/// * `p` - The probability, in `(0, 1)`
+///
+/// # Returns
+///
+/// * `Option<f64>` - The quantile `Φ⁻¹(p)`, or `None` if `p` is outside `(0, 1)`
+pub fn normal_inverse_cdf(p: f64) -> Option<f64> {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.024_25;
+
+    if !(p > 0.0 && p < 1.0) {
+        return None;
+    }
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    let x = if p < P_LOW {
+        tail(Float::sqrt(-2.0 * Float::ln(p)))
+    } else if p > 1.0 - P_LOW {
+        -tail(Float::sqrt(-2.0 * Float::ln(1.0 - p)))
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    };
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn normal_inverse_cdf_works() {
+        // scipy.stats.norm.ppf
+        let expected = [
+            (0.001, -3.090_232_306_167_813),
+            (0.01, -2.326_347_874_040_841),
+            (0.05, -1.644_853_626_951_472_2),
+            (0.5, 0.0),
+            (0.975, 1.959_963_984_540_054),
+        ];
+        for (p, z) in expected {
+            assert_approx_eq!(normal_inverse_cdf(p).unwrap(), z, 1e-8);
+        }
+        assert_eq!(normal_inverse_cdf(0.0), None);
+        assert_eq!(normal_inverse_cdf(1.0), None);
+        assert_approx_eq!(normal_pdf(0.0), 0.398_942_280_401_432_7, 1e-15);
+    }
+
+    #[test]
+    fn normal_cdf_works() {
+        // scipy.stats.norm.cdf
+        let expected = [
+            (-3.0, 0.001_349_898_031_630_094_5),
+            (-1.0, 0.158_655_253_931_457_05),
+            (0.0, 0.5),
+            (1.959_963_984_540_054, 0.975),
+            (4.0, 0.999_968_328_758_166_9),
+        ];
+        for (x, p) in expected {
+            assert_approx_eq!(normal_cdf(x), p, 1e-7);
+        }
+    }
+}
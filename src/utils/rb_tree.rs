@@ -2,6 +2,9 @@ use alloc::{boxed::Box, vec::Vec};
 use core::mem::MaybeUninit;
 use ordered_float::{FloatCore, OrderedFloat};
 
+use super::MIN_GROWABLE_CAPACITY;
+use crate::QuantileMethod;
+
 /// Red-Black tree node colors used to maintain tree balance properties.
 ///
 /// Red-Black trees maintain balance by ensuring:
@@ -42,6 +45,103 @@ struct Node<T> {
     /// Total count of elements in this node's subtree (including duplicates)
     /// Used for efficient quantile and order statistic calculations
     subtree_count: usize,
+
+    /// Total weight of the duplicate values stored in this node
+    weight: T,
+
+    /// Total weight of elements in this node's subtree
+    /// Used for efficient weighted quantile calculations
+    subtree_weight: T,
+
+    /// Sum of elements in this node's subtree (including duplicates)
+    /// Used for efficient absolute deviation calculations
+    subtree_sum: T,
+}
+
+/// Count, mean and central moment sums `Σ(x - mean)^k` for `k = 2..=4` of a set of elements
+///
+/// Sets are combined with the pairwise update of Pébay (2008), so the sums stay accurate
+/// when the spread of the values is small against their level, e.g. prices.
+#[derive(Debug, Clone, Copy)]
+struct CentralMoments<T> {
+    count: T,
+    mean: T,
+    m2: T,
+    m3: T,
+    m4: T,
+}
+
+impl<T: FloatCore> CentralMoments<T> {
+    /// Returns the moments of the empty set
+    fn empty() -> Self {
+        Self {
+            count: T::zero(),
+            mean: T::zero(),
+            m2: T::zero(),
+            m3: T::zero(),
+            m4: T::zero(),
+        }
+    }
+
+    /// Returns the moments of `count` duplicates of a value
+    fn of(value: T, count: u32) -> Self {
+        Self {
+            count: T::from(count).unwrap_or_else(T::nan),
+            mean: value,
+            ..Self::empty()
+        }
+    }
+
+    /// Returns the moments of the union of two disjoint sets
+    fn merge(self, other: Self) -> Self {
+        if other.count.is_zero() {
+            return self;
+        }
+        if self.count.is_zero() {
+            return other;
+        }
+
+        let (na, nb) = (self.count, other.count);
+        let n = na + nb;
+        let delta = other.mean - self.mean;
+        let (d_n, d2) = (delta / n, delta * delta);
+        let nab = na * nb;
+        let _3 = T::from(3).unwrap_or_else(T::nan);
+        let _4 = _3 + T::one();
+        let _6 = _3 + _3;
+
+        Self {
+            count: n,
+            mean: self.mean + d_n * nb,
+            m2: self.m2 + other.m2 + d2 * nab / n,
+            m3: self.m3
+                + other.m3
+                + d2 * d_n * nab * (na - nb) / n
+                + _3 * d_n * (na * other.m2 - nb * self.m2),
+            m4: self.m4
+                + other.m4
+                + d2 * d_n * d_n * nab * (na * na - nab + nb * nb) / n
+                + _6 * d_n * d_n * (na * na * other.m2 + nb * nb * self.m2)
+                + _4 * d_n * (na * other.m3 - nb * self.m3),
+        }
+    }
+
+    /// Returns `Σ (sign · (x - t))^k` over the set
+    ///
+    /// Expands binomially around the mean of the set, so every term is of the order of
+    /// the distance of the values from the threshold rather than of their level.
+    fn partial_sum(&self, order: usize, threshold: T, sign: T) -> Option<T> {
+        let central = [self.count, T::zero(), self.m2, self.m3, self.m4];
+        let shift = sign * (self.mean - threshold);
+        let mut binomial = T::one();
+        let mut total = T::zero();
+        for (j, &sum_j) in central.iter().enumerate().take(order + 1) {
+            let sign_j = if j % 2 == 1 { sign } else { T::one() };
+            total = total + binomial * sign_j * sum_j * shift.powi((order - j) as i32);
+            binomial = binomial * T::from(order - j)? / T::from(j + 1)?;
+        }
+        Some(total)
+    }
 }
 
 /// A Red-Black tree implementation optimized for quantile calculations and sliding windows.
@@ -51,8 +151,11 @@ struct Node<T> {
 /// consistent performance in real-time applications.
 ///
 /// Key features:
-/// - Fixed capacity with no dynamic allocation after initialization
+/// - Fixed capacity with no dynamic allocation after initialization, unless explicitly resized
 /// - Efficient quantile/percentile calculations via subtree counts
+/// - Weighted quantiles via subtree weights, unweighted values carry a weight of one
+/// - Mean and median absolute deviations via subtree sums and order-statistic selection
+/// - Partial moments around any threshold via optional subtree central moments
 /// - Duplicate value support (multiset behavior)
 /// - Memory reuse through internal free list management
 #[derive(Debug)]
@@ -78,7 +181,7 @@ pub struct RbTree<T> {
     total_count: usize,
 
     /// Maximum number of nodes this tree can hold
-    /// Fixed at initialization, determines size of nodes and free_list arrays
+    /// Set at initialization or by `resize`, determines size of nodes and free_list arrays
     capacity: usize,
 
     /// Index of the root node in the nodes array
@@ -86,8 +189,42 @@ pub struct RbTree<T> {
     root: usize,
 
     /// Sentinel value representing null/empty nodes
-    /// Typically set to capacity (one past the last valid index)
+    /// Set to `usize::MAX` so it stays valid when the tree is resized
     nil: usize,
+
+    /// Central moments of the subtree of each node, indexed like `nodes`
+    /// Only allocated and updated for trees created with `with_moments`
+    moments: Option<Box<[CentralMoments<T>]>>,
+}
+
+impl<T: Clone> Clone for RbTree<T> {
+    /// Copies the nodes slot by slot, leaving the slots of the free list uninitialized
+    fn clone(&self) -> Self {
+        let mut live = vec![true; self.capacity];
+        for &idx in &self.free_list[..self.free_top] {
+            live[idx] = false;
+        }
+
+        let mut nodes = Box::new_uninit_slice(self.capacity);
+        for ((slot, node), live) in nodes.iter_mut().zip(self.nodes.iter()).zip(live) {
+            if live {
+                // Slots outside the free list hold initialized nodes
+                slot.write(unsafe { node.assume_init_ref() }.clone());
+            }
+        }
+
+        Self {
+            nodes,
+            free_list: self.free_list.clone(),
+            free_top: self.free_top,
+            len: self.len,
+            total_count: self.total_count,
+            capacity: self.capacity,
+            root: self.root,
+            nil: self.nil,
+            moments: self.moments.clone(),
+        }
+    }
 }
 
 #[allow(dead_code)]
@@ -109,11 +246,25 @@ impl<T: FloatCore + Copy> RbTree<T> {
             len: 0,
             total_count: 0,
             capacity,
-            root: capacity,
-            nil: capacity,
+            root: usize::MAX,
+            nil: usize::MAX,
+            moments: None,
         }
     }
 
+    /// Creates a tree that also keeps the central moments of every subtree, which
+    /// partial moments around arbitrary thresholds are computed from
+    pub fn with_moments(capacity: usize) -> Self {
+        let mut tree = Self::new(capacity);
+        tree.moments = Some(vec![CentralMoments::empty(); capacity].into_boxed_slice());
+        tree
+    }
+
+    #[inline]
+    pub const fn has_moments(&self) -> bool {
+        self.moments.is_some()
+    }
+
     #[inline]
     pub const fn len(&self) -> usize {
         self.len
@@ -140,21 +291,32 @@ impl<T: FloatCore + Copy> RbTree<T> {
     }
 
     pub fn insert(&mut self, value: T) -> Option<usize> {
+        self.insert_with(value, 1, T::one())
+    }
+
+    /// Inserts a value carrying the given weight
+    pub fn insert_weighted(&mut self, value: T, weight: T) -> Option<usize> {
+        self.insert_with(value, 1, weight)
+    }
+
+    /// Inserts `count` duplicates of a value carrying a total of `weight`
+    fn insert_with(&mut self, value: T, count: u32, weight: T) -> Option<usize> {
         let ordered_value = OrderedFloat(value);
 
         if let Some(existing_idx) = self.find_node(value) {
-            self.increment_count(existing_idx);
+            self.increment_count(existing_idx, count, weight);
             return Some(existing_idx);
         }
 
         let new_idx = self.allocate_node()?;
         let parent_idx = self.find_insertion_parent(ordered_value);
 
-        self.create_node(new_idx, ordered_value, parent_idx);
+        let sum = value * T::from(count)?;
+        self.create_node(new_idx, ordered_value, parent_idx, count, weight, sum);
         self.link_to_parent(new_idx, parent_idx, ordered_value);
 
         self.len += 1;
-        self.total_count += 1;
+        self.total_count += count as usize;
 
         self.update_subtree_counts_to_root(new_idx);
         self.fix_insertion_violations(new_idx);
@@ -169,10 +331,15 @@ impl<T: FloatCore + Copy> RbTree<T> {
     }
 
     pub fn remove(&mut self, value: T) -> Option<T> {
+        self.remove_weighted(value, T::one())
+    }
+
+    /// Removes a value that was inserted with the given weight
+    pub fn remove_weighted(&mut self, value: T, weight: T) -> Option<T> {
         let node_idx = self.find_node(value)?;
 
         if self.node_at(node_idx).count > 1 {
-            self.decrement_count(node_idx);
+            self.decrement_count(node_idx, weight);
         } else {
             self.delete_node(node_idx);
             self.len -= 1;
@@ -222,6 +389,27 @@ impl<T: FloatCore + Copy> RbTree<T> {
         self.find_kth_element(target_index)
     }
 
+    /// Returns the quantile estimated with the given method
+    pub fn quantile_with(&self, q: f64, method: QuantileMethod) -> Option<T> {
+        method.quantile(q, self.total_count, |k| self.find_kth_element(k))
+    }
+
+    /// Returns the total weight of all stored values
+    pub fn total_weight(&self) -> T {
+        self.get_subtree_weight(self.root)
+    }
+
+    /// Returns the weighted quantile, the smallest value whose cumulative weight
+    /// reaches `q` times the total weight
+    pub fn weighted_quantile(&self, q: f64) -> Option<T> {
+        if self.total_count == 0 {
+            return None;
+        }
+
+        let target = T::from(q.clamp(0.0, 1.0))? * self.total_weight();
+        self.find_weighted_element(target)
+    }
+
     pub fn percentile(&self, p: f64) -> Option<T> {
         self.quantile(p / 100.0)
     }
@@ -230,6 +418,76 @@ impl<T: FloatCore + Copy> RbTree<T> {
         self.quantile(0.5)
     }
 
+    /// Inserts a value, doubling the capacity first if every node is in use
+    pub fn insert_growing(&mut self, value: T) -> Option<usize> {
+        if self.remaining_capacity() == 0 && self.find_node(value).is_none() {
+            self.resize(self.capacity * 2);
+        }
+        self.insert(value)
+    }
+
+    /// Removes a value, halving the capacity if the tree becomes sparse
+    pub fn remove_shrinking(&mut self, value: T) -> Option<T> {
+        let removed = self.remove(value)?;
+        if self.capacity > MIN_GROWABLE_CAPACITY && self.len <= self.capacity / 4 {
+            self.resize(Ord::max(self.capacity / 2, MIN_GROWABLE_CAPACITY));
+        }
+        Some(removed)
+    }
+
+    /// Changes the capacity of the tree, keeping all stored values
+    ///
+    /// Growing keeps the nodes in place, shrinking rebuilds the tree in O(n log n).
+    pub fn resize(&mut self, capacity: usize) {
+        assert!(capacity > 0, "Capacity must be greater than 0");
+        assert!(capacity >= self.len, "Capacity must be >= len");
+
+        if capacity < self.capacity {
+            let mut tree = if self.has_moments() {
+                Self::with_moments(capacity)
+            } else {
+                Self::new(capacity)
+            };
+            self.copy_into(self.root, &mut tree);
+            *self = tree;
+            return;
+        }
+
+        let mut nodes = Box::new_uninit_slice(capacity);
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            nodes[i] = core::mem::replace(node, MaybeUninit::uninit());
+        }
+
+        let mut free_list = Vec::with_capacity(capacity);
+        free_list.extend_from_slice(&self.free_list[..self.free_top]);
+        free_list.extend(self.capacity..capacity);
+        self.free_top = free_list.len();
+        free_list.resize(capacity, 0);
+
+        if let Some(moments) = &mut self.moments {
+            let mut grown = vec![CentralMoments::empty(); capacity];
+            grown[..moments.len()].copy_from_slice(moments);
+            *moments = grown.into_boxed_slice();
+        }
+
+        self.nodes = nodes;
+        self.free_list = free_list.into_boxed_slice();
+        self.capacity = capacity;
+    }
+
+    /// Inserts every value of the subtree rooted at `node_idx` into `tree`
+    fn copy_into(&self, node_idx: usize, tree: &mut Self) {
+        if node_idx == self.nil {
+            return;
+        }
+
+        let node = self.node_at(node_idx);
+        tree.insert_with(node.value.into_inner(), node.count, node.weight);
+
+        self.copy_into(node.left, tree);
+        self.copy_into(node.right, tree);
+    }
+
     pub fn reset(&mut self) {
         self.len = 0;
         self.total_count = 0;
@@ -269,15 +527,26 @@ impl<T: FloatCore + Copy> RbTree<T> {
         unsafe { self.nodes[idx].assume_init_mut() }
     }
 
-    const fn create_node(&mut self, idx: usize, value: OrderedFloat<T>, parent: usize) {
+    const fn create_node(
+        &mut self,
+        idx: usize,
+        value: OrderedFloat<T>,
+        parent: usize,
+        count: u32,
+        weight: T,
+        sum: T,
+    ) {
         let node = Node {
             value,
-            count: 1,
+            count,
             parent,
             left: self.nil,
             right: self.nil,
             color: Color::Red,
-            subtree_count: 1,
+            subtree_count: count as usize,
+            weight,
+            subtree_weight: weight,
+            subtree_sum: sum,
         };
         self.nodes[idx].write(node);
     }
@@ -349,26 +618,30 @@ impl<T: FloatCore + Copy> RbTree<T> {
         node
     }
 
-    const fn increment_count(&mut self, node_idx: usize) {
-        self.node_at_mut(node_idx).count += 1;
-        self.total_count += 1;
+    fn increment_count(&mut self, node_idx: usize, count: u32, weight: T) {
+        let node = self.node_at_mut(node_idx);
+        node.count += count;
+        node.weight = node.weight + weight;
+        self.total_count += count as usize;
         self.update_subtree_counts_to_root(node_idx);
     }
 
-    const fn decrement_count(&mut self, node_idx: usize) {
-        self.node_at_mut(node_idx).count -= 1;
+    fn decrement_count(&mut self, node_idx: usize, weight: T) {
+        let node = self.node_at_mut(node_idx);
+        node.count -= 1;
+        node.weight = node.weight - weight;
         self.total_count -= 1;
         self.update_subtree_counts_to_root(node_idx);
     }
 
-    const fn update_subtree_counts_to_root(&mut self, mut node: usize) {
+    fn update_subtree_counts_to_root(&mut self, mut node: usize) {
         while node != self.nil {
             self.recalculate_subtree_count(node);
             node = self.node_at(node).parent;
         }
     }
 
-    const fn recalculate_subtree_count(&mut self, node_idx: usize) {
+    fn recalculate_subtree_count(&mut self, node_idx: usize) {
         if node_idx == self.nil {
             return;
         }
@@ -381,7 +654,50 @@ impl<T: FloatCore + Copy> RbTree<T> {
             .saturating_add(left_count)
             .saturating_add(right_count);
 
-        self.node_at_mut(node_idx).subtree_count = total;
+        let weight =
+            node.weight + self.get_subtree_weight(node.left) + self.get_subtree_weight(node.right);
+
+        let value = node.value.into_inner();
+        let sum = value * T::from(node.count).unwrap_or_else(T::nan)
+            + self.get_subtree_sum(node.left)
+            + self.get_subtree_sum(node.right);
+
+        let moments = self.moments.is_some().then(|| {
+            self.get_subtree_moments(node.left)
+                .merge(CentralMoments::of(value, node.count))
+                .merge(self.get_subtree_moments(node.right))
+        });
+
+        let node = self.node_at_mut(node_idx);
+        node.subtree_count = total;
+        node.subtree_weight = weight;
+        node.subtree_sum = sum;
+        if let Some((all, moments)) = self.moments.as_mut().zip(moments) {
+            all[node_idx] = moments;
+        }
+    }
+
+    fn get_subtree_sum(&self, node_idx: usize) -> T {
+        if node_idx == self.nil {
+            T::zero()
+        } else {
+            self.node_at(node_idx).subtree_sum
+        }
+    }
+
+    fn get_subtree_moments(&self, node_idx: usize) -> CentralMoments<T> {
+        match &self.moments {
+            Some(moments) if node_idx != self.nil => moments[node_idx],
+            _ => CentralMoments::empty(),
+        }
+    }
+
+    fn get_subtree_weight(&self, node_idx: usize) -> T {
+        if node_idx == self.nil {
+            T::zero()
+        } else {
+            self.node_at(node_idx).subtree_weight
+        }
     }
 
     const fn get_subtree_count(&self, node_idx: usize) -> usize {
@@ -419,6 +735,56 @@ impl<T: FloatCore + Copy> RbTree<T> {
         }
     }
 
+    /// Returns the number of stored elements less than and equal to a value
+    ///
+    /// Descends from the root once, accumulating the counts of the left subtrees passed
+    /// on the way, so it is O(log n).
+    pub fn rank_counts(&self, value: T) -> (usize, usize) {
+        let value = OrderedFloat(value);
+        let mut current = self.root;
+        let mut less = 0;
+
+        while current != self.nil {
+            let node = self.node_at(current);
+            match value.cmp(&node.value) {
+                core::cmp::Ordering::Equal => {
+                    return (
+                        less + self.get_subtree_count(node.left),
+                        node.count as usize,
+                    );
+                }
+                core::cmp::Ordering::Less => current = node.left,
+                core::cmp::Ordering::Greater => {
+                    less += self.get_subtree_count(node.left) + node.count as usize;
+                    current = node.right;
+                }
+            }
+        }
+        (less, 0)
+    }
+
+    fn find_weighted_element(&self, target: T) -> Option<T> {
+        let mut current = self.root;
+        let mut remaining = target;
+
+        while current != self.nil {
+            let node = self.node_at(current);
+            let left_weight = self.get_subtree_weight(node.left);
+
+            if node.left != self.nil && remaining <= left_weight {
+                current = node.left;
+            } else if remaining <= left_weight + node.weight {
+                return Some(node.value.into_inner());
+            } else {
+                remaining = remaining - left_weight - node.weight;
+                current = node.right;
+            }
+        }
+
+        // Rounding can leave the target marginally above the total weight
+        self.max()
+    }
+
     #[inline]
     const fn get_color(&self, node_idx: usize) -> Color {
         if node_idx == self.nil {
@@ -445,7 +811,7 @@ impl<T: FloatCore + Copy> RbTree<T> {
         self.get_color(node_idx) == Color::Black
     }
 
-    const fn rotate_left(&mut self, x: usize) {
+    fn rotate_left(&mut self, x: usize) {
         if x == self.nil {
             return;
         }
@@ -479,7 +845,7 @@ impl<T: FloatCore + Copy> RbTree<T> {
         self.recalculate_subtree_count(y);
     }
 
-    const fn rotate_right(&mut self, y: usize) {
+    fn rotate_right(&mut self, y: usize) {
         if y == self.nil {
             return;
         }
@@ -790,75 +1156,179 @@ impl<T: FloatCore + Copy> RbTree<T> {
         }
     }
 
-    pub fn median_absolute_deviation(&self) -> Option<T> {
-        if self.total_count == 0 {
-            return None;
+    /// Returns the median absolute deviation from the median in O(log² n) without allocating
+    ///
+    /// The deviations of the values below the median, read from the median outwards, and
+    /// of the values above it form two sorted sequences. The k-th smallest deviation is
+    /// selected by binary searching how many of the k smallest come from each sequence,
+    /// where every probe is an O(log n) order-statistic lookup.
+    pub fn median_absolute_deviation(&self, method: QuantileMethod) -> Option<T> {
+        let n = self.total_count;
+        let median = self.quantile_with(0.5, method)?;
+        let (below, _) = self.prefix_below(median);
+
+        method.quantile(0.5, n, |k| self.kth_absolute_deviation(k, median, below))
+    }
+
+    /// Returns the k-th smallest absolute deviation from `center`
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The zero-based rank of the deviation
+    /// * `center` - The value deviations are measured from
+    /// * `below` - The number of elements less than `center`
+    fn kth_absolute_deviation(&self, k: usize, center: T, below: usize) -> Option<T> {
+        let above = self.total_count - below;
+        let lower = |i: usize| self.find_kth_element(below - 1 - i).map(|v| center - v);
+        let upper = |j: usize| self.find_kth_element(below + j).map(|v| v - center);
+
+        // Number of the k + 1 smallest deviations taken from below the center
+        let (mut lo, mut hi) = ((k + 1).saturating_sub(above), Ord::min(k + 1, below));
+        while lo < hi {
+            let i = lo + (hi - lo) / 2;
+            if lower(i)? < upper(k - i)? {
+                lo = i + 1;
+            } else {
+                hi = i;
+            }
         }
 
-        let median = self.median()?;
-        let mut deviations: Vec<OrderedFloat<T>> = Vec::with_capacity(self.total_count);
-
-        self.collect_absolute_deviations(self.root, median, &mut deviations);
-
-        deviations.sort_unstable();
-
-        let mid = deviations.len() / 2;
-        Some(deviations[mid].into_inner())
+        let (i, j) = (lo, k + 1 - lo);
+        match (i > 0, j > 0) {
+            (true, true) => Some(FloatCore::max(lower(i - 1)?, upper(j - 1)?)),
+            (true, false) => lower(i - 1),
+            (false, true) => upper(j - 1),
+            (false, false) => None,
+        }
     }
 
-    fn collect_absolute_deviations(
-        &self,
-        node_idx: usize,
-        median: T,
-        deviations: &mut Vec<OrderedFloat<T>>,
-    ) {
-        if node_idx == self.nil {
-            return;
+    /// Returns the mean absolute deviation from `mean` in O(log n)
+    ///
+    /// Splits the elements at `mean`, so the sum of absolute deviations follows from the
+    /// count and sum of the elements below it and the subtree sums of the whole tree.
+    pub fn mean_absolute_deviation(&self, mean: T) -> Option<T> {
+        if self.total_count == 0 {
+            return None;
         }
+        let (below, sum_below) = self.prefix_below(mean);
+        let above = self.total_count - below;
+        let sum_above = self.get_subtree_sum(self.root) - sum_below;
 
-        let node = self.node_at(node_idx);
-        let value = node.value.into_inner();
-        let deviation = (value - median).abs();
+        let total_deviation =
+            (mean * T::from(below)? - sum_below) + (sum_above - mean * T::from(above)?);
+        Some(FloatCore::max(total_deviation, T::zero()) / T::from(self.total_count)?)
+    }
 
-        let ordered_deviation = OrderedFloat(deviation);
+    /// Returns the count and the sum of the elements less than a value in O(log n)
+    fn prefix_below(&self, value: T) -> (usize, T) {
+        let value = OrderedFloat(value);
+        let mut current = self.root;
+        let (mut count, mut sum) = (0, T::zero());
 
-        for _ in 0..node.count {
-            deviations.push(ordered_deviation);
+        while current != self.nil {
+            let node = self.node_at(current);
+            if value <= node.value {
+                current = node.left;
+            } else {
+                count += self.get_subtree_count(node.left) + node.count as usize;
+                sum = sum
+                    + self.get_subtree_sum(node.left)
+                    + node.value.into_inner() * T::from(node.count).unwrap_or_else(T::nan);
+                current = node.right;
+            }
         }
-
-        self.collect_absolute_deviations(node.left, median, deviations);
-        self.collect_absolute_deviations(node.right, median, deviations);
+        (count, sum)
     }
 
-    pub fn mean_absolute_deviation(&self, mean: T) -> Option<T> {
-        if self.total_count == 0 {
-            return None;
+    /// Returns the central moments of the elements strictly below or strictly above a
+    /// value in O(log n), or `None` if the tree does not keep moments
+    fn moments_beyond(&self, value: T, above: bool) -> Option<CentralMoments<T>> {
+        self.moments.as_ref()?;
+        let value = OrderedFloat(value);
+        let mut current = self.root;
+        let mut moments = CentralMoments::empty();
+
+        while current != self.nil {
+            let node = self.node_at(current);
+            let (inner, outer) = if above {
+                (node.right, node.left)
+            } else {
+                (node.left, node.right)
+            };
+            if (above && value >= node.value) || (!above && value <= node.value) {
+                current = inner;
+            } else {
+                moments = moments
+                    .merge(self.get_subtree_moments(inner))
+                    .merge(CentralMoments::of(node.value.into_inner(), node.count));
+                current = outer;
+            }
         }
-        let total_deviation = self.sum_absolute_deviations_from_mean(self.root, mean);
-        let count_as_t = T::from(self.total_count)?;
-        Some(total_deviation / count_as_t)
+        Some(moments)
     }
 
-    /// Sum absolute deviations from mean - O(n)
-    fn sum_absolute_deviations_from_mean(&self, node_idx: usize, mean: T) -> T {
-        if node_idx == self.nil {
-            return T::zero();
+    /// Returns the sum of the `k` smallest elements, counting duplicates, in O(log n)
+    ///
+    /// Descends towards the k-th smallest element, adding the subtree sums of the left
+    /// subtrees and the nodes passed on the way.
+    pub fn smallest_sum(&self, k: usize) -> Option<T> {
+        if k > self.total_count {
+            return None;
         }
+        let mut current = self.root;
+        let (mut remaining, mut sum) = (k, T::zero());
 
-        let node = self.node_at(node_idx);
-        let value = node.value.into_inner();
-        let deviation = (value - mean).abs();
+        while current != self.nil && remaining > 0 {
+            let node = self.node_at(current);
+            let left_count = self.get_subtree_count(node.left);
+            if remaining <= left_count {
+                current = node.left;
+                continue;
+            }
 
-        let count_as_t = match T::from(node.count) {
-            Some(count) => count,
-            None => return T::zero(),
-        };
+            sum = sum + self.get_subtree_sum(node.left);
+            remaining -= left_count;
+            let taken = Ord::min(remaining, node.count as usize);
+            sum = sum + node.value.into_inner() * T::from(taken)?;
+            remaining -= taken;
+            current = node.right;
+        }
+        Some(sum)
+    }
+
+    /// Returns the lower partial moment `Σ max(t - x, 0)^k / n` in O(log n)
+    ///
+    /// The terms of the elements below the threshold are expanded from their central
+    /// moments, so any threshold, such as the current mean, can be queried. Requires a
+    /// tree created with `with_moments`.
+    pub fn lower_partial_moment(&self, order: usize, threshold: T) -> Option<T> {
+        let below = self.moments_beyond(threshold, false)?;
+        self.partial_moment(order, threshold, below, -T::one())
+    }
 
-        let node_deviation = deviation * count_as_t;
-        let left_deviation = self.sum_absolute_deviations_from_mean(node.left, mean);
-        let right_deviation = self.sum_absolute_deviations_from_mean(node.right, mean);
+    /// Returns the upper partial moment `Σ max(x - t, 0)^k / n` in O(log n)
+    ///
+    /// Elements equal to the threshold contribute nothing, so they are left out like in
+    /// the count of order 0. Requires a tree created with `with_moments`.
+    pub fn upper_partial_moment(&self, order: usize, threshold: T) -> Option<T> {
+        let above = self.moments_beyond(threshold, true)?;
+        self.partial_moment(order, threshold, above, T::one())
+    }
 
-        node_deviation + left_deviation + right_deviation
+    /// Normalizes `Σ (sign · (x - t))^k` over the given elements by the number of
+    /// elements in the tree
+    fn partial_moment(
+        &self,
+        order: usize,
+        threshold: T,
+        moments: CentralMoments<T>,
+        sign: T,
+    ) -> Option<T> {
+        if self.total_count == 0 || order > 4 {
+            return None;
+        }
+        let total = moments.partial_sum(order, threshold, sign)?;
+        Some(FloatCore::max(total, T::zero()) / T::from(self.total_count)?)
     }
 }
 
@@ -1184,6 +1654,272 @@ mod tests {
         assert_eq!(tree.len(), 1);
     }
 
+    #[test]
+    fn test_rbtree_resize() {
+        let mut tree = RbTree::<f64>::new(4);
+        for v in [5.0, 3.0, 8.0, 3.0, 1.0] {
+            tree.insert(v).unwrap();
+        }
+        assert_eq!(tree.remaining_capacity(), 0);
+        assert!(tree.insert(9.0).is_none());
+
+        tree.resize(8);
+        assert_eq!(tree.capacity(), 8);
+        for v in [9.0, 2.0, 7.0, 6.0] {
+            tree.insert(v).unwrap();
+        }
+        assert_eq!(tree.len(), 8);
+        assert_eq!(tree.total_count(), 9);
+        assert_eq!(tree.median(), Some(5.0));
+
+        for v in [9.0, 2.0, 7.0, 6.0, 8.0] {
+            tree.remove(v).unwrap();
+        }
+        tree.resize(3);
+        assert_eq!(tree.capacity(), 3);
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.total_count(), 4);
+        assert_eq!(tree.min(), Some(1.0));
+        assert_eq!(tree.quantile(0.5), Some(3.0));
+        assert_eq!(tree.max(), Some(5.0));
+
+        tree.remove(1.0).unwrap();
+        tree.insert(4.0).unwrap();
+        assert_eq!(tree.quantile(0.5), Some(3.0));
+        assert_eq!(tree.quantile(1.0), Some(5.0));
+    }
+
+    #[test]
+    fn test_rbtree_rank_counts() {
+        let mut tree = RbTree::new(10);
+        for v in [5.0, 1.0, 3.0, 3.0, 8.0, 3.0, 10.0] {
+            tree.insert(v);
+        }
+
+        assert_eq!(tree.rank_counts(0.0), (0, 0));
+        assert_eq!(tree.rank_counts(1.0), (0, 1));
+        assert_eq!(tree.rank_counts(3.0), (1, 3));
+        assert_eq!(tree.rank_counts(4.0), (4, 0));
+        assert_eq!(tree.rank_counts(10.0), (6, 1));
+        assert_eq!(tree.rank_counts(11.0), (7, 0));
+
+        tree.remove(3.0);
+        assert_eq!(tree.rank_counts(3.0), (1, 2));
+        assert_eq!(tree.rank_counts(5.0), (3, 1));
+    }
+
+    #[test]
+    fn test_rbtree_absolute_deviations() {
+        fn brute_median(sorted: &[f64]) -> f64 {
+            let n = sorted.len();
+            if n % 2 == 1 {
+                sorted[n / 2]
+            } else {
+                (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+            }
+        }
+
+        let inputs: Vec<f64> = (0..200)
+            .map(|i| ((i * 37 % 23) as f64 - 11.0) * 0.5 + (i % 7) as f64)
+            .collect();
+
+        for period in [1, 2, 5, 8, 13] {
+            let mut tree = RbTree::<f64>::new(period);
+            for (i, &value) in inputs.iter().enumerate() {
+                if i >= period {
+                    tree.remove(inputs[i - period]);
+                }
+                tree.insert(value);
+
+                let mut window = inputs[i + 1 - period.min(i + 1)..=i].to_vec();
+                window.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                let median = brute_median(&window);
+                let mut deviations: Vec<f64> = window.iter().map(|v| (v - median).abs()).collect();
+                deviations.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                let mean = window.iter().sum::<f64>() / window.len() as f64;
+                let mean_dev =
+                    window.iter().map(|v| (v - mean).abs()).sum::<f64>() / window.len() as f64;
+
+                assert_eq!(
+                    tree.median_absolute_deviation(QuantileMethod::Linear),
+                    Some(brute_median(&deviations)),
+                    "period {} step {}",
+                    period,
+                    i
+                );
+                assert!((tree.mean_absolute_deviation(mean).unwrap() - mean_dev).abs() < 1e-9);
+            }
+        }
+
+        let mut tree = RbTree::<f64>::new(4);
+        for v in [1.0, 2.0, 4.0, 10.0] {
+            tree.insert(v);
+        }
+        // Lower median 2 with deviations 0, 1, 2, 8 and higher median 4 with deviations 0, 2, 3, 6
+        assert_eq!(
+            tree.median_absolute_deviation(QuantileMethod::Lower),
+            Some(1.0)
+        );
+        assert_eq!(
+            tree.median_absolute_deviation(QuantileMethod::Higher),
+            Some(3.0)
+        );
+        assert_eq!(
+            RbTree::<f64>::new(1).median_absolute_deviation(QuantileMethod::Linear),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rbtree_smallest_sum() {
+        let mut tree = RbTree::<f64>::new(8);
+        for v in [5.0, -2.0, 3.0, -2.0, 8.0, 0.5, 3.0, -7.0] {
+            tree.insert(v);
+        }
+        let mut sorted = [5.0, -2.0, 3.0, -2.0, 8.0, 0.5, 3.0, -7.0];
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        for k in 0..=8 {
+            let expected: f64 = sorted[..k].iter().sum();
+            assert_eq!(tree.smallest_sum(k), Some(expected), "k {}", k);
+        }
+        assert_eq!(tree.smallest_sum(9), None);
+    }
+
+    #[test]
+    fn test_rbtree_partial_moments() {
+        let inputs = [
+            3.0, -1.0, 2.0, 2.0, -4.0, 0.5, 2.0, 7.0, -1.0, 3.0, 0.0, 2.5,
+        ];
+        let period = 5;
+        let mut tree = RbTree::<f64>::with_moments(period);
+
+        for (i, &v) in inputs.iter().enumerate() {
+            if i >= period {
+                tree.remove(inputs[i - period]);
+            }
+            tree.insert(v);
+            let window = &inputs[i.saturating_sub(period - 1)..=i];
+            let n = window.len() as f64;
+
+            for threshold in [-1.0, 0.0, 1.75, 2.0, 10.0] {
+                for k in 0..=4 {
+                    let lower: f64 = window
+                        .iter()
+                        .filter(|&&x| x < threshold)
+                        .map(|&x| (threshold - x).powi(k as i32))
+                        .sum();
+                    let upper: f64 = window
+                        .iter()
+                        .filter(|&&x| x > threshold)
+                        .map(|&x| (x - threshold).powi(k as i32))
+                        .sum();
+                    let l = tree.lower_partial_moment(k, threshold).unwrap();
+                    let u = tree.upper_partial_moment(k, threshold).unwrap();
+                    assert!(
+                        (l - lower / n).abs() < 1e-9,
+                        "lower k {} t {}",
+                        k,
+                        threshold
+                    );
+                    assert!(
+                        (u - upper / n).abs() < 1e-9,
+                        "upper k {} t {}",
+                        k,
+                        threshold
+                    );
+                }
+            }
+        }
+
+        assert_eq!(tree.lower_partial_moment(5, 0.0), None);
+        tree.reset();
+        assert_eq!(tree.upper_partial_moment(2, 0.0), None);
+    }
+
+    #[test]
+    fn test_rbtree_partial_moments_at_price_level() {
+        for base in [100.0, 10_000.0, 50_000.0] {
+            let period = 20;
+            let inputs: Vec<f64> = (0..60)
+                .map(|i| base + ((i * 37 % 23) as f64 - 11.0) * 0.013 + (i as f64) * 0.002)
+                .collect();
+            let mut tree = RbTree::<f64>::with_moments(8);
+
+            for (i, &v) in inputs.iter().enumerate() {
+                if i >= period {
+                    tree.remove_shrinking(inputs[i - period]);
+                }
+                tree.insert_growing(v);
+                let window = &inputs[i.saturating_sub(period - 1)..=i];
+                let n = window.len() as f64;
+                let mean = window.iter().sum::<f64>() / n;
+
+                for k in 0..=4 {
+                    let lower: f64 = window
+                        .iter()
+                        .filter(|&&x| x < mean)
+                        .map(|&x| (mean - x).powi(k as i32))
+                        .sum::<f64>()
+                        / n;
+                    let upper: f64 = window
+                        .iter()
+                        .filter(|&&x| x > mean)
+                        .map(|&x| (x - mean).powi(k as i32))
+                        .sum::<f64>()
+                        / n;
+                    let l = tree.lower_partial_moment(k, mean).unwrap();
+                    let u = tree.upper_partial_moment(k, mean).unwrap();
+                    assert!(
+                        (l - lower).abs() <= 1e-9 * lower.max(1e-6),
+                        "base {base} k {k}"
+                    );
+                    assert!(
+                        (u - upper).abs() <= 1e-9 * upper.max(1e-6),
+                        "base {base} k {k}"
+                    );
+                }
+            }
+        }
+
+        let mut tree = RbTree::<f64>::new(4);
+        tree.insert(1.0);
+        assert_eq!(tree.lower_partial_moment(2, 2.0), None);
+    }
+
+    #[test]
+    fn test_rbtree_weighted_quantile() {
+        let mut tree = RbTree::<f64>::new(4);
+        assert_eq!(tree.weighted_quantile(0.5), None);
+
+        tree.insert_weighted(1.0, 1.0);
+        tree.insert_weighted(2.0, 1.0);
+        tree.insert_weighted(3.0, 6.0);
+        assert_eq!(tree.total_weight(), 8.0);
+        assert_eq!(tree.weighted_quantile(0.0), Some(1.0));
+        assert_eq!(tree.weighted_quantile(0.1), Some(1.0));
+        assert_eq!(tree.weighted_quantile(0.25), Some(2.0));
+        assert_eq!(tree.weighted_quantile(0.5), Some(3.0));
+        assert_eq!(tree.weighted_quantile(1.0), Some(3.0));
+
+        tree.remove_weighted(3.0, 6.0);
+        assert_eq!(tree.total_weight(), 2.0);
+        assert_eq!(tree.weighted_quantile(0.5), Some(1.0));
+
+        tree.insert_weighted(2.0, 3.0);
+        assert_eq!(tree.total_count(), 3);
+        assert_eq!(tree.total_weight(), 5.0);
+        assert_eq!(tree.weighted_quantile(0.5), Some(2.0));
+
+        tree.resize(16);
+        tree.resize(8);
+        assert_eq!(tree.total_weight(), 5.0);
+
+        tree.remove_weighted(2.0, 1.0);
+        assert_eq!(tree.total_weight(), 4.0);
+        assert_eq!(tree.weighted_quantile(0.25), Some(1.0));
+        assert_eq!(tree.weighted_quantile(0.5), Some(2.0));
+    }
+
     #[test]
     fn test_rbtree_complex_removal_patterns() {
         let mut tree = RbTree::<f64>::new(10);
@@ -1670,4 +2406,29 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_clone_keeps_live_nodes() {
+        let mut tree = RbTree::<f64>::with_moments(6);
+        for v in [4.0, 1.0, 3.0, 1.0, 5.0, 2.0] {
+            tree.insert(v);
+        }
+        tree.remove(3.0);
+        tree.remove(1.0);
+
+        let mut copy = tree.clone();
+        assert_eq!(copy.len(), tree.len());
+        assert_eq!(copy.total_count(), tree.total_count());
+        assert_eq!(copy.rank_counts(4.0), tree.rank_counts(4.0));
+        assert_eq!(copy.median(), tree.median());
+        assert_eq!(
+            copy.lower_partial_moment(2, 3.0),
+            tree.lower_partial_moment(2, 3.0)
+        );
+
+        assert!(copy.insert(6.0).is_some());
+        assert!(copy.insert(7.0).is_some());
+        assert_eq!(copy.max(), Some(7.0));
+        assert_eq!(tree.max(), Some(5.0));
+    }
 }
@@ -0,0 +1,259 @@
+use super::{Deque, MIN_GROWABLE_CAPACITY};
+
+/// A growable window of timestamped values
+///
+/// Holds every observation whose timestamp lies within `(now - duration, now]`. The
+/// buffer doubles when full and halves when it becomes sparse, so memory follows the
+/// number of observations currently in the window. An optional upper bound caps the
+/// buffer, in which case the oldest observation is evicted once the bound is reached.
+///
+/// Every observation is assigned a sequence number in insertion order, so structures
+/// that reference positions (such as monotonic queues) can be expired in lockstep.
+#[derive(Debug, Clone)]
+pub struct TimeWindow<T> {
+    /// Length of the window in timestamp units
+    duration: u64,
+    /// Optional upper bound on the number of observations held
+    max_capacity: Option<usize>,
+    /// Buffer of (timestamp, value) pairs
+    buf: Deque<(u64, T)>,
+    /// Sequence number of the oldest observation in the window
+    head: usize,
+}
+
+impl<T> TimeWindow<T>
+where
+    T: Default + Clone,
+{
+    /// Creates a new `TimeWindow` instance with the specified duration.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - The length of the window in timestamp units
+    /// * `max_capacity` - The optional upper bound on the number of observations held
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The `TimeWindow` instance
+    pub fn new(duration: u64, max_capacity: Option<usize>) -> Self {
+        assert!(duration > 0, "duration must be > 0");
+        assert!(
+            max_capacity.is_none_or(|cap| cap > 0),
+            "max capacity must be > 0"
+        );
+        let cap = max_capacity.map_or(MIN_GROWABLE_CAPACITY, |max| {
+            Ord::min(max, MIN_GROWABLE_CAPACITY)
+        });
+        Self {
+            duration,
+            max_capacity,
+            buf: Deque::new(cap),
+            head: 0,
+        }
+    }
+
+    /// Returns the length of the window in timestamp units
+    ///
+    /// # Returns
+    ///
+    /// * `u64` - The length of the window
+    #[inline]
+    pub const fn duration(&self) -> u64 {
+        self.duration
+    }
+
+    /// Returns the number of observations in the window
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of observations in the window
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns true if the window is empty
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if the window is empty
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Returns the sequence number of the oldest observation in the window
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The sequence number of the oldest observation
+    #[inline]
+    pub const fn head(&self) -> usize {
+        self.head
+    }
+
+    /// Returns the timestamp of the most recent observation
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u64>` - The latest timestamp, or `None` if the window is empty
+    #[inline]
+    pub fn latest(&self) -> Option<u64> {
+        self.buf.back().map(|&(ts, _)| ts)
+    }
+
+    /// Pushes a new observation into the window
+    ///
+    /// Grows the buffer when it is full, or evicts the oldest observation if the
+    /// buffer has reached `max_capacity`. A timestamp older than the latest one is clamped
+    /// to the latest, so out-of-order input cannot break the ordering eviction relies on.
+    ///
+    /// # Arguments
+    ///
+    /// * `ts` - The timestamp of the observation
+    /// * `value` - The value of the observation
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The evicted value if the window was at its maximum capacity
+    pub fn push(&mut self, ts: u64, value: T) -> Option<T> {
+        let ts = self.latest().map_or(ts, |latest| Ord::max(latest, ts));
+
+        let cap = self.buf.capacity();
+        if self.buf.is_full() && self.max_capacity.is_none_or(|max| cap < max) {
+            let grown = cap * 2;
+            self.buf
+                .resize(self.max_capacity.map_or(grown, |max| Ord::min(max, grown)));
+        }
+
+        let evicted = self.buf.push_back((ts, value)).map(|(_, v)| v);
+        if evicted.is_some() {
+            self.head += 1;
+        }
+        evicted
+    }
+
+    /// Pops the oldest observation if it has aged out of the window
+    ///
+    /// Should be called repeatedly until it returns `None` before pushing an observation
+    /// with timestamp `now`.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current timestamp
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The expired value, or `None` if the oldest observation is still in the window
+    pub fn pop_expired(&mut self, now: u64) -> Option<T> {
+        let &(ts, _) = self.buf.front()?;
+        if now < self.duration || ts > now - self.duration {
+            return None;
+        }
+
+        let (_, value) = self.buf.pop_front()?;
+        self.head += 1;
+
+        let cap = self.buf.capacity();
+        if cap > MIN_GROWABLE_CAPACITY && self.buf.len() <= cap / 4 {
+            self.buf.resize(Ord::max(cap / 2, MIN_GROWABLE_CAPACITY));
+        }
+
+        Some(value)
+    }
+
+    /// Resets the window, clearing all observations
+    pub fn reset(&mut self) {
+        self.buf.reset();
+        self.head = 0;
+    }
+
+    /// Returns an iterator over the values in the window, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.buf.iter().map(|(_, v)| v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expiry() {
+        let mut window = TimeWindow::new(10, None);
+        assert_eq!(window.push(0, 1), None);
+        assert_eq!(window.push(5, 2), None);
+        assert_eq!(window.push(9, 3), None);
+
+        assert_eq!(window.pop_expired(9), None);
+        assert_eq!(window.pop_expired(10), Some(1));
+        assert_eq!(window.pop_expired(10), None);
+        assert_eq!(window.head(), 1);
+
+        assert_eq!(window.pop_expired(19), Some(2));
+        assert_eq!(window.pop_expired(19), Some(3));
+        assert_eq!(window.pop_expired(19), None);
+        assert!(window.is_empty());
+        assert_eq!(window.head(), 3);
+    }
+
+    #[test]
+    fn test_grow_and_shrink() {
+        let mut window = TimeWindow::new(1_000, None);
+        for ts in 0..100 {
+            window.push(ts, ts);
+        }
+        assert_eq!(window.len(), 100);
+        assert!(window.buf.capacity() >= 100);
+        assert_eq!(
+            window.iter().copied().collect::<Vec<_>>(),
+            (0..100).collect::<Vec<_>>()
+        );
+
+        let mut expired = 0;
+        while window.pop_expired(1_095).is_some() {
+            expired += 1;
+        }
+        assert_eq!(expired, 96);
+        assert_eq!(
+            window.iter().copied().collect::<Vec<_>>(),
+            vec![96, 97, 98, 99]
+        );
+        assert!(window.buf.capacity() < 100);
+    }
+
+    #[test]
+    fn test_max_capacity() {
+        let mut window = TimeWindow::new(1_000, Some(3));
+        assert_eq!(window.push(0, 1), None);
+        assert_eq!(window.push(1, 2), None);
+        assert_eq!(window.push(2, 3), None);
+        assert_eq!(window.push(3, 4), Some(1));
+        assert_eq!(window.buf.capacity(), 3);
+        assert_eq!(window.head(), 1);
+        assert_eq!(window.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_stale_timestamp_is_clamped() {
+        let mut window = TimeWindow::new(10, None);
+        window.push(5, 1);
+        window.push(3, 2);
+        assert_eq!(window.latest(), Some(5));
+
+        assert_eq!(window.pop_expired(15), Some(1));
+        assert_eq!(window.pop_expired(15), Some(2));
+        assert!(window.is_empty());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut window = TimeWindow::new(10, None);
+        window.push(0, 1.0);
+        window.push(1, 2.0);
+        window.reset();
+        assert!(window.is_empty());
+        assert_eq!(window.head(), 0);
+        assert_eq!(window.latest(), None);
+    }
+}
@@ -0,0 +1,17 @@
+/// Method used to estimate the Value-at-Risk and Expected Shortfall of the window
+///
+/// Risk reports usually show the three estimates side by side: the historical estimate
+/// makes no distributional assumption, the Gaussian estimate only uses the mean and
+/// standard deviation, and the Cornish–Fisher estimate corrects the Gaussian quantile for
+/// the skewness and excess kurtosis of the window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VarMethod {
+    /// Empirical quantile of the window, with the expected shortfall averaging the tail
+    #[default]
+    Historical,
+    /// Normal distribution fitted to the mean and standard deviation
+    Gaussian,
+    /// Normal quantile adjusted by the Cornish–Fisher expansion for skewness and kurtosis,
+    /// also known as modified VaR
+    CornishFisher,
+}
@@ -0,0 +1,102 @@
+use num_traits::Float;
+
+use crate::rolling::RollingWma;
+
+/// # Linearly Weighted Moving Average (WMA)
+///
+/// Averages a rolling window with linearly increasing weights, the oldest value weighted 1 and
+/// the newest weighted `period`, so the average reacts faster to recent values than the
+/// equal-weight mean while still smoothing over the whole window.
+///
+/// Alongside the window sum it keeps the position-weighted sum `Σ i·x_i`. Sliding the window
+/// lowers every remaining weight by one, which amounts to subtracting the window sum, so each
+/// update is O(1) regardless of the period.
+///
+/// # Examples
+///
+/// ```
+/// use ta_statistics::WeightedMovingAverage;
+/// use assert_approx_eq::assert_approx_eq;
+///
+/// let mut wma = WeightedMovingAverage::new(4);
+/// let mut results = vec![];
+/// let inputs = [10.0, 10.5, 11.2, 10.9, 11.5, 11.9, 12.3, 12.1, 11.8, 12.5];
+/// inputs.iter().for_each(|i| {
+///     wma.next(*i).get().map(|v| results.push(v));
+/// });
+///
+/// let expected: [f64; 7] = [10.82, 11.16, 11.51, 11.88, 12.06, 12.0, 12.19];
+/// for (i, e) in expected.iter().enumerate() {
+///     assert_approx_eq!(e, results[i], 0.0001);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WeightedMovingAverage<T>(RollingWma<T>);
+
+impl<T: Default + Clone + Float> WeightedMovingAverage<T> {
+    /// Creates a new `WeightedMovingAverage` instance with the specified period
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The size of the rolling window
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The moving average object
+    pub fn new(period: usize) -> Self {
+        Self(RollingWma::new(period))
+    }
+
+    /// Returns the period of the moving average
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The period of the moving average
+    pub const fn period(&self) -> usize {
+        self.0.period()
+    }
+
+    /// Resets the moving average
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The moving average object
+    pub fn reset(&mut self) -> &mut Self {
+        self.0.reset();
+        self
+    }
+
+    /// Recomputes the moving average, could be called to avoid
+    /// prolonged compounding of floating rounding errors
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The moving average object
+    pub fn recompute(&mut self) -> &mut Self {
+        self.0.recompute();
+        self
+    }
+
+    /// Updates the moving average with a new value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The new value to be added to the rolling window
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The moving average object
+    pub fn next(&mut self, value: T) -> &mut Self {
+        self.0.next(value);
+        self
+    }
+
+    /// Returns the linearly weighted moving average
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The weighted moving average, or `None` if the window is not full
+    pub fn get(&self) -> Option<T> {
+        self.0.wma()
+    }
+}
@@ -0,0 +1,269 @@
+use num_traits::Float;
+use ordered_float::PrimitiveFloat;
+
+use crate::{rolling::WeightedMoments, utils::RbTree};
+
+/// A structure that computes weighted statistics over a fixed-size window of values.
+///
+/// `WeightedStatistics<T>` accepts a non-negative weight alongside every value, such as the
+/// traded volume of a price, and computes weighted measures that are the bread and butter of
+/// execution analytics: VWAP and its bands, volume-weighted dispersion and volume profiles.
+///
+/// Weights are frequency weights, so an observation with weight `w` counts as `w` identical
+/// observations. Weighted quantiles are served by the same order-statistic tree as the
+/// unweighted quantiles of [`SingleStatistics`](crate::SingleStatistics), with subtree weights
+/// giving O(log n) updates and lookups.
+///
+/// # Examples
+///
+/// ```
+/// use ta_statistics::WeightedStatistics;
+/// use assert_approx_eq::assert_approx_eq;
+///
+/// let mut stats = WeightedStatistics::new(3);
+/// let mut results = vec![];
+/// let inputs = [(10.0, 100.0), (11.0, 200.0), (12.0, 100.0), (11.0, 300.0), (10.0, 100.0)];
+/// inputs.iter().for_each(|&(price, volume)| {
+///     stats.next(price, volume).mean().map(|v| results.push(v));
+/// });
+///
+/// let expected: [f64; 3] = [11.0, 11.1667, 11.0];
+/// for (i, e) in expected.iter().enumerate() {
+///     assert_approx_eq!(e, results[i], 0.0001);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct WeightedStatistics<T> {
+    /// Weighted rolling moments
+    moments: WeightedMoments<T>,
+    /// Weighted median/quantiles
+    rb_tree: RbTree<T>,
+}
+
+impl<T> WeightedStatistics<T>
+where
+    T: Default + Clone + Float + PrimitiveFloat,
+{
+    /// Creates a new `WeightedStatistics` instance with the specified period.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The period of the statistics
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics object
+    pub fn new(period: usize) -> Self {
+        Self {
+            moments: WeightedMoments::new(period),
+            rb_tree: RbTree::new(period),
+        }
+    }
+
+    /// Returns the period of the statistics
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The period of the statistics
+    pub const fn period(&self) -> usize {
+        self.moments.period()
+    }
+
+    /// Resets the statistics
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub fn reset(&mut self) -> &mut Self {
+        self.moments.reset();
+        self.rb_tree.reset();
+        self
+    }
+
+    /// Recomputes the weighted statistics, could be called to avoid
+    /// prolonged compounding of floating rounding errors
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub fn recompute(&mut self) -> &mut Self {
+        self.moments.recompute();
+        self
+    }
+
+    /// Returns the Delta Degrees of Freedom
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - The Delta Degrees of Freedom
+    pub const fn ddof(&self) -> bool {
+        self.moments.ddof()
+    }
+
+    /// Sets the Delta Degrees of Freedom
+    ///
+    /// When enabled, the variance divides by the sum of weights minus one.
+    ///
+    /// # Arguments
+    ///
+    /// * `ddof` - The Delta Degrees of Freedom
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub const fn set_ddof(&mut self, ddof: bool) -> &mut Self {
+        self.moments.set_ddof(ddof);
+        self
+    }
+
+    /// Updates the weighted statistics with a new value and its weight
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The new value to be added to the time series
+    /// * `weight` - The non-negative weight of the value, such as the traded volume
+    ///
+    /// # Returns
+    ///
+    /// * `&mut Self` - The statistics object
+    pub fn next(&mut self, value: T, weight: T) -> &mut Self {
+        self.moments.next(value, weight);
+        if let Some((popped, popped_weight)) = self.moments.popped() {
+            self.rb_tree.remove_weighted(popped, popped_weight);
+        }
+        self.rb_tree.insert_weighted(value, weight);
+
+        self
+    }
+
+    /// Returns the sum of weights in the rolling window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The sum of weights in the window, or `None` if the window is not full
+    pub fn total_weight(&self) -> Option<T> {
+        self.moments.total_weight()
+    }
+
+    /// Returns the weighted sum of values in the rolling window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The sum of `weight * value` in the window, or `None` if the window is not full
+    pub fn sum(&self) -> Option<T> {
+        self.moments.sum()
+    }
+
+    /// Returns the weighted mean of values in the rolling window
+    ///
+    /// With prices as values and traded volumes as weights this is the rolling VWAP.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The weighted mean, or `None` if the window is not full or the total weight is zero
+    pub fn mean(&self) -> Option<T> {
+        self.moments.mean()
+    }
+
+    /// Returns the weighted variance of values in the rolling window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The weighted variance, or `None` if the window is not full or the total weight is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::WeightedStatistics;
+    /// use assert_approx_eq::assert_approx_eq;
+    ///
+    /// let mut stats = WeightedStatistics::new(3);
+    /// let mut results = vec![];
+    /// let inputs = [(10.0, 100.0), (11.0, 200.0), (12.0, 100.0), (11.0, 300.0), (10.0, 100.0)];
+    /// inputs.iter().for_each(|&(price, volume)| {
+    ///     stats.next(price, volume).variance().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 3] = [0.5, 0.1389, 0.4];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    ///
+    /// stats.reset().set_ddof(true);
+    /// results = vec![];
+    /// inputs.iter().for_each(|&(price, volume)| {
+    ///     stats.next(price, volume).variance().map(|v| results.push(v));
+    /// });
+    ///
+    /// let expected: [f64; 3] = [0.5013, 0.1391, 0.4008];
+    /// for (i, e) in expected.iter().enumerate() {
+    ///     assert_approx_eq!(e, results[i], 0.0001);
+    /// }
+    /// ```
+    pub fn variance(&self) -> Option<T> {
+        self.moments.variance()
+    }
+
+    /// Returns the weighted standard deviation of values in the rolling window
+    ///
+    /// Bands at `mean() ± k * stddev()` give the usual VWAP bands.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The weighted standard deviation, or `None` if the window is not full or the total weight is zero
+    pub fn stddev(&self) -> Option<T> {
+        self.moments.stddev()
+    }
+
+    /// Returns the z-score of the most recent value against the weighted mean and standard deviation
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The z-score, or `None` if the window is not full or the standard deviation is zero
+    pub fn zscore(&self) -> Option<T> {
+        self.moments.zscore()
+    }
+
+    /// Returns the weighted median of values in the rolling window
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The weighted median, or `None` if the window is not full
+    pub fn median(&self) -> Option<T> {
+        self.quantile(0.5)
+    }
+
+    /// Returns the weighted quantile of values in the rolling window
+    ///
+    /// The weighted quantile is the smallest value whose cumulative weight, in ascending
+    /// order of values, reaches `q` times the total weight.
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - The quantile to calculate
+    ///
+    /// # Returns
+    ///
+    /// * `Option<T>` - The weighted quantile, or `None` if the window is not full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ta_statistics::WeightedStatistics;
+    ///
+    /// let mut stats = WeightedStatistics::new(3);
+    /// let mut results = vec![];
+    /// let inputs = [(10.0, 100.0), (11.0, 200.0), (12.0, 100.0), (11.0, 300.0), (10.0, 100.0)];
+    /// inputs.iter().for_each(|&(price, volume)| {
+    ///     stats.next(price, volume).quantile(0.2).map(|v| results.push(v));
+    /// });
+    ///
+    /// assert_eq!(&results, &[10.0, 11.0, 10.0]);
+    /// ```
+    pub fn quantile(&self, q: f64) -> Option<T> {
+        if !self.moments.is_ready() || !(0.0..=1.0).contains(&q) {
+            return None;
+        }
+        self.rb_tree.weighted_quantile(q)
+    }
+}